@@ -0,0 +1,29 @@
+use crate::activity::AndroidApp;
+use framework_core::common::{ClipboardBackend, ClipboardError};
+
+/// Accesses the system clipboard through the activity's ClipboardManager
+pub struct AndroidClipboard {
+    app: AndroidApp,
+}
+
+impl AndroidClipboard {
+    pub fn new(app: AndroidApp) -> Self {
+        Self { app }
+    }
+}
+
+impl ClipboardBackend for AndroidClipboard {
+    fn text(&mut self) -> Result<String, ClipboardError> {
+        crate::util::clipboard_text(&self.app).ok_or(ClipboardError::Empty)
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        if crate::util::set_clipboard_text(&self.app, text) {
+            Ok(())
+        } else {
+            Err(ClipboardError::Unavailable(String::from(
+                "ClipboardManager rejected the clip",
+            )))
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::view::AndroidActivity;
+use jni::objects::{JObject, JString, JValueGen};
+use jni::JNIEnv;
+
+/// https://developer.android.com/reference/android/content/ClipData
+///
+/// API level 11
+pub struct AndroidClipData<'a> {
+    j_object: JObject<'a>,
+}
+
+impl<'a> AndroidClipData<'a> {
+    /// https://developer.android.com/reference/android/content/ClipData#newPlainText(java.lang.CharSequence,%20java.lang.CharSequence)
+    ///
+    /// API level 11
+    pub fn new_plain_text(
+        jni_env: &mut JNIEnv<'a>,
+        label: &str,
+        text: &str,
+    ) -> jni::errors::Result<Self> {
+        let label = jni_env.new_string(label)?;
+        let text = jni_env.new_string(text)?;
+
+        let owned_obj = jni_env.call_static_method(
+            "android/content/ClipData",
+            "newPlainText",
+            "(Ljava/lang/CharSequence;Ljava/lang/CharSequence;)Landroid/content/ClipData;",
+            &[JValueGen::Object(&label), JValueGen::Object(&text)],
+        )?;
+
+        Ok(Self {
+            j_object: owned_obj.l()?,
+        })
+    }
+
+    /// https://developer.android.com/reference/android/content/ClipData#getItemCount()
+    ///
+    /// API level 11
+    pub fn get_item_count(&self, jni_env: &mut JNIEnv<'a>) -> jni::errors::Result<jni::sys::jint> {
+        let owned_obj = jni_env.call_method(&self.j_object, "getItemCount", "()I", &[])?;
+
+        owned_obj.i()
+    }
+
+    /// https://developer.android.com/reference/android/content/ClipData#getItemAt(int)
+    /// and https://developer.android.com/reference/android/content/ClipData.Item#coerceToText(android.content.Context)
+    ///
+    /// API level 11
+    pub fn get_item_text(
+        &self,
+        jni_env: &mut JNIEnv<'a>,
+        activity: &AndroidActivity<'a>,
+        index: jni::sys::jint,
+    ) -> jni::errors::Result<String> {
+        let item = jni_env
+            .call_method(
+                &self.j_object,
+                "getItemAt",
+                "(I)Landroid/content/ClipData$Item;",
+                &[JValueGen::Int(index)],
+            )?
+            .l()?;
+
+        let char_sequence = jni_env
+            .call_method(
+                &item,
+                "coerceToText",
+                "(Landroid/content/Context;)Ljava/lang/CharSequence;",
+                &[JValueGen::Object(activity.as_obj())],
+            )?
+            .l()?;
+
+        let owned_obj =
+            jni_env.call_method(&char_sequence, "toString", "()Ljava/lang/String;", &[])?;
+        let j_string = JString::from(owned_obj.l()?);
+        let text = jni_env.get_string(&j_string)?.into();
+
+        Ok(text)
+    }
+
+    pub(crate) fn as_obj(&self) -> &JObject<'a> {
+        &self.j_object
+    }
+}
+
+impl<'a> From<JObject<'a>> for AndroidClipData<'a> {
+    fn from(j_object: JObject<'a>) -> Self {
+        Self { j_object }
+    }
+}
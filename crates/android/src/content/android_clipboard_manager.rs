@@ -0,0 +1,58 @@
+use super::AndroidClipData;
+use jni::objects::{JObject, JValueGen};
+use jni::JNIEnv;
+
+/// https://developer.android.com/reference/android/content/ClipboardManager
+///
+/// API level 11
+pub struct AndroidClipboardManager<'a> {
+    j_object: JObject<'a>,
+}
+
+impl<'a> AndroidClipboardManager<'a> {
+    /// https://developer.android.com/reference/android/content/ClipboardManager#getPrimaryClip()
+    ///
+    /// API level 11
+    pub fn get_primary_clip(
+        &self,
+        jni_env: &mut JNIEnv<'a>,
+    ) -> jni::errors::Result<Option<AndroidClipData<'a>>> {
+        let owned_obj = jni_env.call_method(
+            &self.j_object,
+            "getPrimaryClip",
+            "()Landroid/content/ClipData;",
+            &[],
+        )?;
+
+        let j_object = owned_obj.l()?;
+
+        if j_object.is_null() {
+            return Ok(None);
+        }
+
+        Ok(Some(AndroidClipData::from(j_object)))
+    }
+
+    /// https://developer.android.com/reference/android/content/ClipboardManager#setPrimaryClip(android.content.ClipData)
+    ///
+    /// API level 11
+    pub fn set_primary_clip(
+        &self,
+        jni_env: &mut JNIEnv<'a>,
+        clip_data: &AndroidClipData<'a>,
+    ) -> jni::errors::Result<()> {
+        jni_env.call_method(
+            &self.j_object,
+            "setPrimaryClip",
+            "(Landroid/content/ClipData;)V",
+            &[JValueGen::Object(clip_data.as_obj())],
+        )?;
+        Ok(())
+    }
+}
+
+impl<'a> From<JObject<'a>> for AndroidClipboardManager<'a> {
+    fn from(j_object: JObject<'a>) -> Self {
+        Self { j_object }
+    }
+}
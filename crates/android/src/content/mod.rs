@@ -0,0 +1,5 @@
+mod android_clip_data;
+mod android_clipboard_manager;
+
+pub use android_clip_data::*;
+pub use android_clipboard_manager::*;
@@ -1,9 +1,12 @@
+mod android_clipboard;
 mod android_jvm;
 
+pub mod content;
 pub mod graphics;
 pub mod util;
 pub mod view;
 
 pub use ::android_activity as activity;
+pub use android_clipboard::*;
 pub use android_jvm::*;
 pub use jni;
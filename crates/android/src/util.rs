@@ -1,6 +1,8 @@
 use crate::activity::AndroidApp;
+use crate::content::AndroidClipData;
 use crate::view::*;
 use crate::AndroidJVM;
+use std::path::PathBuf;
 
 pub fn show_system_bars(app: &AndroidApp) {
     let vm = AndroidJVM::from(app);
@@ -121,3 +123,75 @@ pub fn is_this_device_a_controller(app: &AndroidApp, id: i32) -> bool {
 
     is_controller
 }
+
+pub struct InputDeviceInfo {
+    pub name: String,
+    pub vendor_id: u16,
+    pub product_id: u16,
+}
+
+pub fn input_device_info(app: &AndroidApp, id: i32) -> Option<InputDeviceInfo> {
+    let vm = AndroidJVM::from(app);
+    let mut info = None;
+
+    vm.wrap(|jni_env| {
+        let device = AndroidInputDevice::get_device(jni_env, id)?;
+
+        info = Some(InputDeviceInfo {
+            name: device.get_name(jni_env)?,
+            vendor_id: device.get_vendor_id(jni_env)? as u16,
+            product_id: device.get_product_id(jni_env)? as u16,
+        });
+
+        Ok(())
+    });
+
+    info
+}
+
+/// None if the clipboard is empty or inaccessible
+pub fn clipboard_text(app: &AndroidApp) -> Option<String> {
+    let vm = AndroidJVM::from(app);
+    let mut text = None;
+
+    vm.wrap(|jni_env| {
+        let activity = AndroidActivity::from(app);
+        let clipboard_manager = activity.get_clipboard_manager(jni_env)?;
+
+        let Some(clip_data) = clipboard_manager.get_primary_clip(jni_env)? else {
+            return Ok(());
+        };
+
+        if clip_data.get_item_count(jni_env)? > 0 {
+            text = Some(clip_data.get_item_text(jni_env, &activity, 0)?);
+        }
+
+        Ok(())
+    });
+
+    text
+}
+
+/// Returns false if the clipboard is inaccessible
+pub fn set_clipboard_text(app: &AndroidApp, text: &str) -> bool {
+    let vm = AndroidJVM::from(app);
+    let mut success = false;
+
+    vm.wrap(|jni_env| {
+        let activity = AndroidActivity::from(app);
+        let clipboard_manager = activity.get_clipboard_manager(jni_env)?;
+        let clip_data = AndroidClipData::new_plain_text(jni_env, "", text)?;
+
+        clipboard_manager.set_primary_clip(jni_env, &clip_data)?;
+        success = true;
+
+        Ok(())
+    });
+
+    success
+}
+
+/// The app's private internal storage directory, the same as `Context.getFilesDir()`
+pub fn internal_storage_path(app: &AndroidApp) -> Option<PathBuf> {
+    app.internal_data_path()
+}
@@ -1,5 +1,6 @@
 use super::*;
 use crate::activity::AndroidApp;
+use crate::content::AndroidClipboardManager;
 use jni::objects::JObject;
 use jni::JNIEnv;
 
@@ -21,6 +22,26 @@ impl<'a> AndroidActivity<'a> {
         Ok(AndroidWindow::from(JObject::try_from(owned_obj)?))
     }
 
+    /// https://developer.android.com/reference/android/content/Context#getSystemService(java.lang.String)
+    /// with https://developer.android.com/reference/android/content/Context#CLIPBOARD_SERVICE
+    ///
+    /// API level 11
+    pub fn get_clipboard_manager(
+        &self,
+        jni_env: &mut JNIEnv<'a>,
+    ) -> jni::errors::Result<AndroidClipboardManager<'a>> {
+        let service_name = jni_env.new_string("clipboard")?;
+
+        let owned_obj = jni_env.call_method(
+            &self.j_object,
+            "getSystemService",
+            "(Ljava/lang/String;)Ljava/lang/Object;",
+            &[jni::objects::JValueGen::Object(&service_name)],
+        )?;
+
+        Ok(AndroidClipboardManager::from(owned_obj.l()?))
+    }
+
     /// https://developer.android.com/reference/android/app/Activity#getWindow()
     ///
     /// API level 21
@@ -28,6 +49,10 @@ impl<'a> AndroidActivity<'a> {
         jni_env.call_method(&self.j_object, "finish", "()V", &[])?;
         Ok(())
     }
+
+    pub(crate) fn as_obj(&self) -> &JObject<'a> {
+        &self.j_object
+    }
 }
 
 impl<'a> From<&AndroidApp> for AndroidActivity<'a> {
@@ -1,4 +1,4 @@
-use jni::objects::JObject;
+use jni::objects::{JObject, JString};
 use jni::JNIEnv;
 
 /// https://developer.android.com/reference/android/view/InputDevice
@@ -36,6 +36,36 @@ impl<'a> AndroidInputDevice<'a> {
 
         owned_obj.i()
     }
+
+    /// https://developer.android.com/reference/android/view/InputDevice#getName()
+    ///
+    /// API level 9
+    pub fn get_name(&self, jni_env: &mut JNIEnv<'a>) -> jni::errors::Result<String> {
+        let owned_obj =
+            jni_env.call_method(&self.j_object, "getName", "()Ljava/lang/String;", &[])?;
+        let j_string = JString::from(owned_obj.l()?);
+        let name = jni_env.get_string(&j_string)?.into();
+
+        Ok(name)
+    }
+
+    /// https://developer.android.com/reference/android/view/InputDevice#getVendorId()
+    ///
+    /// API level 19
+    pub fn get_vendor_id(&self, jni_env: &mut JNIEnv<'a>) -> jni::errors::Result<jni::sys::jint> {
+        let owned_obj = jni_env.call_method(&self.j_object, "getVendorId", "()I", &[])?;
+
+        owned_obj.i()
+    }
+
+    /// https://developer.android.com/reference/android/view/InputDevice#getProductId()
+    ///
+    /// API level 19
+    pub fn get_product_id(&self, jni_env: &mut JNIEnv<'a>) -> jni::errors::Result<jni::sys::jint> {
+        let owned_obj = jni_env.call_method(&self.j_object, "getProductId", "()I", &[])?;
+
+        owned_obj.i()
+    }
 }
 
 impl<'a> From<JObject<'a>> for AndroidInputDevice<'a> {
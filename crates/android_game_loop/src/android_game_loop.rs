@@ -1,9 +1,11 @@
+use crate::android_game_window::AndroidGameWindow;
 use crate::android_rumble_pack::AndroidRumblePack;
-use crate::{android_game_window::AndroidGameWindow, event_translation::translate_input_event};
+use crate::event_translation::{translate_input_event, ControllerIdentifier};
 use android::activity::{MainEvent as AndroidMainEvent, PollEvent as AndroidPollEvent};
 use android_activity::input::Axis as AndroidAxis;
 use android_activity::InputStatus as AndroidInputStatus;
 use framework_core::runtime::*;
+use input::ControllerInfo;
 use math::UVec2;
 use std::future::Future;
 use std::time::Instant;
@@ -63,6 +65,8 @@ async fn run(
 
     // init the window and runtime
     let window = AndroidGameWindow::new(window_config).await?;
+    let mut controller_identifier =
+        ControllerIdentifier::new(runtime_params.controller_mappings.clone());
     let mut game_runtime = GameRuntimeCore::new(Box::new(window), runtime_params)?;
 
     let mut combining_accent = None;
@@ -70,7 +74,9 @@ async fn run(
     game_runtime.push_event(GameWindowEvent::InputEvent(
         InputEvent::ControllerConnected {
             controller_id: 0,
+            info: ControllerInfo::default(),
             rumble_pack: Box::new(AndroidRumblePack),
+            device: None,
         },
     ));
 
@@ -82,6 +88,11 @@ async fn run(
         if timeout.is_zero() {
             game_runtime.tick();
 
+            // secondary windows are unsupported
+            for request in game_runtime.take_window_requests() {
+                game_runtime.reject_window(request.id);
+            }
+
             // update timeout
             let wake_instant = game_runtime.target_wake_instant();
             timeout = wake_instant.saturating_duration_since(Instant::now());
@@ -107,6 +118,10 @@ async fn run(
                             game_runtime.push_event(GameWindowEvent::Resized(size));
                         }
                     }
+                    AndroidMainEvent::ConfigChanged { .. } => {
+                        let scale_factor = game_runtime.game_io().window().scale_factor();
+                        game_runtime.push_event(GameWindowEvent::ScaleFactorChanged(scale_factor));
+                    }
                     AndroidMainEvent::Destroy => {
                         terminated = true;
                     }
@@ -130,6 +145,7 @@ async fn run(
                     translate_input_event(
                         &app,
                         game_runtime.game_io().window(),
+                        &mut controller_identifier,
                         &mut combining_accent,
                         event,
                         |translated| {
@@ -1,9 +1,12 @@
 use crate::window_handle::AndroidWindowHandle;
 use crate::AndroidPlatformApp;
-use framework_core::common::GameWindow;
-use framework_core::graphics::{wgpu, Color, GraphicsContext, HasGraphicsContext, RenderTarget};
+use framework_core::common::{ClipboardBackend, GameWindow};
+use framework_core::graphics::{
+    wgpu, AcquiredSurfaceTexture, Color, GraphicsContext, HasGraphicsContext, RenderTarget,
+};
 use framework_core::runtime::{GameWindowConfig, GameWindowLifecycle};
 use math::{IVec2, Rect, UVec2};
+use std::path::PathBuf;
 
 pub(crate) struct AndroidGameWindow {
     pub(crate) app: AndroidPlatformApp,
@@ -94,10 +97,33 @@ impl GameWindowLifecycle for AndroidGameWindow {
         }
     }
 
+    fn recreate_graphics(&mut self) -> anyhow::Result<()> {
+        let graphics =
+            framework_core::async_task::block_on(self.graphics.recreate(Some(&self.surface)))?;
+
+        self.replace_graphics(graphics);
+        Ok(())
+    }
+
+    fn replace_graphics(&mut self, graphics: GraphicsContext) {
+        self.graphics = graphics;
+        self.surface
+            .configure(self.graphics.device(), &self.surface_config);
+    }
+
     fn acquire_render_target(&mut self) -> Option<RenderTarget> {
-        let wgpu::CurrentSurfaceTexture::Success(surface_texture) =
-            self.surface.get_current_texture()
-        else {
+        let mut acquired = self
+            .graphics
+            .acquire_surface_texture(&self.surface, &self.surface_config);
+
+        if matches!(acquired, AcquiredSurfaceTexture::Lost) {
+            self.rebuild_surface();
+            acquired = self
+                .graphics
+                .acquire_surface_texture(&self.surface, &self.surface_config);
+        }
+
+        let AcquiredSurfaceTexture::Ready(surface_texture) = acquired else {
             return None;
         };
 
@@ -143,6 +169,14 @@ impl GameWindowLifecycle for AndroidGameWindow {
     }
 
     fn set_ime_cursor_area(&mut self, _rect: Rect) {}
+
+    fn storage_directory(&self) -> Option<PathBuf> {
+        android::util::internal_storage_path(&self.app)
+    }
+
+    fn create_clipboard(&self) -> Box<dyn ClipboardBackend> {
+        Box::new(android::AndroidClipboard::new(self.app.clone()))
+    }
 }
 
 impl GameWindow for AndroidGameWindow {
@@ -172,6 +206,15 @@ impl GameWindow for AndroidGameWindow {
         self.size
     }
 
+    fn scale_factor(&self) -> f32 {
+        // 160 dpi is the baseline density on Android
+        self.app
+            .config()
+            .density()
+            .map(|density| density as f32 / 160.0)
+            .unwrap_or(1.0)
+    }
+
     fn request_size(&mut self, _size: UVec2) {
         log::warn!("AndroidGameWindow::request_size() is unimplemented");
     }
@@ -1,5 +1,8 @@
-use crate::key_translation::{translate_android_button, translate_android_key};
-use android::util::is_this_device_a_controller;
+use crate::android_rumble_pack::AndroidRumblePack;
+use crate::key_translation::{
+    android_button_index, translate_android_button, translate_android_key,
+};
+use android::util::{input_device_info, is_this_device_a_controller};
 use android_activity::input::{
     Axis as AndroidAxis, InputEvent as AndroidInputEvent, KeyAction as AndroidKeyAction,
     KeyEvent as AndroidKeyEvent, KeyMapChar as AndroidKeyMapChar, Keycode as AndroidKeyCode,
@@ -8,12 +11,81 @@ use android_activity::input::{
 use android_activity::AndroidApp;
 use framework_core::common::GameWindow;
 use framework_core::runtime::InputEvent;
-use input::{AnalogAxis, Touch, TouchPhase};
+use input::{
+    AnalogAxis, Button, ControllerInfo, ControllerMapping, ControllerMappingDatabase, Key,
+    KeyInput, MouseWheelDelta, Touch, TouchPhase,
+};
 use math::Vec2;
 
+/// Android doesn't report connections, so controllers are identified by the first event from each device
+pub(crate) struct ControllerIdentifier {
+    mappings: ControllerMappingDatabase,
+    device_id: Option<i32>,
+    mapping: Option<ControllerMapping>,
+}
+
+impl ControllerIdentifier {
+    pub(crate) fn new(mappings: ControllerMappingDatabase) -> Self {
+        Self {
+            mappings,
+            device_id: None,
+            mapping: None,
+        }
+    }
+
+    fn identify(&mut self, app: &AndroidApp, device_id: i32, push: &mut impl FnMut(InputEvent)) {
+        if self.device_id == Some(device_id) {
+            return;
+        }
+
+        self.device_id = Some(device_id);
+
+        let info = input_device_info(app, device_id)
+            .map(|device| {
+                // 0 is used when the id is unknown
+                let vendor_id = Some(device.vendor_id).filter(|&id| id != 0);
+                let product_id = Some(device.product_id).filter(|&id| id != 0);
+
+                ControllerInfo::new(device.name, vendor_id, product_id)
+            })
+            .unwrap_or_default();
+
+        self.mapping = info
+            .guid
+            .as_ref()
+            .and_then(|guid| self.mappings.find(guid, "Android"))
+            .cloned();
+
+        // all controllers share the same id, this updates the existing controller
+        push(InputEvent::ControllerConnected {
+            controller_id: 0,
+            info,
+            rumble_pack: Box::new(AndroidRumblePack),
+            device: None,
+        });
+    }
+
+    fn translate_button(&self, android_keycode: AndroidKeyCode, mut push: impl FnMut(Button)) {
+        let Some(mapping) = &self.mapping else {
+            translate_android_button(android_keycode, push);
+            return;
+        };
+
+        match android_button_index(android_keycode) {
+            Some(index) => {
+                if let Some(button) = mapping.button_for_index(index) {
+                    push(button);
+                }
+            }
+            None => translate_android_button(android_keycode, push),
+        }
+    }
+}
+
 pub(crate) fn translate_input_event(
     app: &AndroidApp,
     window: &dyn GameWindow,
+    controller_identifier: &mut ControllerIdentifier,
     combining_accent: &mut Option<char>,
     event: &AndroidInputEvent,
     mut push: impl FnMut(InputEvent),
@@ -21,11 +93,16 @@ pub(crate) fn translate_input_event(
     match event {
         AndroidInputEvent::KeyEvent(key_event) => {
             let key_code = key_event.key_code();
+            let is_controller = is_this_device_a_controller(app, key_event.device_id());
+
+            if is_controller {
+                controller_identifier.identify(app, key_event.device_id(), &mut push);
+            }
 
             // check for controller input
             match key_event.action() {
                 AndroidKeyAction::Down => {
-                    translate_android_button(key_code, |button| {
+                    controller_identifier.translate_button(key_code, |button| {
                         push(InputEvent::ControllerButtonDown {
                             controller_id: 0,
                             button,
@@ -33,7 +110,7 @@ pub(crate) fn translate_input_event(
                     });
                 }
                 AndroidKeyAction::Up => {
-                    translate_android_button(key_code, |button| {
+                    controller_identifier.translate_button(key_code, |button| {
                         push(InputEvent::ControllerButtonUp {
                             controller_id: 0,
                             button,
@@ -43,28 +120,36 @@ pub(crate) fn translate_input_event(
                 _ => {}
             }
 
-            if !is_this_device_a_controller(app, key_event.device_id())
-                || key_event.source() == AndroidInputSource::Keyboard
-            {
+            if !is_controller || key_event.source() == AndroidInputSource::Keyboard {
+                let combined_key_char =
+                    character_map_and_combine_key(&app, key_event, combining_accent);
+
+                // key codes follow key positions, the character map applies the layout
+                let key_input = translate_android_key(key_code).map(|physical| {
+                    let logical = match combined_key_char {
+                        Some(AndroidKeyMapChar::Unicode(c)) => Key::from_char(c),
+                        _ => None,
+                    };
+
+                    KeyInput::new(physical, logical.unwrap_or(physical))
+                });
+
                 // key down + up events
                 match key_event.action() {
                     AndroidKeyAction::Down => {
-                        if let Some(key) = translate_android_key(key_code) {
-                            push(InputEvent::KeyDown(key));
+                        if let Some(key_input) = key_input {
+                            push(InputEvent::KeyDown(key_input));
                         }
                     }
                     AndroidKeyAction::Up => {
-                        if let Some(key) = translate_android_key(key_code) {
-                            push(InputEvent::KeyUp(key));
+                        if let Some(key_input) = key_input {
+                            push(InputEvent::KeyUp(key_input));
                         }
                     }
                     _ => {}
                 }
 
                 // text events
-                let combined_key_char =
-                    character_map_and_combine_key(&app, key_event, combining_accent);
-
                 if matches!(
                     key_event.action(),
                     AndroidKeyAction::Down | AndroidKeyAction::Multiple
@@ -85,6 +170,8 @@ pub(crate) fn translate_input_event(
             | AndroidInputSource::Joystick
                 if motion_event.action() == AndroidMotionAction::Move =>
             {
+                controller_identifier.identify(app, motion_event.device_id(), &mut push);
+
                 const AXIS_LIST: &[(AndroidAxis, AnalogAxis, f32)] = &[
                     (AndroidAxis::X, AnalogAxis::LeftStickX, 1.0),
                     (AndroidAxis::Y, AnalogAxis::LeftStickY, -1.0),
@@ -107,6 +194,42 @@ pub(crate) fn translate_input_event(
                 }
             }
 
+            AndroidInputSource::Mouse => match motion_event.action() {
+                AndroidMotionAction::Scroll => {
+                    for pointer in motion_event.pointers() {
+                        let delta = Vec2::new(
+                            pointer.axis_value(AndroidAxis::Hscroll),
+                            pointer.axis_value(AndroidAxis::Vscroll),
+                        );
+
+                        push(InputEvent::MouseWheel(MouseWheelDelta::Lines(delta)));
+                    }
+                }
+                AndroidMotionAction::Move | AndroidMotionAction::HoverMove => {
+                    for pointer in motion_event.pointers() {
+                        let delta = Vec2::new(
+                            pointer.axis_value(AndroidAxis::RelativeX),
+                            -pointer.axis_value(AndroidAxis::RelativeY),
+                        );
+
+                        if delta != Vec2::ZERO {
+                            push(InputEvent::MouseMotion(delta));
+                        }
+                    }
+                }
+                _ => {}
+            },
+
+            AndroidInputSource::MouseRelative => {
+                // positions are already relative while the pointer is captured
+                for pointer in motion_event.pointers() {
+                    push(InputEvent::MouseMotion(Vec2::new(
+                        pointer.x(),
+                        -pointer.y(),
+                    )));
+                }
+            }
+
             AndroidInputSource::Touchscreen => {
                 let action_pointer_index = motion_event.pointer_index();
 
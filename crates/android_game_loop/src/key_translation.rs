@@ -153,3 +153,51 @@ pub(super) fn translate_android_button(
         _ => {}
     }
 }
+
+/// The button index SDL uses for Android keycodes, used to apply `gamecontrollerdb.txt` mappings
+///
+/// Based on `keycode_to_SDL` in SDL's `SDL_sysjoystick.c`
+pub(super) fn android_button_index(android_keycode: AndroidKeycode) -> Option<u32> {
+    let index = match android_keycode {
+        AndroidKeycode::ButtonA => 0,
+        AndroidKeycode::ButtonB => 1,
+        AndroidKeycode::ButtonX => 2,
+        AndroidKeycode::ButtonY => 3,
+        AndroidKeycode::Back | AndroidKeycode::ButtonSelect => 4,
+        AndroidKeycode::ButtonMode => 5,
+        AndroidKeycode::ButtonStart => 6,
+        AndroidKeycode::ButtonThumbl => 7,
+        AndroidKeycode::ButtonThumbr => 8,
+        AndroidKeycode::ButtonL1 => 9,
+        AndroidKeycode::ButtonR1 => 10,
+        AndroidKeycode::DpadUp => 11,
+        AndroidKeycode::DpadDown => 12,
+        AndroidKeycode::DpadLeft => 13,
+        AndroidKeycode::DpadRight => 14,
+        AndroidKeycode::ButtonL2 => 21,
+        AndroidKeycode::ButtonR2 => 22,
+        AndroidKeycode::ButtonC => 23,
+        AndroidKeycode::ButtonZ => 24,
+        AndroidKeycode::Button1
+        | AndroidKeycode::Button2
+        | AndroidKeycode::Button3
+        | AndroidKeycode::Button4
+        | AndroidKeycode::Button5
+        | AndroidKeycode::Button6
+        | AndroidKeycode::Button7
+        | AndroidKeycode::Button8
+        | AndroidKeycode::Button9
+        | AndroidKeycode::Button10
+        | AndroidKeycode::Button11
+        | AndroidKeycode::Button12
+        | AndroidKeycode::Button13
+        | AndroidKeycode::Button14
+        | AndroidKeycode::Button15
+        | AndroidKeycode::Button16 => {
+            26 + u32::from(android_keycode) - u32::from(AndroidKeycode::Button1)
+        }
+        _ => return None,
+    };
+
+    Some(index)
+}
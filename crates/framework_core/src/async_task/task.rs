@@ -27,6 +27,14 @@ impl<T> AsyncTask<T> {
     }
 }
 
+impl<T> std::fmt::Debug for AsyncTask<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsyncTask")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
 impl<T> Future for AsyncTask<T> {
     type Output = T;
 
@@ -0,0 +1,26 @@
+use super::{ClipboardError, ClipboardImage};
+use std::future::Future;
+use std::pin::Pin;
+
+pub type ClipboardTextFuture = Pin<Box<dyn Future<Output = Result<String, ClipboardError>>>>;
+
+/// Platform specific clipboard access used by `GameInputManager`
+pub trait ClipboardBackend {
+    fn text(&mut self) -> Result<String, ClipboardError>;
+
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError>;
+
+    fn image(&mut self) -> Result<ClipboardImage, ClipboardError> {
+        Err(ClipboardError::Unsupported)
+    }
+
+    fn set_image(&mut self, _image: &ClipboardImage) -> Result<(), ClipboardError> {
+        Err(ClipboardError::Unsupported)
+    }
+
+    /// For platforms that only allow asynchronous reads, resolves immediately by default
+    fn read_text_async(&mut self) -> ClipboardTextFuture {
+        let result = self.text();
+        Box::pin(async move { result })
+    }
+}
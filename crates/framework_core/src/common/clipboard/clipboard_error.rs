@@ -0,0 +1,31 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClipboardError {
+    /// The clipboard is empty or doesn't hold the requested format
+    Empty,
+    /// The platform doesn't support the operation, such as images on web
+    Unsupported,
+    /// The platform only allows asynchronous reads, see `GameIO::read_clipboard_text()`
+    RequiresAsync,
+    /// The platform refused access, such as a browser permission being denied
+    Unavailable(String),
+}
+
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClipboardError::Empty => write!(f, "Clipboard is empty or holds a different format"),
+            ClipboardError::Unsupported => write!(f, "Clipboard operation is unsupported"),
+            ClipboardError::RequiresAsync => {
+                write!(
+                    f,
+                    "Clipboard can only be read asynchronously on this platform"
+                )
+            }
+            ClipboardError::Unavailable(reason) => write!(f, "Clipboard unavailable: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ClipboardError {}
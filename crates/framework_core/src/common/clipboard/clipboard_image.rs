@@ -0,0 +1,19 @@
+use math::UVec2;
+
+/// Unpremultiplied RGBA8 pixels, rows are stored top to bottom
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardImage {
+    pub size: UVec2,
+    pub rgba: Vec<u8>,
+}
+
+impl ClipboardImage {
+    /// Returns None if the data doesn't match the size
+    pub fn new(size: UVec2, rgba: Vec<u8>) -> Option<Self> {
+        if rgba.len() != size.x as usize * size.y as usize * 4 {
+            return None;
+        }
+
+        Some(Self { size, rgba })
+    }
+}
@@ -0,0 +1,47 @@
+use cfg_macros::*;
+
+mod clipboard_backend;
+mod clipboard_error;
+mod clipboard_image;
+mod unavailable_clipboard;
+
+pub use clipboard_backend::*;
+pub use clipboard_error::*;
+pub use clipboard_image::*;
+pub use unavailable_clipboard::*;
+
+cfg_desktop! {
+  mod native_clipboard;
+
+  pub use native_clipboard::*;
+}
+
+cfg_web! {
+  mod web_clipboard;
+
+  pub use web_clipboard::*;
+}
+
+/// The platform's clipboard, falls back to `UnavailableClipboard` on failure
+#[cfg(not(any(target_os = "android", target_arch = "wasm32")))]
+pub fn default_clipboard() -> Box<dyn ClipboardBackend> {
+    match NativeClipboard::new() {
+        Ok(clipboard) => Box::new(clipboard),
+        Err(err) => {
+            logging::log::error!("{err}");
+            Box::new(UnavailableClipboard)
+        }
+    }
+}
+
+/// The platform's clipboard
+#[cfg(target_arch = "wasm32")]
+pub fn default_clipboard() -> Box<dyn ClipboardBackend> {
+    Box::new(WebClipboard)
+}
+
+/// Android requires the app to access the clipboard, see `GameWindowLifecycle::create_clipboard()`
+#[cfg(target_os = "android")]
+pub fn default_clipboard() -> Box<dyn ClipboardBackend> {
+    Box::new(UnavailableClipboard)
+}
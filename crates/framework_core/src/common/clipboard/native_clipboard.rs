@@ -0,0 +1,54 @@
+use super::*;
+use math::UVec2;
+use std::borrow::Cow;
+
+/// The system clipboard on desktop platforms
+pub struct NativeClipboard {
+    clipboard: arboard::Clipboard,
+}
+
+impl NativeClipboard {
+    pub fn new() -> Result<Self, ClipboardError> {
+        let clipboard = arboard::Clipboard::new().map_err(translate_error)?;
+
+        Ok(Self { clipboard })
+    }
+}
+
+impl ClipboardBackend for NativeClipboard {
+    fn text(&mut self) -> Result<String, ClipboardError> {
+        self.clipboard.get_text().map_err(translate_error)
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        self.clipboard.set_text(text).map_err(translate_error)
+    }
+
+    fn image(&mut self) -> Result<ClipboardImage, ClipboardError> {
+        let image = self.clipboard.get_image().map_err(translate_error)?;
+        let size = UVec2::new(image.width as u32, image.height as u32);
+
+        ClipboardImage::new(size, image.bytes.into_owned())
+            .ok_or_else(|| ClipboardError::Unavailable(String::from("Malformed image")))
+    }
+
+    fn set_image(&mut self, image: &ClipboardImage) -> Result<(), ClipboardError> {
+        let image_data = arboard::ImageData {
+            width: image.size.x as usize,
+            height: image.size.y as usize,
+            bytes: Cow::Borrowed(&image.rgba),
+        };
+
+        self.clipboard
+            .set_image(image_data)
+            .map_err(translate_error)
+    }
+}
+
+fn translate_error(err: arboard::Error) -> ClipboardError {
+    match err {
+        arboard::Error::ContentNotAvailable => ClipboardError::Empty,
+        arboard::Error::ClipboardNotSupported => ClipboardError::Unsupported,
+        err => ClipboardError::Unavailable(err.to_string()),
+    }
+}
@@ -0,0 +1,14 @@
+use super::*;
+
+/// Used when the platform's clipboard can't be accessed
+pub struct UnavailableClipboard;
+
+impl ClipboardBackend for UnavailableClipboard {
+    fn text(&mut self) -> Result<String, ClipboardError> {
+        Err(ClipboardError::Unsupported)
+    }
+
+    fn set_text(&mut self, _text: &str) -> Result<(), ClipboardError> {
+        Err(ClipboardError::Unsupported)
+    }
+}
@@ -0,0 +1,47 @@
+use super::*;
+use logging::log;
+use wasm_forward::wasm_bindgen_futures::{spawn_local, JsFuture};
+use wasm_forward::web_sys;
+
+/// Uses the browser's Clipboard API, which only allows asynchronous reads
+pub struct WebClipboard;
+
+fn navigator_clipboard() -> Result<web_sys::Clipboard, ClipboardError> {
+    let window =
+        web_sys::window().ok_or_else(|| ClipboardError::Unavailable(String::from("No window")))?;
+
+    Ok(window.navigator().clipboard())
+}
+
+impl ClipboardBackend for WebClipboard {
+    fn text(&mut self) -> Result<String, ClipboardError> {
+        Err(ClipboardError::RequiresAsync)
+    }
+
+    fn set_text(&mut self, text: &str) -> Result<(), ClipboardError> {
+        let promise = navigator_clipboard()?.write_text(text);
+
+        // the browser may still reject the write, such as when the page isn't focused
+        spawn_local(async move {
+            if let Err(err) = JsFuture::from(promise).await {
+                log::error!("Failed to write to the clipboard: {err:?}");
+            }
+        });
+
+        Ok(())
+    }
+
+    fn read_text_async(&mut self) -> ClipboardTextFuture {
+        let clipboard = navigator_clipboard();
+
+        Box::pin(async move {
+            let promise = clipboard?.read_text();
+
+            let value = JsFuture::from(promise)
+                .await
+                .map_err(|err| ClipboardError::Unavailable(format!("{err:?}")))?;
+
+            value.as_string().ok_or(ClipboardError::Empty)
+        })
+    }
+}
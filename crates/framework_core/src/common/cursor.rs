@@ -0,0 +1,78 @@
+use crate::graphics::{HasGraphicsContext, Texture};
+use math::*;
+use std::future::Future;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorGrab {
+    #[default]
+    None,
+    /// Keeps the cursor within the window
+    Confined,
+    /// Holds the cursor in place, use `InputEvent::MouseMotion` for movement
+    Locked,
+}
+
+/// Standard system cursors, platforms without a matching icon will use the closest alternative
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorIcon {
+    #[default]
+    Default,
+    Pointer,
+    Text,
+    Crosshair,
+    Move,
+    Grab,
+    Grabbing,
+    Wait,
+    Progress,
+    NotAllowed,
+    ResizeHorizontal,
+    ResizeVertical,
+    /// Resizing from the top left or bottom right corner
+    ResizeNwse,
+    /// Resizing from the top right or bottom left corner
+    ResizeNesw,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomCursor {
+    pub size: UVec2,
+    /// Tightly packed RGBA8 pixels
+    pub rgba: Vec<u8>,
+    /// The pixel that's used as the cursor's position, relative to the top left
+    pub hotspot: UVec2,
+}
+
+impl CustomCursor {
+    pub fn new(size: UVec2, rgba: Vec<u8>, hotspot: UVec2) -> Self {
+        Self {
+            size,
+            rgba,
+            hotspot,
+        }
+    }
+
+    /// Expects a texture using 4 bytes per pixel in RGBA order, such as `Texture::DEFAULT_FORMAT`
+    pub fn from_texture(
+        graphics: &impl HasGraphicsContext,
+        texture: &Texture,
+        hotspot: UVec2,
+    ) -> impl Future<Output = Self> {
+        let size = texture.size();
+        let read_future = texture.read_bytes(graphics);
+
+        async move {
+            let rgba = read_future.await;
+            Self::new(size, rgba, hotspot)
+        }
+    }
+
+    /// Whether the pixel data matches the size and the hotspot is within bounds
+    pub fn is_valid(&self) -> bool {
+        let pixel_count = self.size.x as usize * self.size.y as usize;
+
+        self.size.cmpgt(UVec2::ZERO).all()
+            && self.rgba.len() == pixel_count * 4
+            && self.hotspot.cmplt(self.size).all()
+    }
+}
@@ -0,0 +1,181 @@
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDialogFilter {
+    pub name: String,
+    /// Extensions without the leading dot
+    pub extensions: Vec<String>,
+}
+
+/// Options for dialogs opened through `GameIO`, such as `GameIO::pick_file()`
+#[derive(Debug, Default, Clone)]
+pub struct FileDialog {
+    pub title: Option<String>,
+    pub directory: Option<PathBuf>,
+    /// The default file name for save dialogs
+    pub file_name: Option<String>,
+    pub filters: Vec<FileDialogFilter>,
+}
+
+impl FileDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    pub fn with_file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    pub fn with_filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.filters.push(FileDialogFilter {
+            name: name.into(),
+            extensions: extensions.iter().map(|s| s.to_string()).collect(),
+        });
+        self
+    }
+}
+
+/// A file selected through `GameIO::pick_file()` or `GameIO::pick_files()`
+#[derive(Debug, Clone)]
+pub enum PickedFile {
+    Path(PathBuf),
+    /// Browsers don't expose paths, the file is read into memory instead
+    Bytes {
+        name: String,
+        bytes: Vec<u8>,
+    },
+}
+
+impl PickedFile {
+    pub fn name(&self) -> String {
+        match self {
+            PickedFile::Path(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            PickedFile::Bytes { name, .. } => name.clone(),
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            PickedFile::Path(path) => Some(path),
+            PickedFile::Bytes { .. } => None,
+        }
+    }
+
+    pub fn read(&self) -> std::io::Result<Vec<u8>> {
+        match self {
+            PickedFile::Path(path) => std::fs::read(path),
+            PickedFile::Bytes { bytes, .. } => Ok(bytes.clone()),
+        }
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+fn create_rfd_dialog(dialog: FileDialog) -> rfd::AsyncFileDialog {
+    let mut rfd_dialog = rfd::AsyncFileDialog::new();
+
+    if let Some(title) = dialog.title {
+        rfd_dialog = rfd_dialog.set_title(title);
+    }
+
+    if let Some(directory) = dialog.directory {
+        rfd_dialog = rfd_dialog.set_directory(directory);
+    }
+
+    if let Some(file_name) = dialog.file_name {
+        rfd_dialog = rfd_dialog.set_file_name(file_name);
+    }
+
+    for filter in dialog.filters {
+        rfd_dialog = rfd_dialog.add_filter(filter.name, &filter.extensions);
+    }
+
+    rfd_dialog
+}
+
+#[cfg(not(any(target_os = "android", target_arch = "wasm32")))]
+async fn resolve_handle(handle: rfd::FileHandle) -> PickedFile {
+    PickedFile::Path(handle.into())
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn resolve_handle(handle: rfd::FileHandle) -> PickedFile {
+    PickedFile::Bytes {
+        name: handle.file_name(),
+        bytes: handle.read().await,
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+pub(crate) async fn pick_file(dialog: FileDialog) -> Option<PickedFile> {
+    let handle = create_rfd_dialog(dialog).pick_file().await?;
+
+    Some(resolve_handle(handle).await)
+}
+
+#[cfg(not(target_os = "android"))]
+pub(crate) async fn pick_files(dialog: FileDialog) -> Vec<PickedFile> {
+    let handles = create_rfd_dialog(dialog)
+        .pick_files()
+        .await
+        .unwrap_or_default();
+
+    let mut files = Vec::with_capacity(handles.len());
+
+    for handle in handles {
+        files.push(resolve_handle(handle).await);
+    }
+
+    files
+}
+
+#[cfg(not(any(target_os = "android", target_arch = "wasm32")))]
+pub(crate) async fn pick_folder(dialog: FileDialog) -> Option<PathBuf> {
+    let handle = create_rfd_dialog(dialog).pick_folder().await?;
+
+    Some(handle.into())
+}
+
+#[cfg(not(any(target_os = "android", target_arch = "wasm32")))]
+pub(crate) async fn save_file(dialog: FileDialog) -> Option<PathBuf> {
+    let handle = create_rfd_dialog(dialog).save_file().await?;
+
+    Some(handle.into())
+}
+
+#[cfg(target_os = "android")]
+pub(crate) async fn pick_file(_: FileDialog) -> Option<PickedFile> {
+    logging::log::warn!("File dialogs are unsupported on Android");
+    None
+}
+
+#[cfg(target_os = "android")]
+pub(crate) async fn pick_files(_: FileDialog) -> Vec<PickedFile> {
+    logging::log::warn!("File dialogs are unsupported on Android");
+    Vec::new()
+}
+
+#[cfg(any(target_os = "android", target_arch = "wasm32"))]
+pub(crate) async fn pick_folder(_: FileDialog) -> Option<PathBuf> {
+    logging::log::warn!("Folder dialogs are only supported on desktop");
+    None
+}
+
+#[cfg(any(target_os = "android", target_arch = "wasm32"))]
+pub(crate) async fn save_file(_: FileDialog) -> Option<PathBuf> {
+    logging::log::warn!("Save dialogs are only supported on desktop");
+    None
+}
@@ -1,12 +1,19 @@
 use crate::common::*;
 use crate::graphics::PostProcess;
 use crate::runtime::*;
+use input::ControllerMappingDatabase;
 use std::any::TypeId;
+use std::path::PathBuf;
 use std::pin::Pin;
 
 pub struct Game<Loop: GameWindowLoop> {
     window_config: GameWindowConfig<Loop::PlatformApp>,
+    app_id: String,
+    window_state_path: Option<PathBuf>,
+    logical_resolution: bool,
+    controller_mappings: ControllerMappingDatabase,
     target_fps: u16,
+    msaa_sample_count: u32,
     pub service_constructors: Vec<ServiceConstructor>,
     overlay_constructors: Vec<(GameOverlayTarget, OverlayConstructor)>,
     setup_callbacks: Vec<SetupCallback>,
@@ -16,7 +23,12 @@ pub struct Game<Loop: GameWindowLoop> {
 impl<Loop: GameWindowLoop> Game<Loop> {
     pub fn new(title: &str, size: (u32, u32)) -> Self {
         Game {
+            app_id: title.to_string(),
+            window_state_path: None,
+            logical_resolution: false,
+            controller_mappings: ControllerMappingDatabase::default(),
             target_fps: 60,
+            msaa_sample_count: 1,
             window_config: GameWindowConfig::new(title, size),
             service_constructors: Vec::new(),
             overlay_constructors: Vec::new(),
@@ -55,16 +67,51 @@ impl<Loop: GameWindowLoop> Game<Loop> {
         self
     }
 
+    /// Locks the resolution to the window's logical size, see `GameIO::set_logical_resolution()`
+    pub fn with_logical_resolution(mut self, value: bool) -> Self {
+        self.logical_resolution = value;
+        self
+    }
+
+    /// Adds mappings in the SDL `gamecontrollerdb.txt` format, used to support controllers unknown to the backend
+    pub fn with_controller_mappings(mut self, mappings: &str) -> Self {
+        self.controller_mappings.add(mappings);
+        self
+    }
+
     pub fn with_integer_scaling(mut self, value: bool) -> Self {
         self.window_config.integer_scaling = value;
         self
     }
 
+    /// Names the folder or key prefix used by Storage, defaults to the title
+    pub fn with_app_id(mut self, app_id: &str) -> Self {
+        self.app_id = app_id.to_string();
+        self
+    }
+
+    /// Restores the window position, size, and fullscreen state saved in the file from the previous run.
+    ///
+    /// The file is updated as the window is moved or resized, and when the game quits.
+    pub fn with_window_state_persistence(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+
+        self.window_config.window_state = WindowState::load(&path);
+        self.window_state_path = Some(path);
+        self
+    }
+
     pub fn with_target_fps(mut self, target_fps: u16) -> Self {
         self.target_fps = target_fps;
         self
     }
 
+    /// Multisampled anti-aliasing for the scene's render target, lowered to the highest sample count the adapter supports
+    pub fn with_msaa(mut self, sample_count: u32) -> Self {
+        self.msaa_sample_count = sample_count;
+        self
+    }
+
     pub fn with_setup<SetupCallback>(mut self, setup_callback: SetupCallback) -> Self
     where
         SetupCallback: FnOnce(&mut GameIO) + 'static,
@@ -132,7 +179,12 @@ impl<Loop: GameWindowLoop> Game<Loop> {
 
         let params = GameRuntimeCoreParams {
             scene_constructor: Box::new(scene_constructor),
+            app_id: self.app_id,
+            window_state_path: self.window_state_path,
+            logical_resolution: self.logical_resolution,
+            controller_mappings: self.controller_mappings,
             target_fps: self.target_fps,
+            msaa_sample_count: self.msaa_sample_count,
             service_constructors: self.service_constructors,
             overlay_constructors: self.overlay_constructors,
             setup_callbacks: self.setup_callbacks,
@@ -1,25 +1,37 @@
+use crate::common::*;
 use crate::runtime::*;
-use copypasta::{ClipboardContext, ClipboardProvider};
 use input::*;
 use math::*;
 use std::path::PathBuf;
 
 pub struct GameInputManager {
-    clipboard: Option<ClipboardContext>,
+    clipboard: Box<dyn ClipboardBackend>,
     latest_mouse_button: Option<MouseButton>,
     latest_key: Option<Key>,
+    latest_logical_key: Option<Key>,
     touches: Vec<Touch>,
+    gesture_recognizer: GestureRecognizer,
+    virtual_controller: Option<VirtualController>,
     mouse_position: Vec2,
+    mouse_wheel_delta: Vec2,
+    mouse_motion_delta: Vec2,
     previous_mouse_buttons: Vec<MouseButton>,
     pressed_mouse_buttons: Vec<MouseButton>,
     previous_keys: Vec<Key>,
     pressed_keys: Vec<Key>,
     repeated_keys: Vec<Key>,
+    previous_logical_keys: Vec<Key>,
+    pressed_logical_keys: Vec<Key>,
+    // the logical key can change between press and release, such as when shift is released first
+    pressed_key_inputs: Vec<KeyInput>,
     controllers: Vec<GameController>,
+    default_controller_config: ControllerConfig,
     dropping_data: bool,
-    dropped_file: Option<PathBuf>,
+    drop_position: Option<Vec2>,
+    dropped_files: Vec<PathBuf>,
     dropped_text: Option<String>,
     text: String,
+    text_segments: Vec<String>,
     pre_edit: Option<(String, Option<(usize, usize)>)>,
     accept_text: bool,
     requires_ime_update: bool,
@@ -28,31 +40,46 @@ pub struct GameInputManager {
 
 impl Default for GameInputManager {
     fn default() -> Self {
+        Self::new(default_clipboard())
+    }
+}
+
+impl GameInputManager {
+    pub fn new(clipboard: Box<dyn ClipboardBackend>) -> Self {
         Self {
-            clipboard: ClipboardContext::new().ok(),
+            clipboard,
             latest_mouse_button: None,
             latest_key: None,
+            latest_logical_key: None,
             touches: Vec::new(),
+            gesture_recognizer: GestureRecognizer::default(),
+            virtual_controller: None,
             mouse_position: Vec2::new(0.0, 0.0),
+            mouse_wheel_delta: Vec2::ZERO,
+            mouse_motion_delta: Vec2::ZERO,
             previous_mouse_buttons: Vec::new(),
             pressed_mouse_buttons: Vec::new(),
             previous_keys: Vec::new(),
             pressed_keys: Vec::new(),
             repeated_keys: Vec::new(),
+            previous_logical_keys: Vec::new(),
+            pressed_logical_keys: Vec::new(),
+            pressed_key_inputs: Vec::new(),
             controllers: Vec::new(),
+            default_controller_config: ControllerConfig::default(),
             dropping_data: false,
-            dropped_file: None,
+            drop_position: None,
+            dropped_files: Vec::new(),
             dropped_text: None,
             text: String::new(),
+            text_segments: Vec::new(),
             pre_edit: None,
             accept_text: false,
             requires_ime_update: false,
             pending_ime_cursor_area: Default::default(),
         }
     }
-}
 
-impl GameInputManager {
     pub fn requires_ime_update(&self) -> bool {
         self.requires_ime_update
     }
@@ -81,27 +108,45 @@ impl GameInputManager {
         self.pre_edit = None;
     }
 
-    pub fn request_clipboard_text(&mut self) -> String {
-        if let Some(clipboard) = &mut self.clipboard {
-            clipboard.get_contents().unwrap_or_default()
-        } else {
-            String::new()
-        }
+    /// Replaces the clipboard, such as with a platform specific implementation
+    pub fn set_clipboard_backend(&mut self, clipboard: Box<dyn ClipboardBackend>) {
+        self.clipboard = clipboard;
     }
 
-    pub fn set_clipboard_text(&mut self, text: String) -> bool {
-        if let Some(clipboard) = &mut self.clipboard {
-            clipboard.set_contents(text).is_ok()
-        } else {
-            false
-        }
+    /// Fails with `ClipboardError::RequiresAsync` on web, see `GameIO::read_clipboard_text()`
+    pub fn request_clipboard_text(&mut self) -> Result<String, ClipboardError> {
+        self.clipboard.text()
+    }
+
+    pub(crate) fn read_clipboard_text_async(&mut self) -> ClipboardTextFuture {
+        self.clipboard.read_text_async()
+    }
+
+    pub fn set_clipboard_text(&mut self, text: String) -> Result<(), ClipboardError> {
+        self.clipboard.set_text(&text)
+    }
+
+    pub fn request_clipboard_image(&mut self) -> Result<ClipboardImage, ClipboardError> {
+        self.clipboard.image()
     }
 
+    pub fn set_clipboard_image(&mut self, image: &ClipboardImage) -> Result<(), ClipboardError> {
+        self.clipboard.set_image(image)
+    }
+
+    /// All text committed this tick, see `text_segments()` to handle each commit separately
     pub fn text(&self) -> &str {
         &self.text
     }
 
-    // Returns the pre edit text with selection start and end
+    /// Text committed this tick in the order it was received, such as an IME commit followed by a typed character
+    ///
+    /// Backspace and delete are included as "\u{8}" and "\u{7f}"
+    pub fn text_segments(&self) -> &[String] {
+        &self.text_segments
+    }
+
+    /// Returns the pre edit text with selection start and end as byte offsets
     pub fn text_pre_edit(&self) -> Option<(&str, Option<(usize, usize)>)> {
         self.pre_edit
             .as_ref()
@@ -112,11 +157,64 @@ impl GameInputManager {
         &self.touches
     }
 
+    /// Gestures recognized this tick, touches owned by the virtual controller are ignored
+    pub fn gestures(&self) -> &[Gesture] {
+        self.gesture_recognizer.gestures()
+    }
+
+    pub fn gesture_config(&self) -> &GestureConfig {
+        self.gesture_recognizer.config()
+    }
+
+    pub fn set_gesture_config(&mut self, config: GestureConfig) {
+        self.gesture_recognizer.set_config(config);
+    }
+
+    pub fn virtual_controller(&self) -> Option<&VirtualController> {
+        self.virtual_controller.as_ref()
+    }
+
+    pub fn virtual_controller_mut(&mut self) -> Option<&mut VirtualController> {
+        self.virtual_controller.as_mut()
+    }
+
+    /// The virtual controller is exposed as a `GameController` using `VirtualController::CONTROLLER_ID`
+    pub fn set_virtual_controller(&mut self, virtual_controller: Option<VirtualController>) {
+        let id = VirtualController::CONTROLLER_ID;
+
+        match &virtual_controller {
+            Some(virtual_controller) => {
+                if self.controller(id).is_none() {
+                    let mut controller = virtual_controller.create_controller();
+                    controller.set_config(self.default_controller_config.clone());
+                    self.controllers.push(controller);
+                }
+            }
+            None => {
+                if let Some(index) = self.controllers.iter().position(|c| c.id() == id) {
+                    self.controllers.swap_remove(index);
+                }
+            }
+        }
+
+        self.virtual_controller = virtual_controller;
+    }
+
     /// Relative to the render. Top left is (-1.0, 1.0), bottom right is (1.0, -1.0)
     pub fn mouse_position(&self) -> Vec2 {
         self.mouse_position
     }
 
+    /// Lines scrolled since the last tick. Positive x scrolls right, positive y scrolls up
+    pub fn mouse_wheel_delta(&self) -> Vec2 {
+        self.mouse_wheel_delta
+    }
+
+    /// Raw relative mouse motion since the last tick, see `InputEvent::MouseMotion`
+    pub fn mouse_motion_delta(&self) -> Vec2 {
+        self.mouse_motion_delta
+    }
+
     pub fn latest_mouse_button(&self) -> Option<MouseButton> {
         self.latest_mouse_button
     }
@@ -147,6 +245,26 @@ impl GameInputManager {
         self.controllers.iter_mut().find(|c| c.id() == id)
     }
 
+    pub fn default_controller_config(&self) -> &ControllerConfig {
+        &self.default_controller_config
+    }
+
+    /// Applies to every connected controller and controllers connected later
+    pub fn set_default_controller_config(&mut self, config: ControllerConfig) {
+        for controller in &mut self.controllers {
+            controller.set_config(config.clone());
+        }
+
+        self.default_controller_config = config;
+    }
+
+    /// Overrides the default config for a connected controller, the config is lost when the controller disconnects
+    pub fn set_controller_config(&mut self, id: usize, config: ControllerConfig) {
+        if let Some(controller) = self.controller_mut(id) {
+            controller.set_config(config);
+        }
+    }
+
     pub fn latest_button(&self) -> Option<Button> {
         self.controllers
             .iter()
@@ -220,31 +338,111 @@ impl GameInputManager {
         self.previous_keys.contains(&key) && !self.pressed_keys.contains(&key)
     }
 
+    /// Tracked using logical keys
+    pub fn modifiers(&self) -> Modifiers {
+        self.pressed_logical_keys
+            .iter()
+            .map(|key| Modifiers::from_key(*key))
+            .collect()
+    }
+
+    pub fn latest_logical_key(&self) -> Option<Key> {
+        self.latest_logical_key
+    }
+
+    /// Checks keys by their meaning in the active keyboard layout, other key functions use physical positions
+    pub fn is_logical_key_down(&self, key: Key) -> bool {
+        self.pressed_logical_keys.contains(&key)
+    }
+
+    pub fn was_logical_key_just_pressed(&self, key: Key) -> bool {
+        !self.previous_logical_keys.contains(&key) && self.pressed_logical_keys.contains(&key)
+    }
+
+    pub fn was_logical_key_released(&self, key: Key) -> bool {
+        self.previous_logical_keys.contains(&key) && !self.pressed_logical_keys.contains(&key)
+    }
+
+    /// Uses logical keys, and requires the exact set of modifiers to be held
+    pub fn was_shortcut_just_pressed(&self, shortcut: &Shortcut) -> bool {
+        self.was_logical_key_just_pressed(shortcut.key)
+            && shortcut.matches(self.modifiers(), shortcut.key)
+    }
+
     pub fn dropping_data(&self) -> bool {
         self.dropping_data
     }
 
+    /// Where data is hovering while `dropping_data()` is true, or where it was dropped.
+    ///
+    /// Relative to the render, tracks the mouse on platforms that report mouse movement during drags
+    pub fn drop_position(&self) -> Option<Vec2> {
+        self.drop_position
+    }
+
+    /// The first file dropped this tick, see `dropped_files()`
     pub fn dropped_file(&self) -> Option<PathBuf> {
-        self.dropped_file.clone()
+        self.dropped_files.first().cloned()
+    }
+
+    /// Every file dropped this tick
+    pub fn dropped_files(&self) -> &[PathBuf] {
+        &self.dropped_files
     }
 
     pub fn dropped_text(&self) -> Option<String> {
         self.dropped_text.clone()
     }
 
-    pub fn simulate_key_press(&mut self, key: Key) {
-        if !self.pressed_keys.contains(&key) {
-            self.latest_key = Some(key);
-            self.pressed_keys.push(key);
-        } else if !self.repeated_keys.contains(&key) {
-            self.repeated_keys.push(key);
+    pub fn simulate_key_press(&mut self, key: impl Into<KeyInput>) {
+        let key_input = key.into();
+        let KeyInput { physical, logical } = key_input;
+
+        if !self
+            .pressed_key_inputs
+            .iter()
+            .any(|k| k.physical == physical)
+        {
+            self.pressed_key_inputs.push(key_input);
+        }
+
+        if !self.pressed_keys.contains(&physical) {
+            self.latest_key = Some(physical);
+            self.pressed_keys.push(physical);
+        } else if !self.repeated_keys.contains(&physical) {
+            self.repeated_keys.push(physical);
+        }
+
+        if !self.pressed_logical_keys.contains(&logical) {
+            self.latest_logical_key = Some(logical);
+            self.pressed_logical_keys.push(logical);
         }
     }
 
-    pub fn simulate_key_release(&mut self, key: Key) {
-        if let Some(index) = self.pressed_keys.iter().position(|v| *v == key) {
+    pub fn simulate_key_release(&mut self, key: impl Into<KeyInput>) {
+        let KeyInput {
+            physical,
+            mut logical,
+        } = key.into();
+
+        if let Some(index) = self.pressed_keys.iter().position(|v| *v == physical) {
             self.pressed_keys.swap_remove(index);
         }
+
+        let key_inputs = &mut self.pressed_key_inputs;
+
+        if let Some(index) = key_inputs.iter().position(|k| k.physical == physical) {
+            logical = key_inputs.swap_remove(index).logical;
+        }
+
+        if key_inputs.iter().any(|k| k.logical == logical) {
+            // still held by another physical key
+            return;
+        }
+
+        if let Some(index) = self.pressed_logical_keys.iter().position(|v| *v == logical) {
+            self.pressed_logical_keys.swap_remove(index);
+        }
     }
 
     pub fn simulate_mouse_press(&mut self, button: MouseButton) {
@@ -264,18 +462,36 @@ impl GameInputManager {
         self.mouse_position = position;
     }
 
+    pub fn simulate_mouse_wheel(&mut self, delta: MouseWheelDelta) {
+        self.mouse_wheel_delta += delta.lines();
+    }
+
+    pub fn simulate_mouse_motion(&mut self, delta: Vec2) {
+        self.mouse_motion_delta += delta;
+    }
+
     pub(crate) fn flush(&mut self) {
         self.previous_mouse_buttons
             .clone_from(&self.pressed_mouse_buttons);
         self.repeated_keys.clear();
         self.previous_keys.clone_from(&self.pressed_keys);
+        self.previous_logical_keys
+            .clone_from(&self.pressed_logical_keys);
         self.latest_mouse_button = None;
         self.latest_key = None;
-        self.dropped_file = None;
+        self.latest_logical_key = None;
+        self.mouse_wheel_delta = Vec2::ZERO;
+        self.mouse_motion_delta = Vec2::ZERO;
+        self.dropped_files.clear();
         self.dropped_text = None;
+
+        if !self.dropping_data {
+            self.drop_position = None;
+        }
         self.requires_ime_update = false;
         self.pending_ime_cursor_area = None;
         self.text.clear();
+        self.text_segments.clear();
         self.touches.retain_mut(|touch| {
             if touch.phase == TouchPhase::Start {
                 // change phase as it's not our first frame with this touch
@@ -290,7 +506,32 @@ impl GameInputManager {
         }
     }
 
-    pub(crate) fn finalize_events(&mut self) {
+    pub(crate) fn finalize_events(&mut self, resolution: UVec2) {
+        let aspect_ratio = resolution.x as f32 / resolution.y.max(1) as f32;
+
+        if let Some(virtual_controller) = &mut self.virtual_controller {
+            virtual_controller.update(&self.touches, aspect_ratio);
+
+            let id = VirtualController::CONTROLLER_ID;
+
+            if let Some(controller) = self.controllers.iter_mut().find(|c| c.id() == id) {
+                virtual_controller.apply(controller);
+            }
+        }
+
+        let gesture_touches: Vec<Touch> = match &self.virtual_controller {
+            Some(virtual_controller) => self
+                .touches
+                .iter()
+                .filter(|touch| !virtual_controller.owns_touch(touch))
+                .cloned()
+                .collect(),
+            None => self.touches.clone(),
+        };
+
+        self.gesture_recognizer
+            .update(&gesture_touches, aspect_ratio, Instant::now());
+
         for controller in &mut self.controllers {
             controller.update_sticks();
         }
@@ -313,17 +554,38 @@ impl GameInputManager {
                     None => self.touches.push(touch),
                 }
             }
-            InputEvent::MouseMoved(position) => self.mouse_position = position,
+            InputEvent::MouseMoved(position) => {
+                self.mouse_position = position;
+
+                if self.dropping_data {
+                    self.drop_position = Some(position);
+                }
+            }
+            InputEvent::MouseWheel(delta) => self.simulate_mouse_wheel(delta),
+            InputEvent::MouseMotion(delta) => self.simulate_mouse_motion(delta),
             InputEvent::MouseButtonDown(button) => self.simulate_mouse_press(button),
             InputEvent::MouseButtonUp(button) => self.simulate_mouse_release(button),
             InputEvent::KeyDown(key) => self.simulate_key_press(key),
             InputEvent::KeyUp(key) => self.simulate_key_release(key),
             InputEvent::ControllerConnected {
                 controller_id,
+                info,
                 rumble_pack,
+                device,
             } => {
-                self.controllers
-                    .push(GameController::new(controller_id, rumble_pack));
+                if let Some(controller) = self.controller_mut(controller_id) {
+                    controller.set_info(info);
+                    controller.set_rumble_pack(rumble_pack);
+                    controller.set_device(device);
+                    return;
+                }
+
+                let mut controller = GameController::new(controller_id, rumble_pack);
+                controller.set_info(info);
+                controller.set_device(device);
+                controller.set_config(self.default_controller_config.clone());
+
+                self.controllers.push(controller);
             }
             InputEvent::ControllerDisconnected(id) => {
                 if let Some(index) = self.controllers.iter().position(|c| c.id() == id) {
@@ -357,7 +619,8 @@ impl GameInputManager {
             }
             InputEvent::Text(text) => {
                 if self.accept_text {
-                    self.text = text;
+                    self.text.push_str(&text);
+                    self.text_segments.push(text);
                     self.pre_edit = None;
                 }
             }
@@ -371,8 +634,14 @@ impl GameInputManager {
             }
             InputEvent::DropStart => {
                 self.dropping_data = true;
+                self.drop_position = Some(self.mouse_position);
             }
             InputEvent::DropCancelled => {
+                // some platforms also send this after completed drops
+                if self.dropping_data {
+                    self.drop_position = None;
+                }
+
                 self.dropping_data = false;
             }
             InputEvent::DroppedText(text) => {
@@ -380,7 +649,7 @@ impl GameInputManager {
                 self.dropping_data = false;
             }
             InputEvent::DroppedFile(path_buf) => {
-                self.dropped_file = Some(path_buf);
+                self.dropped_files.push(path_buf);
                 self.dropping_data = false;
             }
         }
@@ -3,19 +3,61 @@ use crate::async_task::*;
 use crate::common::*;
 use crate::graphics::*;
 use crate::runtime::*;
-use math::Instant;
+use crate::storage::Storage;
+use math::{Instant, UVec2};
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 use std::future::Future;
+use std::path::PathBuf;
 use std::time::Duration;
 
+/// State tied to a single window, swapped into `GameIO` while the window is updated and drawn
+pub(crate) struct WindowContext {
+    id: GameWindowId,
+    window: Box<dyn GameWindowLifecycle>,
+    input_manager: GameInputManager,
+    current_monitor: Option<usize>,
+    monitor_changed: bool,
+    logical_resolution: bool,
+    transitioning: bool,
+}
+
+impl WindowContext {
+    pub(crate) fn new(id: GameWindowId, window: Box<dyn GameWindowLifecycle>) -> Self {
+        let current_monitor = window.current_monitor();
+
+        let input_manager = GameInputManager::new(window.create_clipboard());
+
+        Self {
+            id,
+            window,
+            input_manager,
+            current_monitor,
+            monitor_changed: false,
+            logical_resolution: false,
+            transitioning: false,
+        }
+    }
+
+    pub(crate) fn window(&self) -> &dyn GameWindowLifecycle {
+        &*self.window
+    }
+}
+
 pub struct GameIO {
+    window_id: GameWindowId,
     window: Box<dyn GameWindowLifecycle>,
     resources: HashMap<TypeId, Box<dyn Any>>,
     pub(crate) runtime_requests: Vec<GameRuntimeRequest>,
     disabled_post_processes: Vec<TypeId>,
+    open_windows: Vec<GameWindowId>,
+    next_window_id: u64,
     async_executor: async_executor::LocalExecutor<'static>,
     input_manager: GameInputManager,
+    storage: Storage,
+    current_monitor: Option<usize>,
+    monitor_changed: bool,
+    logical_resolution: bool,
     target_fps: u16,
     game_start_instant: Instant,
     frame_start_instant: Instant,
@@ -37,14 +79,25 @@ impl HasGraphicsContext for GameIO {
 }
 
 impl GameIO {
-    pub(crate) fn new(window: Box<dyn GameWindowLifecycle>) -> Self {
+    pub(crate) fn new(window: Box<dyn GameWindowLifecycle>, storage: Storage) -> Self {
+        let current_monitor = window.current_monitor();
+
+        let input_manager = GameInputManager::new(window.create_clipboard());
+
         Self {
+            window_id: GameWindowId::MAIN,
             window,
             resources: HashMap::new(),
             runtime_requests: Vec::new(),
             disabled_post_processes: Vec::new(),
+            open_windows: Vec::new(),
+            next_window_id: GameWindowId::MAIN.0 + 1,
             async_executor: async_executor::LocalExecutor::new(),
-            input_manager: GameInputManager::default(),
+            input_manager,
+            storage,
+            current_monitor,
+            monitor_changed: false,
+            logical_resolution: false,
             target_fps: 60,
             game_start_instant: Instant::now(),
             frame_start_instant: Instant::now(),
@@ -60,6 +113,11 @@ impl GameIO {
         }
     }
 
+    /// The window currently being updated or drawn, `GameWindowId::MAIN` outside of secondary windows
+    pub fn window_id(&self) -> GameWindowId {
+        self.window_id
+    }
+
     pub fn window(&self) -> &dyn GameWindowLifecycle {
         &*self.window
     }
@@ -68,6 +126,90 @@ impl GameIO {
         &mut *self.window
     }
 
+    /// True for one tick after the window moves to a different monitor
+    pub fn window_monitor_changed(&self) -> bool {
+        self.monitor_changed
+    }
+
+    pub fn logical_resolution(&self) -> bool {
+        self.logical_resolution
+    }
+
+    /// While enabled, the resolution is locked to the window's logical size,
+    /// keeping content at a consistent physical size across displays with different scale factors.
+    ///
+    /// Replaces any locked resolution, disabling unlocks the resolution.
+    pub fn set_logical_resolution(&mut self, enabled: bool) {
+        self.logical_resolution = enabled;
+
+        if enabled {
+            self.apply_logical_resolution();
+        } else {
+            self.window.unlock_resolution();
+        }
+    }
+
+    /// Requests a new window sharing the same graphics device.
+    ///
+    /// The window is created by the backend between ticks, backends without multi-window support close it immediately.
+    pub fn open_window(
+        &mut self,
+        config: SecondaryWindowConfig,
+        content: WindowContent,
+    ) -> GameWindowId {
+        let id = GameWindowId(self.next_window_id);
+        self.next_window_id += 1;
+
+        self.open_windows.push(id);
+        self.runtime_requests
+            .push(GameRuntimeRequest::OpenWindow(id, config, content));
+
+        id
+    }
+
+    /// Closing the main window quits the game
+    pub fn close_window(&mut self, id: GameWindowId) {
+        if id == GameWindowId::MAIN {
+            self.quit();
+            return;
+        }
+
+        if let Some(index) = self.open_windows.iter().position(|open| *open == id) {
+            self.open_windows.remove(index);
+            self.runtime_requests
+                .push(GameRuntimeRequest::CloseWindow(id));
+        }
+    }
+
+    /// False after the window is closed by the user, `close_window()`, or the backend
+    pub fn is_window_open(&self, id: GameWindowId) -> bool {
+        id == GameWindowId::MAIN || self.open_windows.contains(&id)
+    }
+
+    pub(crate) fn forget_window(&mut self, id: GameWindowId) {
+        self.open_windows.retain(|open| *open != id);
+    }
+
+    pub(crate) fn swap_window_context(&mut self, context: &mut WindowContext) {
+        use std::mem::swap;
+
+        swap(&mut self.window_id, &mut context.id);
+        swap(&mut self.window, &mut context.window);
+        swap(&mut self.input_manager, &mut context.input_manager);
+        swap(&mut self.current_monitor, &mut context.current_monitor);
+        swap(&mut self.monitor_changed, &mut context.monitor_changed);
+        swap(
+            &mut self.logical_resolution,
+            &mut context.logical_resolution,
+        );
+        swap(&mut self.transitioning, &mut context.transitioning);
+    }
+
+    fn apply_logical_resolution(&mut self) {
+        let logical_size = self.window.logical_size().round().as_uvec2();
+        self.window.lock_resolution(logical_size.max(UVec2::ONE));
+    }
+
     pub fn input(&self) -> &GameInputManager {
         &self.input_manager
     }
@@ -76,6 +218,14 @@ impl GameIO {
         &mut self.input_manager
     }
 
+    pub fn storage(&self) -> &Storage {
+        &self.storage
+    }
+
+    pub fn storage_mut(&mut self) -> &mut Storage {
+        &mut self.storage
+    }
+
     pub fn resource<R: Any>(&self) -> Option<&R> {
         self.resources.get(&TypeId::of::<R>())?.downcast_ref::<R>()
     }
@@ -200,6 +350,34 @@ impl GameIO {
         AsyncTask::from(task)
     }
 
+    /// Reads the clipboard asynchronously, required on web where the browser may prompt for permission
+    pub fn read_clipboard_text(&mut self) -> AsyncTask<Result<String, ClipboardError>> {
+        let future = self.input_manager.read_clipboard_text_async();
+        self.spawn_local_task(future)
+    }
+
+    /// Resolves to None if the dialog is cancelled.
+    ///
+    /// Web displays a file input, resolving to `PickedFile::Bytes`
+    pub fn pick_file(&self, dialog: FileDialog) -> AsyncTask<Option<PickedFile>> {
+        self.spawn_local_task(super::file_dialog::pick_file(dialog))
+    }
+
+    /// Resolves to an empty list if the dialog is cancelled, see `pick_file()`
+    pub fn pick_files(&self, dialog: FileDialog) -> AsyncTask<Vec<PickedFile>> {
+        self.spawn_local_task(super::file_dialog::pick_files(dialog))
+    }
+
+    /// Desktop only, resolves to None if the dialog is cancelled
+    pub fn pick_folder(&self, dialog: FileDialog) -> AsyncTask<Option<PathBuf>> {
+        self.spawn_local_task(super::file_dialog::pick_folder(dialog))
+    }
+
+    /// Desktop only, resolves to None if the dialog is cancelled
+    pub fn save_file(&self, dialog: FileDialog) -> AsyncTask<Option<PathBuf>> {
+        self.spawn_local_task(super::file_dialog::save_file(dialog))
+    }
+
     pub fn is_in_transition(&self) -> bool {
         self.transitioning
     }
@@ -244,6 +422,10 @@ impl GameIO {
         }
 
         self.input_manager.flush();
+        self.monitor_changed = false;
+
+        let mut check_monitor = false;
+        let mut check_resolution = false;
 
         for event in events {
             match event {
@@ -251,13 +433,22 @@ impl GameIO {
                     self.window.rebuild_surface();
                 }
                 GameWindowEvent::CloseRequested => {
-                    self.quitting = true;
+                    self.close_window(self.window_id);
                 }
                 GameWindowEvent::Moved(position) => {
                     self.window.moved(position);
+                    check_monitor = true;
                 }
                 GameWindowEvent::Resized(size) => {
                     self.window.resized(size);
+                    check_monitor = true;
+                    check_resolution = true;
+                }
+                GameWindowEvent::ScaleFactorChanged(_) => {
+                    check_resolution = true;
+                }
+                GameWindowEvent::MonitorChanged => {
+                    check_monitor = true;
                 }
                 GameWindowEvent::InputEvent(input_event) => {
                     self.input_manager.handle_event(input_event);
@@ -265,7 +456,20 @@ impl GameIO {
             }
         }
 
-        self.input_manager.finalize_events();
+        self.input_manager.finalize_events(self.window.resolution());
+
+        if check_resolution && self.logical_resolution {
+            self.apply_logical_resolution();
+        }
+
+        if check_monitor {
+            let current_monitor = self.window.current_monitor();
+
+            if current_monitor != self.current_monitor {
+                self.current_monitor = current_monitor;
+                self.monitor_changed = true;
+            }
+        }
     }
 
     pub(crate) fn set_frame_start_instant(&mut self, instant: Instant) {
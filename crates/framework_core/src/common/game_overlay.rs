@@ -13,6 +13,9 @@ pub trait GameOverlay {
     /// Called every tick after scene updates and before service post_updates
     fn post_update(&mut self, _game_io: &mut GameIO) {}
 
+    /// Called after the graphics device was lost and recreated, see `Scene::graphics_recreated()`
+    fn graphics_recreated(&mut self, _game_io: &mut GameIO) {}
+
     /// Called to perform rendering. Not guaranteed to be called after every update
     fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass);
 }
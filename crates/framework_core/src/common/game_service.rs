@@ -6,4 +6,7 @@ pub trait GameService {
 
     /// Called every tick after all updates
     fn post_update(&mut self, _game_io: &mut GameIO) {}
+
+    /// Called after the graphics device was lost and recreated, see `Scene::graphics_recreated()`
+    fn graphics_recreated(&mut self, _game_io: &mut GameIO) {}
 }
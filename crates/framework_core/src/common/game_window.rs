@@ -1,3 +1,4 @@
+use super::{CursorGrab, CursorIcon, CustomCursor, FullscreenMode, MonitorInfo};
 use crate::graphics::Color;
 use math::*;
 
@@ -10,6 +11,29 @@ pub trait GameWindow {
 
     fn set_fullscreen(&mut self, fullscreen: bool);
 
+    /// Returns FullscreenMode::Borderless while windowed
+    fn fullscreen_mode(&self) -> FullscreenMode {
+        FullscreenMode::Borderless
+    }
+
+    /// Enters fullscreen on the monitor at the index from `monitors()`, or the current monitor if None.
+    ///
+    /// Falls back to borderless fullscreen if the video mode isn't supported by the monitor.
+    fn set_fullscreen_mode(&mut self, monitor_index: Option<usize>, mode: FullscreenMode) {
+        let _ = (monitor_index, mode);
+        self.set_fullscreen(true);
+    }
+
+    /// Monitors connected to the system, empty on platforms without monitor information
+    fn monitors(&self) -> Vec<MonitorInfo> {
+        Vec::new()
+    }
+
+    /// Index of the monitor containing the window, see `GameIO::window_monitor_changed()`
+    fn current_monitor(&self) -> Option<usize> {
+        None
+    }
+
     fn size(&self) -> UVec2;
 
     fn request_size(&mut self, size: UVec2);
@@ -28,6 +52,24 @@ pub trait GameWindow {
 
     fn set_title(&mut self, title: &str);
 
+    fn cursor_visible(&self) -> bool {
+        true
+    }
+
+    fn set_cursor_visible(&mut self, _visible: bool) {}
+
+    fn cursor_grab(&self) -> CursorGrab {
+        CursorGrab::None
+    }
+
+    /// Platforms that only support one grab mode will fall back to the other
+    fn set_cursor_grab(&mut self, _grab: CursorGrab) {}
+
+    fn set_cursor_icon(&mut self, _icon: CursorIcon) {}
+
+    /// Replaced by the next call to set_cursor_icon
+    fn set_custom_cursor(&mut self, _cursor: &CustomCursor) {}
+
     fn clear_color(&self) -> Option<Color>;
 
     fn set_clear_color(&mut self, color: Option<Color>);
@@ -36,6 +78,26 @@ pub trait GameWindow {
 
     fn set_vsync_enabled(&mut self, enabled: bool);
 
+    /// Ratio of physical pixels to logical pixels, such as 2.0 on many HiDPI displays
+    fn scale_factor(&self) -> f32 {
+        1.0
+    }
+
+    /// The window size in logical pixels
+    fn logical_size(&self) -> Vec2 {
+        self.to_logical(self.size().as_vec2())
+    }
+
+    /// Converts from physical pixels to logical pixels
+    fn to_logical(&self, physical: Vec2) -> Vec2 {
+        physical / self.scale_factor()
+    }
+
+    /// Converts from logical pixels to physical pixels
+    fn to_physical(&self, logical: Vec2) -> Vec2 {
+        logical * self.scale_factor()
+    }
+
     fn ime_height(&self) -> i32 {
         0
     }
@@ -1,22 +1,34 @@
+mod clipboard;
+mod cursor;
 pub(crate) mod default_resources;
+mod file_dialog;
 mod game;
 mod game_input_manager;
 mod game_io;
 mod game_overlay;
 mod game_service;
 mod game_window;
+mod monitor_info;
 mod next_scene;
 mod scene;
 mod scene_manager;
 mod scene_transition;
+mod secondary_window;
+mod text_edit_buffer;
 
+pub use clipboard::*;
+pub use cursor::*;
+pub use file_dialog::{FileDialog, FileDialogFilter, PickedFile};
 pub use game::*;
 pub use game_input_manager::*;
 pub use game_io::*;
 pub use game_overlay::*;
 pub use game_service::*;
 pub use game_window::*;
+pub use monitor_info::*;
 pub use next_scene::*;
 pub use scene::*;
 pub(crate) use scene_manager::*;
 pub use scene_transition::*;
+pub use secondary_window::*;
+pub use text_edit_buffer::*;
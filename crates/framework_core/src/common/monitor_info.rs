@@ -0,0 +1,55 @@
+use math::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VideoMode {
+    pub size: UVec2,
+    pub bit_depth: u16,
+    /// In hertz
+    pub refresh_rate: f32,
+}
+
+impl VideoMode {
+    /// Sorted refresh rates without duplicates
+    pub fn distinct_refresh_rates(video_modes: &[VideoMode]) -> Vec<f32> {
+        let mut refresh_rates: Vec<_> = video_modes.iter().map(|mode| mode.refresh_rate).collect();
+        refresh_rates.sort_by(f32::total_cmp);
+        refresh_rates.dedup();
+        refresh_rates
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    /// Used to select the monitor with `GameWindow::set_fullscreen_mode`
+    pub index: usize,
+    pub name: String,
+    pub primary: bool,
+    /// Position in the desktop, in physical pixels
+    pub position: IVec2,
+    /// In physical pixels
+    pub size: UVec2,
+    pub scale_factor: f32,
+    /// Refresh rates in hertz supported by the video modes, sorted without duplicates
+    pub refresh_rates: Vec<f32>,
+    pub video_modes: Vec<VideoMode>,
+}
+
+impl MonitorInfo {
+    pub fn bounds(&self) -> IRect {
+        IRect::new(
+            self.position.x,
+            self.position.y,
+            self.size.x as i32,
+            self.size.y as i32,
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum FullscreenMode {
+    /// A borderless window covering the monitor, keeps the desktop's video mode
+    #[default]
+    Borderless,
+    /// Takes over the monitor and switches to the video mode
+    Exclusive(VideoMode),
+}
@@ -1,5 +1,6 @@
 use crate::common::{Scene, SceneTransition};
 
+#[derive(Default)]
 pub enum NextScene {
     Push {
         scene: Box<dyn Scene>,
@@ -16,6 +17,7 @@ pub enum NextScene {
     Pop {
         transition: Option<Box<dyn SceneTransition>>,
     },
+    #[default]
     None,
 }
 
@@ -79,9 +81,3 @@ impl NextScene {
         next_scene
     }
 }
-
-impl Default for NextScene {
-    fn default() -> Self {
-        Self::None
-    }
-}
@@ -14,6 +14,10 @@ pub trait Scene {
     /// Called before the scene is dropped
     fn destroy(&mut self, _game_io: &mut GameIO) {}
 
+    /// Called after the graphics device was lost and recreated.
+    /// Textures, meshes, and pipelines created before this call are unusable and should be rebuilt.
+    fn graphics_recreated(&mut self, _game_io: &mut GameIO) {}
+
     /// Called every tick even when the scene is inactive
     fn continuous_update(&mut self, _game_io: &mut GameIO) {}
 
@@ -325,6 +325,13 @@ impl SceneManager {
         }
     }
 
+    /// Notifies every scene, including scenes kept alive by transitions and the scene stack
+    pub fn graphics_recreated(&mut self, game_io: &mut GameIO) {
+        for (_, scene) in &mut self.scenes {
+            scene.graphics_recreated(game_io);
+        }
+    }
+
     pub fn draw(
         &mut self,
         game_io: &mut GameIO,
@@ -0,0 +1,92 @@
+use super::{GameIO, GameOverlay, Scene};
+use crate::runtime::{OverlayConstructor, SceneConstructor};
+use math::UVec2;
+
+/// Identifies a window owned by the runtime, see `GameIO::open_window()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GameWindowId(pub(crate) u64);
+
+impl GameWindowId {
+    /// The window the game was started with
+    pub const MAIN: GameWindowId = GameWindowId(0);
+}
+
+#[derive(Debug, Clone)]
+pub struct SecondaryWindowConfig {
+    pub title: String,
+    pub size: UVec2,
+    pub resolution: Option<UVec2>,
+    pub integer_scaling: bool,
+    pub resizable: bool,
+    pub borderless: bool,
+    pub always_on_top: bool,
+}
+
+impl SecondaryWindowConfig {
+    pub fn new(title: impl Into<String>, size: UVec2) -> Self {
+        Self {
+            title: title.into(),
+            size,
+            resolution: None,
+            integer_scaling: false,
+            resizable: true,
+            borderless: false,
+            always_on_top: false,
+        }
+    }
+
+    pub fn with_resolution(mut self, resolution: Option<UVec2>) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    pub fn with_integer_scaling(mut self, value: bool) -> Self {
+        self.integer_scaling = value;
+        self
+    }
+
+    pub fn with_resizable(mut self, value: bool) -> Self {
+        self.resizable = value;
+        self
+    }
+
+    pub fn with_borderless(mut self, value: bool) -> Self {
+        self.borderless = value;
+        self
+    }
+
+    pub fn with_always_on_top(mut self, value: bool) -> Self {
+        self.always_on_top = value;
+        self
+    }
+}
+
+/// What a secondary window displays
+///
+/// Constructors and callbacks run while the window is active,
+/// so `GameIO::window()` and `GameIO::input()` refer to the secondary window.
+pub enum WindowContent {
+    /// Rendered with a separate scene stack, at the window's resolution
+    Scene(SceneConstructor),
+    /// Drawn directly to the window, `GameOverlay::draw()` is called with the window's surface
+    Overlay(OverlayConstructor),
+}
+
+impl WindowContent {
+    pub fn scene<S: Scene + 'static>(constructor: impl FnOnce(&mut GameIO) -> S + 'static) -> Self {
+        Self::Scene(Box::new(|game_io| Box::new(constructor(game_io))))
+    }
+
+    pub fn overlay<O: GameOverlay + 'static>(
+        constructor: impl FnOnce(&mut GameIO) -> O + 'static,
+    ) -> Self {
+        Self::Overlay(Box::new(|game_io| Box::new(constructor(game_io))))
+    }
+}
+
+/// A window the backend should create, see `GameRuntimeCore::take_window_requests()`
+#[derive(Debug, Clone)]
+pub struct WindowRequest {
+    pub id: GameWindowId,
+    pub config: SecondaryWindowConfig,
+}
@@ -0,0 +1,639 @@
+use super::{ClipboardError, GameIO, GameInputManager};
+use crate::async_task::AsyncTask;
+use input::{Key, Modifiers};
+use std::ops::Range;
+
+const MAX_UNDO_STATES: usize = 100;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TextEditResponse {
+    pub changed: bool,
+    /// Return or Enter was pressed
+    pub submitted: bool,
+}
+
+/// The text to display while editing, ranges are byte offsets into `text`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TextEditDisplay {
+    /// The buffer's text with the IME pre edit text inserted at the cursor
+    pub text: String,
+    pub cursor: usize,
+    pub selection: Option<Range<usize>>,
+    pub pre_edit: Option<Range<usize>>,
+    /// The range the IME is currently converting within the pre edit text
+    pub pre_edit_selection: Option<Range<usize>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Insert,
+    Delete,
+    Other,
+}
+
+#[derive(Debug, Clone)]
+struct UndoState {
+    text: String,
+    cursor: usize,
+    anchor: Option<usize>,
+}
+
+/// Text editing state shared by text fields, positions are byte offsets and always on char boundaries
+#[derive(Debug, Default)]
+pub struct TextEditBuffer {
+    text: String,
+    cursor: usize,
+    anchor: Option<usize>,
+    max_length: Option<usize>,
+    pre_edit: Option<(String, Option<(usize, usize)>)>,
+    undo_stack: Vec<UndoState>,
+    redo_stack: Vec<UndoState>,
+    last_edit: Option<EditKind>,
+    pending_paste: Option<AsyncTask<Result<String, ClipboardError>>>,
+}
+
+impl TextEditBuffer {
+    pub fn new(text: impl Into<String>) -> Self {
+        let text = text.into();
+
+        Self {
+            cursor: text.len(),
+            text,
+            ..Default::default()
+        }
+    }
+
+    /// Limit in chars
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// Limit in chars, doesn't affect existing text
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.max_length = max_length;
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Moves the cursor to the end and clears undo history
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        self.cursor = self.text.len();
+        self.anchor = None;
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.last_edit = None;
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Clamps to the text and snaps to the previous char boundary
+    pub fn set_cursor(&mut self, cursor: usize, select: bool) {
+        let cursor = self.floor_char_boundary(cursor);
+        self.move_cursor(cursor, select);
+    }
+
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+
+        if anchor == self.cursor {
+            return None;
+        }
+
+        Some(anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    pub fn selected_text(&self) -> Option<&str> {
+        self.selection().map(|range| &self.text[range])
+    }
+
+    pub fn select_all(&mut self) {
+        self.anchor = Some(0);
+        self.cursor = self.text.len();
+        self.last_edit = None;
+    }
+
+    pub fn clear_selection(&mut self) {
+        self.anchor = None;
+    }
+
+    pub fn pre_edit(&self) -> Option<&str> {
+        self.pre_edit.as_ref().map(|(text, _)| text.as_str())
+    }
+
+    pub fn display(&self) -> TextEditDisplay {
+        let Some((pre_edit, pre_edit_selection)) = &self.pre_edit else {
+            return TextEditDisplay {
+                text: self.text.clone(),
+                cursor: self.cursor,
+                selection: self.selection(),
+                pre_edit: None,
+                pre_edit_selection: None,
+            };
+        };
+
+        let mut text = self.text.clone();
+        text.insert_str(self.cursor, pre_edit);
+
+        let start = self.cursor;
+        let pre_edit_selection = pre_edit_selection.map(|(a, b)| start + a..start + b);
+
+        TextEditDisplay {
+            text,
+            cursor: pre_edit_selection
+                .as_ref()
+                .map(|range| range.end)
+                .unwrap_or(start + pre_edit.len()),
+            selection: None,
+            pre_edit: Some(start..start + pre_edit.len()),
+            pre_edit_selection,
+        }
+    }
+
+    /// Replaces the selection, control characters are ignored and text past the max length is dropped
+    pub fn insert(&mut self, text: &str) -> bool {
+        let selection = self.selection();
+        let remaining = self.max_length.map(|max_length| {
+            let selected_chars = selection
+                .clone()
+                .map(|range| self.text[range].chars().count())
+                .unwrap_or_default();
+
+            (max_length + selected_chars).saturating_sub(self.text.chars().count())
+        });
+
+        let filtered: String = text
+            .chars()
+            .filter(|c| !c.is_control())
+            .take(remaining.unwrap_or(usize::MAX))
+            .collect();
+
+        if filtered.is_empty() {
+            return false;
+        }
+
+        self.record_undo(EditKind::Insert);
+
+        if let Some(range) = selection {
+            self.text.replace_range(range.clone(), "");
+            self.cursor = range.start;
+        }
+
+        self.anchor = None;
+        self.text.insert_str(self.cursor, &filtered);
+        self.cursor += filtered.len();
+
+        true
+    }
+
+    /// Deletes the selection or the char before the cursor
+    pub fn backspace(&mut self, word: bool) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+
+        let start = if word {
+            self.previous_word_boundary(self.cursor)
+        } else {
+            self.previous_char_boundary(self.cursor)
+        };
+
+        self.delete_range(start..self.cursor)
+    }
+
+    /// Deletes the selection or the char after the cursor
+    pub fn delete(&mut self, word: bool) -> bool {
+        if self.delete_selection() {
+            return true;
+        }
+
+        let end = if word {
+            self.next_word_boundary(self.cursor)
+        } else {
+            self.next_char_boundary(self.cursor)
+        };
+
+        self.delete_range(self.cursor..end)
+    }
+
+    pub fn move_left(&mut self, word: bool, select: bool) {
+        if let (Some(range), false) = (self.selection(), select) {
+            return self.move_cursor(range.start, false);
+        }
+
+        let cursor = if word {
+            self.previous_word_boundary(self.cursor)
+        } else {
+            self.previous_char_boundary(self.cursor)
+        };
+
+        self.move_cursor(cursor, select);
+    }
+
+    pub fn move_right(&mut self, word: bool, select: bool) {
+        if let (Some(range), false) = (self.selection(), select) {
+            return self.move_cursor(range.end, false);
+        }
+
+        let cursor = if word {
+            self.next_word_boundary(self.cursor)
+        } else {
+            self.next_char_boundary(self.cursor)
+        };
+
+        self.move_cursor(cursor, select);
+    }
+
+    pub fn move_home(&mut self, select: bool) {
+        self.move_cursor(0, select);
+    }
+
+    pub fn move_end(&mut self, select: bool) {
+        self.move_cursor(self.text.len(), select);
+    }
+
+    pub fn copy(&self, input: &mut GameInputManager) -> bool {
+        match self.selected_text() {
+            Some(text) => input.set_clipboard_text(text.to_string()).is_ok(),
+            None => false,
+        }
+    }
+
+    pub fn cut(&mut self, input: &mut GameInputManager) -> bool {
+        self.copy(input) && self.delete_selection()
+    }
+
+    /// Platforms that only allow asynchronous reads, such as web, paste during a later `update()`
+    pub fn paste(&mut self, game_io: &mut GameIO) -> bool {
+        match game_io.input_mut().request_clipboard_text() {
+            Ok(text) => self.paste_text(&text),
+            Err(ClipboardError::RequiresAsync) => {
+                self.pending_paste = Some(game_io.read_clipboard_text());
+                false
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Pastes the result of an asynchronous read once it resolves
+    fn resolve_pending_paste(&mut self) -> bool {
+        let Some(task) = self.pending_paste.take_if(|task| task.is_finished()) else {
+            return false;
+        };
+
+        match task.join() {
+            Some(Ok(text)) => self.paste_text(&text),
+            _ => false,
+        }
+    }
+
+    fn paste_text(&mut self, text: &str) -> bool {
+        // pasting is undone separately from typing
+        self.last_edit = None;
+        self.insert(text)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    pub fn undo(&mut self) -> bool {
+        let Some(state) = self.undo_stack.pop() else {
+            return false;
+        };
+
+        let current = self.swap_state(state);
+        self.redo_stack.push(current);
+        true
+    }
+
+    pub fn redo(&mut self) -> bool {
+        let Some(state) = self.redo_stack.pop() else {
+            return false;
+        };
+
+        let current = self.swap_state(state);
+        self.undo_stack.push(current);
+        true
+    }
+
+    /// Applies text, IME pre edit, navigation, clipboard, and undo input for this tick
+    ///
+    /// Expects text input to be started with `GameInputManager::start_text_input()`
+    pub fn update(&mut self, game_io: &mut GameIO) -> TextEditResponse {
+        let mut response = TextEditResponse {
+            changed: self.resolve_pending_paste(),
+            ..Default::default()
+        };
+
+        let input = game_io.input_mut();
+        let modifiers = input.modifiers();
+        let word = modifiers.contains(word_modifier());
+        let select = modifiers.shift();
+
+        for segment in input.text_segments().to_vec() {
+            let mut pending = String::new();
+
+            for c in segment.chars() {
+                if !matches!(c, '\u{8}' | '\u{7f}' | '\n' | '\r') {
+                    pending.push(c);
+                    continue;
+                }
+
+                response.changed |= self.insert(&pending);
+                pending.clear();
+
+                match c {
+                    '\u{8}' => response.changed |= self.backspace(word),
+                    '\u{7f}' => response.changed |= self.delete(word),
+                    _ => response.submitted = true,
+                }
+            }
+
+            response.changed |= self.insert(&pending);
+        }
+
+        self.pre_edit = input
+            .text_pre_edit()
+            .map(|(text, selection)| (text.to_string(), selection))
+            .filter(|(text, _)| !text.is_empty());
+
+        if self.pre_edit.is_some() {
+            // navigation keys belong to the IME while composing
+            return response;
+        }
+
+        let pressed = |key: Key| input.was_key_just_pressed(key) || input.is_key_repeated(key);
+
+        if pressed(Key::Left) {
+            self.move_left(word, select);
+        }
+
+        if pressed(Key::Right) {
+            self.move_right(word, select);
+        }
+
+        if pressed(Key::Home) || pressed(Key::Up) {
+            self.move_home(select);
+        }
+
+        if pressed(Key::End) || pressed(Key::Down) {
+            self.move_end(select);
+        }
+
+        let shortcut_modifiers =
+            modifiers & (Modifiers::CONTROL | Modifiers::META | Modifiers::ALT);
+
+        if shortcut_modifiers != primary_modifier() {
+            return response;
+        }
+
+        let shortcut_pressed =
+            |key: Key| input.was_logical_key_just_pressed(key) || input.is_key_repeated(key);
+
+        let select_all = shortcut_pressed(Key::A);
+        let copy = shortcut_pressed(Key::C);
+        let cut = shortcut_pressed(Key::X);
+        let paste = shortcut_pressed(Key::V);
+        let undo = shortcut_pressed(Key::Z) && !select;
+        let redo = shortcut_pressed(Key::Y) || (shortcut_pressed(Key::Z) && select);
+
+        if select_all {
+            self.select_all();
+        }
+
+        if copy {
+            self.copy(input);
+        }
+
+        if cut {
+            response.changed |= self.cut(input);
+        }
+
+        if paste {
+            response.changed |= self.paste(game_io);
+        }
+
+        if undo {
+            response.changed |= self.undo();
+        }
+
+        if redo {
+            response.changed |= self.redo();
+        }
+
+        response
+    }
+
+    fn move_cursor(&mut self, cursor: usize, select: bool) {
+        if select {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+
+        self.cursor = cursor;
+        self.last_edit = None;
+    }
+
+    fn delete_selection(&mut self) -> bool {
+        match self.selection() {
+            Some(range) => self.delete_range(range),
+            None => false,
+        }
+    }
+
+    fn delete_range(&mut self, range: Range<usize>) -> bool {
+        if range.is_empty() {
+            return false;
+        }
+
+        self.record_undo(EditKind::Delete);
+        self.text.replace_range(range.clone(), "");
+        self.cursor = range.start;
+        self.anchor = None;
+
+        true
+    }
+
+    /// Consecutive edits of the same kind are undone together
+    fn record_undo(&mut self, kind: EditKind) {
+        self.redo_stack.clear();
+
+        if self.last_edit == Some(kind) && kind != EditKind::Other {
+            return;
+        }
+
+        self.last_edit = Some(kind);
+
+        if self.undo_stack.len() >= MAX_UNDO_STATES {
+            self.undo_stack.remove(0);
+        }
+
+        self.undo_stack.push(UndoState {
+            text: self.text.clone(),
+            cursor: self.cursor,
+            anchor: self.anchor,
+        });
+    }
+
+    fn swap_state(&mut self, state: UndoState) -> UndoState {
+        self.last_edit = Some(EditKind::Other);
+
+        UndoState {
+            text: std::mem::replace(&mut self.text, state.text),
+            cursor: std::mem::replace(&mut self.cursor, state.cursor),
+            anchor: std::mem::replace(&mut self.anchor, state.anchor),
+        }
+    }
+
+    fn floor_char_boundary(&self, index: usize) -> usize {
+        let mut index = index.min(self.text.len());
+
+        while !self.text.is_char_boundary(index) {
+            index -= 1;
+        }
+
+        index
+    }
+
+    fn previous_char_boundary(&self, index: usize) -> usize {
+        self.text[..index]
+            .char_indices()
+            .next_back()
+            .map(|(i, _)| i)
+            .unwrap_or_default()
+    }
+
+    fn next_char_boundary(&self, index: usize) -> usize {
+        self.text[index..]
+            .chars()
+            .next()
+            .map(|c| index + c.len_utf8())
+            .unwrap_or(index)
+    }
+
+    /// Skips whitespace and punctuation, then the word before the index
+    fn previous_word_boundary(&self, index: usize) -> usize {
+        let mut chars = self.text[..index].char_indices().rev().peekable();
+
+        while chars.next_if(|(_, c)| !c.is_alphanumeric()).is_some() {}
+        while chars.next_if(|(_, c)| c.is_alphanumeric()).is_some() {}
+
+        chars.peek().map(|(i, c)| i + c.len_utf8()).unwrap_or(0)
+    }
+
+    /// Skips whitespace and punctuation, then the word after the index
+    fn next_word_boundary(&self, index: usize) -> usize {
+        let mut chars = self.text[index..].char_indices().peekable();
+
+        while chars.next_if(|(_, c)| !c.is_alphanumeric()).is_some() {}
+        while chars.next_if(|(_, c)| c.is_alphanumeric()).is_some() {}
+
+        chars
+            .peek()
+            .map(|(i, _)| index + i)
+            .unwrap_or(self.text.len())
+    }
+}
+
+/// The modifier used for shortcuts such as copy and paste
+fn primary_modifier() -> Modifiers {
+    if cfg!(target_os = "macos") {
+        Modifiers::META
+    } else {
+        Modifiers::CONTROL
+    }
+}
+
+/// The modifier used to move and delete by words
+fn word_modifier() -> Modifiers {
+    if cfg!(target_os = "macos") {
+        Modifiers::ALT
+    } else {
+        Modifiers::CONTROL
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn editing() {
+        let mut buffer = TextEditBuffer::new("hello world").with_max_length(20);
+
+        buffer.move_left(true, false);
+        assert_eq!(buffer.cursor(), 6);
+
+        buffer.move_left(true, true);
+        assert_eq!(buffer.selected_text(), Some("hello "));
+
+        assert!(buffer.insert("goodbye, "));
+        assert_eq!(buffer.text(), "goodbye, world");
+
+        // typing is undone together, moving the cursor starts a new undo state
+        buffer.set_cursor(9, false);
+        buffer.insert("new");
+        buffer.insert(" ");
+        assert_eq!(buffer.text(), "goodbye, new world");
+
+        buffer.undo();
+        assert_eq!(buffer.text(), "goodbye, world");
+        buffer.undo();
+        assert_eq!(buffer.text(), "hello world");
+        buffer.redo();
+        assert_eq!(buffer.text(), "goodbye, world");
+
+        buffer.move_end(false);
+        assert!(buffer.backspace(true));
+        assert_eq!(buffer.text(), "goodbye, ");
+
+        // max length is measured in chars
+        buffer.insert("ññññññññññññ");
+        assert_eq!(buffer.text().chars().count(), 20);
+        assert!(!buffer.insert("a"));
+    }
+
+    #[test]
+    fn async_paste() {
+        let executor = async_executor::LocalExecutor::new();
+        let task = executor.spawn(async { Ok(String::from("pasted")) });
+
+        let mut buffer = TextEditBuffer::new("");
+        buffer.pending_paste = Some(AsyncTask::from(task));
+
+        // reads are picked up once the task resolves
+        assert!(!buffer.resolve_pending_paste());
+        assert!(executor.try_tick());
+        assert!(buffer.resolve_pending_paste());
+        assert_eq!(buffer.text(), "pasted");
+        assert!(buffer.pending_paste.is_none());
+    }
+
+    #[test]
+    fn display_pre_edit() {
+        let mut buffer = TextEditBuffer::new("ab");
+        buffer.move_left(false, false);
+        buffer.pre_edit = Some((String::from("xyz"), Some((1, 2))));
+
+        let display = buffer.display();
+        assert_eq!(display.text, "axyzb");
+        assert_eq!(display.pre_edit, Some(1..4));
+        assert_eq!(display.pre_edit_selection, Some(2..3));
+        assert_eq!(display.cursor, 3);
+    }
+}
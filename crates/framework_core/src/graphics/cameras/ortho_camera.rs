@@ -158,6 +158,18 @@ impl OrthoCamera {
         self.state.requested_height = self.state.height;
     }
 
+    /// Same as resize_to_window, but scaled by the window's scale factor,
+    /// keeping content at a consistent physical size across displays
+    pub fn resize_to_window_logical(&mut self, window: &dyn GameWindowLifecycle) {
+        let resolution = window.resolution().as_vec2();
+        let logical_resolution = resolution / window.scale_factor();
+
+        self.state.width = resolution.x;
+        self.state.height = resolution.y;
+        self.state.requested_width = logical_resolution.x;
+        self.state.requested_height = logical_resolution.y;
+    }
+
     pub fn scale(&self) -> Vec2 {
         self.state.calculate_final_scale()
     }
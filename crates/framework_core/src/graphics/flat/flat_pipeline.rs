@@ -3,27 +3,62 @@ use crate::graphics::*;
 use math::*;
 
 /// A RenderPipeline for rendering FlatModels. Preset and accessible from GameIO::resource()
+///
+/// Also holds a variant of the pipeline for every StencilMode.
 pub struct FlatPipeline {
     render_pipeline: RenderPipeline<Vec2, FlatInstanceData>,
+    stencil_pipelines: Vec<RenderPipeline<Vec2, FlatInstanceData>>,
 }
 
 impl FlatPipeline {
     pub(crate) fn new(game_io: &GameIO) -> Self {
-        let device = game_io.graphics().device();
-
-        let shader = device.create_shader_module(include_wgsl!("flat_shader.wgsl"));
-
-        let render_pipeline = RenderPipelineBuilder::new(game_io)
-            .with_uniform_bind_group(&[BindGroupLayoutEntry {
-                visibility: wgpu::ShaderStages::VERTEX,
-                binding_type: OrthoCamera::binding_type(),
-            }])
-            .with_vertex_shader(&shader, "vs_main")
-            .with_fragment_shader(&shader, "fs_main")
-            .build::<Vec2, FlatInstanceData>()
+        let shader = game_io
+            .graphics()
+            .load_preprocessed_wgsl_from_str(
+                &WgslPreprocessor::new(),
+                include_str!("flat_shader.wgsl"),
+            )
             .unwrap();
 
-        Self { render_pipeline }
+        let create_builder = || {
+            RenderPipelineBuilder::new(game_io)
+                .with_uniform_bind_group(&[BindGroupLayoutEntry {
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    binding_type: OrthoCamera::binding_type(),
+                }])
+                .with_vertex_shader(&shader, "vs_main")
+                .with_fragment_shader(&shader, "fs_main")
+        };
+
+        let render_pipeline = create_builder().build::<Vec2, FlatInstanceData>().unwrap();
+
+        let stencil_pipelines = StencilMode::ALL
+            .iter()
+            .map(|stencil_mode| {
+                create_builder()
+                    .with_stencil_mode(*stencil_mode)
+                    .build::<Vec2, FlatInstanceData>()
+                    .unwrap()
+            })
+            .collect();
+
+        Self {
+            render_pipeline,
+            stencil_pipelines,
+        }
+    }
+
+    /// Pipelines for use within a stencil section, see `RenderPass::begin_stencil_section`
+    pub fn as_stencil_pipeline(
+        &self,
+        stencil_mode: StencilMode,
+    ) -> &RenderPipeline<Vec2, FlatInstanceData> {
+        let index = StencilMode::ALL
+            .iter()
+            .position(|mode| *mode == stencil_mode)
+            .unwrap();
+
+        &self.stencil_pipelines[index]
     }
 }
 
@@ -1,9 +1,4 @@
-struct CameraUniform {
-    view_proj: mat4x4<f32>,
-};
-
-@group(0) @binding(0)
-var<uniform> camera: CameraUniform;
+#include <framework/camera.wgsl>
 
 struct VertexInput {
     @location(0) vertex: vec2<f32>,
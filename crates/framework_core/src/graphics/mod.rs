@@ -3,6 +3,7 @@ mod copy;
 mod flat;
 mod post_processing;
 mod sprites;
+mod text;
 mod wgpu_abstraction;
 
 pub use cameras::*;
@@ -10,6 +11,7 @@ pub use copy::*;
 pub use flat::*;
 pub use post_processing::*;
 pub use sprites::*;
+pub use text::*;
 pub use wgpu_abstraction::*;
 
 pub use wgpu;
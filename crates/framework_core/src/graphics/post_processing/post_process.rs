@@ -7,6 +7,10 @@ pub trait PostProcess {
 
     fn update(&mut self, _game_io: &GameIO) {}
 
+    /// Called after the graphics device was lost and recreated, the pipeline should be rebuilt.
+    /// See `Scene::graphics_recreated()`
+    fn graphics_recreated(&mut self, _game_io: &GameIO) {}
+
     fn draw(
         &mut self,
         game_io: &GameIO,
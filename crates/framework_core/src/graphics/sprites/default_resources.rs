@@ -3,41 +3,164 @@ use crate::graphics::*;
 use std::sync::Arc;
 
 /// A wrapped SpritePipeline for storage in resources. Preset and accessible from GameIO::resource()
+///
+/// Holds a variant of the pipeline for every BlendMode and every StencilMode.
 pub struct DefaultSpritePipeline {
     shader: wgpu::ShaderModule,
-    pipeline: SpritePipeline<SpriteInstanceData>,
+    pipelines: Vec<SpritePipeline<SpriteInstanceData>>,
+    stencil_pipelines: Vec<SpritePipeline<SpriteInstanceData>>,
 }
 
 impl DefaultSpritePipeline {
     pub(crate) fn new(game_io: &GameIO) -> Self {
-        let device = game_io.graphics().device();
+        let shader = game_io
+            .graphics()
+            .load_preprocessed_wgsl_from_str(
+                &WgslPreprocessor::new(),
+                include_str!("sprite_shader.wgsl"),
+            )
+            .unwrap();
+
+        let pipelines = BlendMode::ALL
+            .iter()
+            .map(|blend_mode| {
+                let fragment_entry = match blend_mode {
+                    BlendMode::Multiply | BlendMode::Screen => "fs_premultiplied",
+                    _ => "fs_main",
+                };
+
+                let render_pipeline = RenderPipelineBuilder::new(game_io)
+                    .with_uniform_bind_group(&[BindGroupLayoutEntry {
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        binding_type: OrthoCamera::binding_type(),
+                    }])
+                    .with_instance_bind_group(
+                        SpritePipeline::<SpriteInstanceData>::instance_bind_group_layout(),
+                    )
+                    .with_vertex_shader(&shader, "vs_main")
+                    .with_fragment_shader(&shader, fragment_entry)
+                    .with_blend_mode(*blend_mode)
+                    .build::<SpriteVertex, SpriteInstanceData>()
+                    .unwrap();
+
+                SpritePipeline::from_custom_pipeline(render_pipeline)
+            })
+            .collect();
+
+        let stencil_pipelines = StencilMode::ALL
+            .iter()
+            .map(|stencil_mode| {
+                // masks discard pixels below the alpha cutoff
+                let fragment_entry = match stencil_mode {
+                    StencilMode::Write => "fs_mask",
+                    _ => "fs_main",
+                };
+
+                let render_pipeline = RenderPipelineBuilder::new(game_io)
+                    .with_uniform_bind_group(&[BindGroupLayoutEntry {
+                        visibility: wgpu::ShaderStages::VERTEX,
+                        binding_type: OrthoCamera::binding_type(),
+                    }])
+                    .with_instance_bind_group(
+                        SpritePipeline::<SpriteInstanceData>::instance_bind_group_layout(),
+                    )
+                    .with_vertex_shader(&shader, "vs_main")
+                    .with_fragment_shader(&shader, fragment_entry)
+                    .with_stencil_mode(*stencil_mode)
+                    .build::<SpriteVertex, SpriteInstanceData>()
+                    .unwrap();
+
+                SpritePipeline::from_custom_pipeline(render_pipeline)
+            })
+            .collect();
+
+        Self {
+            shader,
+            pipelines,
+            stencil_pipelines,
+        }
+    }
+
+    /// Shader module containing `vs_main`, `fs_main`, `fs_premultiplied`, and `fs_mask`
+    pub fn shader_module(&self) -> &wgpu::ShaderModule {
+        &self.shader
+    }
+
+    /// The pipeline using BlendMode::Alpha
+    pub fn as_sprite_pipeline(&self) -> &SpritePipeline<SpriteInstanceData> {
+        self.as_blended_sprite_pipeline(BlendMode::Alpha)
+    }
+
+    pub fn as_blended_sprite_pipeline(
+        &self,
+        blend_mode: BlendMode,
+    ) -> &SpritePipeline<SpriteInstanceData> {
+        let index = BlendMode::ALL
+            .iter()
+            .position(|mode| *mode == blend_mode)
+            .unwrap();
+
+        &self.pipelines[index]
+    }
 
-        let shader = device.create_shader_module(include_wgsl!("sprite_shader.wgsl"));
+    /// Pipelines for use within a stencil section, uses BlendMode::Alpha
+    pub fn as_stencil_sprite_pipeline(
+        &self,
+        stencil_mode: StencilMode,
+    ) -> &SpritePipeline<SpriteInstanceData> {
+        let index = StencilMode::ALL
+            .iter()
+            .position(|mode| *mode == stencil_mode)
+            .unwrap();
+
+        &self.stencil_pipelines[index]
+    }
+}
+
+/// A SpritePipeline for drawing `PaletteSprite`s. Preset and accessible from GameIO::resource()
+pub struct DefaultPaletteSpritePipeline {
+    pipeline: SpritePipeline<PaletteSpriteInstanceData>,
+}
+
+impl DefaultPaletteSpritePipeline {
+    pub(crate) fn new(game_io: &GameIO) -> Self {
+        let shader = game_io
+            .graphics()
+            .load_preprocessed_wgsl_from_str(
+                &WgslPreprocessor::new(),
+                include_str!("palette_shader.wgsl"),
+            )
+            .unwrap();
+
+        let mut instance_entries =
+            SpritePipeline::<PaletteSpriteInstanceData>::instance_bind_group_layout().to_vec();
+
+        instance_entries.push(BindGroupLayoutEntry {
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            binding_type: wgpu::BindingType::Texture {
+                multisampled: false,
+                view_dimension: wgpu::TextureViewDimension::D2,
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+            },
+        });
 
         let render_pipeline = RenderPipelineBuilder::new(game_io)
             .with_uniform_bind_group(&[BindGroupLayoutEntry {
                 visibility: wgpu::ShaderStages::VERTEX,
                 binding_type: OrthoCamera::binding_type(),
             }])
-            .with_instance_bind_group(
-                SpritePipeline::<SpriteInstanceData>::instance_bind_group_layout(),
-            )
+            .with_instance_bind_group(&instance_entries)
             .with_vertex_shader(&shader, "vs_main")
             .with_fragment_shader(&shader, "fs_main")
-            .build::<SpriteVertex, SpriteInstanceData>()
+            .build::<SpriteVertex, PaletteSpriteInstanceData>()
             .unwrap();
 
         Self {
-            shader,
             pipeline: SpritePipeline::from_custom_pipeline(render_pipeline),
         }
     }
 
-    pub fn shader_module(&self) -> &wgpu::ShaderModule {
-        &self.shader
-    }
-
-    pub fn as_sprite_pipeline(&self) -> &SpritePipeline<SpriteInstanceData> {
+    pub fn as_sprite_pipeline(&self) -> &SpritePipeline<PaletteSpriteInstanceData> {
         &self.pipeline
     }
 }
@@ -1,9 +1,15 @@
 mod default_resources;
+mod nine_slice;
+mod palette_sprite;
 mod sprite;
+mod sprite_material;
 mod sprite_pipeline;
 mod sprite_queue;
 
 pub use default_resources::*;
+pub use nine_slice::*;
+pub use palette_sprite::*;
 pub use sprite::*;
+pub use sprite_material::*;
 pub use sprite_pipeline::*;
 pub use sprite_queue::*;
@@ -0,0 +1,112 @@
+use crate::common::GameIO;
+use crate::graphics::*;
+use math::*;
+use std::sync::Arc;
+
+/// Draws a texture stretched to fit bounds while keeping its corners unscaled
+#[derive(Clone)]
+pub struct NineSlice {
+    sprite: Sprite,
+    frame: Rect,
+    center: Rect,
+}
+
+impl NineSlice {
+    /// `center` is the stretchable region of the texture in pixels
+    pub fn new(game_io: &GameIO, texture: Arc<Texture>, center: Rect) -> Self {
+        let sprite = Sprite::new(game_io, texture);
+        let frame = sprite.frame();
+
+        Self {
+            sprite,
+            frame,
+            center,
+        }
+    }
+
+    /// `frame` is the region of the texture to use, `center` is relative to the texture and must be inside of the frame
+    pub fn with_frame(mut self, frame: Rect) -> Self {
+        self.frame = frame;
+        self
+    }
+
+    pub fn with_sampler(mut self, sampler: Arc<TextureSampler>) -> Self {
+        self.sprite.set_sampler(sampler);
+        self
+    }
+
+    pub fn frame(&self) -> Rect {
+        self.frame
+    }
+
+    pub fn center(&self) -> Rect {
+        self.center
+    }
+
+    /// The combined size of the unscaled edges
+    pub fn min_size(&self) -> Vec2 {
+        self.frame.size() - self.center.size()
+    }
+
+    pub fn draw(&self, sprite_queue: &mut SpriteQueue, bounds: Rect, color: Color) {
+        let frame = self.frame;
+        let center = self.center;
+
+        let source_x = [frame.left(), center.left(), center.right(), frame.right()];
+        let source_y = [frame.top(), center.top(), center.bottom(), frame.bottom()];
+
+        let dest_x = Self::dest_edges(source_x, bounds.left(), bounds.right());
+        let dest_y = Self::dest_edges(source_y, bounds.top(), bounds.bottom());
+
+        let mut sprite = self.sprite.clone();
+        sprite.set_color(color);
+
+        for row in 0..3 {
+            for col in 0..3 {
+                let source = Rect::from_corners(
+                    Vec2::new(source_x[col], source_y[row]),
+                    Vec2::new(source_x[col + 1], source_y[row + 1]),
+                );
+
+                let dest = Rect::from_corners(
+                    Vec2::new(dest_x[col], dest_y[row]),
+                    Vec2::new(dest_x[col + 1], dest_y[row + 1]),
+                );
+
+                if source.width <= 0.0 || source.height <= 0.0 {
+                    continue;
+                }
+
+                if dest.width <= 0.0 || dest.height <= 0.0 {
+                    continue;
+                }
+
+                sprite.set_frame(source);
+                sprite.set_position(dest.position());
+                sprite.set_size(dest.size());
+                sprite_queue.draw_sprite(&sprite);
+            }
+        }
+    }
+
+    fn dest_edges(source: [f32; 4], start: f32, end: f32) -> [f32; 4] {
+        let start_edge = source[1] - source[0];
+        let end_edge = source[3] - source[2];
+        let available = end - start;
+
+        // shrink the edges when the bounds are too small to fit them
+        let edge_total = start_edge + end_edge;
+        let scale = if edge_total > available && edge_total > 0.0 {
+            available / edge_total
+        } else {
+            1.0
+        };
+
+        [
+            start,
+            start + start_edge * scale,
+            end - end_edge * scale,
+            end,
+        ]
+    }
+}
@@ -0,0 +1,41 @@
+#include <framework/sprite_vertex.wgsl>
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) @interpolate(flat) palette_index: u32,
+};
+
+@vertex
+fn vs_main(v_in: SpriteVertexInput, @location(7) palette_index: u32) -> VertexOutput {
+    var v_out : VertexOutput;
+
+    v_out.position = sprite_position(v_in);
+    v_out.uv = sprite_uv(v_in);
+    v_out.color = v_in.color;
+    v_out.palette_index = palette_index;
+
+    return v_out;
+}
+
+
+@group(1) @binding(0)
+var txture: texture_2d<f32>;
+@group(1) @binding(1)
+var smplr: sampler;
+@group(1) @binding(2)
+var palette: texture_2d<f32>;
+
+@fragment
+fn fs_main(v_out: VertexOutput) -> @location(0) vec4<f32> {
+    // color indices are stored in the red channel
+    let index = u32(round(textureSample(txture, smplr, v_out.uv).r * 255.0));
+
+    // each row of the palette texture is a palette
+    let palette_size = textureDimensions(palette);
+    let x = min(index, palette_size.x - 1u);
+    let y = min(v_out.palette_index, palette_size.y - 1u);
+
+    return v_out.color * textureLoad(palette, vec2<u32>(x, y), 0);
+}
@@ -0,0 +1,290 @@
+use crate::common::GameIO;
+use crate::graphics::*;
+use math::*;
+use std::sync::Arc;
+
+/// A sprite using an index texture, colored by a row in a palette texture.
+///
+/// Indices are read from the red channel of the sprite's texture, see `IndexedImage` and `PaletteSprite::load_index_texture()`.
+/// Draw with `DefaultPaletteSpritePipeline`.
+#[derive(Clone)]
+pub struct PaletteSprite {
+    sprite: Sprite,
+    palette: Arc<Texture>,
+    palette_index: u32,
+}
+
+impl PaletteSprite {
+    pub fn new(game_io: &GameIO, index_texture: Arc<Texture>, palette: Arc<Texture>) -> Self {
+        Self {
+            sprite: Sprite::new(game_io, index_texture),
+            palette,
+            palette_index: 0,
+        }
+    }
+
+    /// Loads an image as an index texture, using the luminance of each pixel as the index
+    pub fn load_index_texture(
+        graphics: &impl HasGraphicsContext,
+        bytes: &[u8],
+    ) -> image::ImageResult<Arc<Texture>> {
+        let image = image::load_from_memory(bytes)?.to_luma8();
+        let (width, height) = image.dimensions();
+
+        Ok(create_index_texture(
+            graphics,
+            UVec2::new(width, height),
+            &image,
+        ))
+    }
+
+    /// Creates a palette texture, each list of colors becomes a row.
+    ///
+    /// Shorter palettes are padded with transparent colors.
+    pub fn create_palette_texture(
+        graphics: &impl HasGraphicsContext,
+        palettes: &[&[Color]],
+    ) -> Arc<Texture> {
+        let (size, bytes) = palette_texture_bytes(palettes);
+        Texture::from_rgba(graphics, size, &bytes)
+    }
+
+    pub fn sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+
+    pub fn sprite_mut(&mut self) -> &mut Sprite {
+        &mut self.sprite
+    }
+
+    pub fn palette(&self) -> &Arc<Texture> {
+        &self.palette
+    }
+
+    pub fn set_palette(&mut self, palette: Arc<Texture>) {
+        self.palette = palette;
+    }
+
+    /// The row in the palette texture
+    pub fn palette_index(&self) -> u32 {
+        self.palette_index
+    }
+
+    pub fn set_palette_index(&mut self, index: u32) {
+        self.palette_index = index;
+    }
+}
+
+impl Instance<PaletteSpriteInstanceData> for PaletteSprite {
+    fn instance_data(&self) -> PaletteSpriteInstanceData {
+        PaletteSpriteInstanceData {
+            sprite: self.sprite.instance_data(),
+            palette_index: self.palette_index,
+        }
+    }
+
+    fn instance_resources(&self) -> Vec<Arc<dyn AsBinding>> {
+        vec![
+            self.sprite.texture().clone(),
+            self.sprite.sampler().clone(),
+            self.palette.clone(),
+        ]
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PaletteSpriteInstanceData {
+    pub sprite: SpriteInstanceData,
+    pub palette_index: u32,
+}
+
+impl InstanceData for PaletteSpriteInstanceData {
+    fn instance_layout() -> InstanceLayout {
+        InstanceLayout::new(&[
+            VertexFormat::Float32x3,
+            VertexFormat::Float32x3,
+            VertexFormat::Float32x3,
+            VertexFormat::Float32x4,
+            VertexFormat::Float32x4,
+            VertexFormat::Uint32,
+        ])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IndexedImageError {
+    /// Indexed images are limited to 256 colors
+    TooManyColors,
+    InvalidLength,
+}
+
+impl std::fmt::Display for IndexedImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndexedImageError::TooManyColors => write!(f, "Image has more than 256 colors"),
+            IndexedImageError::InvalidLength => {
+                write!(f, "Byte count doesn't match the image size")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IndexedImageError {}
+
+/// An image split into color indices and a palette
+#[derive(Debug, Clone)]
+pub struct IndexedImage {
+    pub size: UVec2,
+    pub indices: Vec<u8>,
+    pub palette: Vec<Color>,
+}
+
+impl IndexedImage {
+    /// Colors are indexed in order of appearance, fully transparent pixels share one entry
+    pub fn from_rgba(size: UVec2, rgba: &[u8]) -> Result<Self, IndexedImageError> {
+        if rgba.len() != size.x as usize * size.y as usize * 4 {
+            return Err(IndexedImageError::InvalidLength);
+        }
+
+        let mut palette_bytes: Vec<[u8; 4]> = Vec::new();
+        let mut indices = Vec::with_capacity(rgba.len() / 4);
+
+        for pixel in rgba.chunks_exact(4) {
+            let mut color = [pixel[0], pixel[1], pixel[2], pixel[3]];
+
+            if color[3] == 0 {
+                color = [0; 4];
+            }
+
+            let index = match palette_bytes.iter().position(|c| *c == color) {
+                Some(index) => index,
+                None => {
+                    palette_bytes.push(color);
+                    palette_bytes.len() - 1
+                }
+            };
+
+            if index > u8::MAX as usize {
+                return Err(IndexedImageError::TooManyColors);
+            }
+
+            indices.push(index as u8);
+        }
+
+        let palette = palette_bytes
+            .into_iter()
+            .map(|[r, g, b, a]| Color::from_rgba_u8s(r, g, b, a))
+            .collect();
+
+        Ok(Self {
+            size,
+            indices,
+            palette,
+        })
+    }
+
+    pub fn create_index_texture(&self, graphics: &impl HasGraphicsContext) -> Arc<Texture> {
+        create_index_texture(graphics, self.size, &self.indices)
+    }
+
+    /// Creates a palette texture with a single row
+    pub fn create_palette_texture(&self, graphics: &impl HasGraphicsContext) -> Arc<Texture> {
+        PaletteSprite::create_palette_texture(graphics, &[&self.palette])
+    }
+}
+
+fn create_index_texture(
+    graphics: &impl HasGraphicsContext,
+    size: UVec2,
+    indices: &[u8],
+) -> Arc<Texture> {
+    // linear format to avoid srgb conversion altering the indices
+    let texture = Texture::new_empty(graphics, size, wgpu::TextureFormat::R8Unorm);
+    texture.write_region(graphics, UVec2::ZERO, size, indices);
+    texture
+}
+
+fn palette_texture_bytes(palettes: &[&[Color]]) -> (UVec2, Vec<u8>) {
+    let width = palettes
+        .iter()
+        .map(|palette| palette.len())
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    let height = palettes.len().max(1);
+
+    let mut bytes = Vec::with_capacity(width * height * 4);
+
+    for palette in palettes {
+        for color in palette.iter() {
+            bytes.extend(color.to_rgba_u8s());
+        }
+
+        bytes.resize(bytes.len() + (width - palette.len()) * 4, 0);
+    }
+
+    bytes.resize(width * height * 4, 0);
+
+    (UVec2::new(width as u32, height as u32), bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn indexing() {
+        let rgba = [
+            255, 0, 0, 255, // red
+            0, 0, 0, 0, // transparent
+            255, 0, 0, 255, // red
+            9, 9, 9, 0, // transparent with color
+            0, 255, 0, 255, // green
+            0, 255, 0, 128, // translucent green
+        ];
+
+        let image = IndexedImage::from_rgba(UVec2::new(3, 2), &rgba).unwrap();
+
+        assert_eq!(image.indices, [0, 1, 0, 1, 2, 3]);
+        assert_eq!(
+            image.palette,
+            [
+                Color::RED,
+                Color::TRANSPARENT,
+                Color::GREEN,
+                Color::from_rgba_u8s(0, 255, 0, 128)
+            ]
+        );
+
+        assert_eq!(
+            IndexedImage::from_rgba(UVec2::new(2, 2), &rgba).unwrap_err(),
+            IndexedImageError::InvalidLength
+        );
+    }
+
+    #[test]
+    fn color_limit() {
+        let rgba: Vec<u8> = (0..257u32)
+            .flat_map(|i| [(i % 256) as u8, (i / 256) as u8, 0, 255])
+            .collect();
+
+        assert_eq!(
+            IndexedImage::from_rgba(UVec2::new(257, 1), &rgba).unwrap_err(),
+            IndexedImageError::TooManyColors
+        );
+
+        assert!(IndexedImage::from_rgba(UVec2::new(256, 1), &rgba[..256 * 4]).is_ok());
+    }
+
+    #[test]
+    fn palette_rows() {
+        let (size, bytes) = palette_texture_bytes(&[&[Color::RED, Color::GREEN], &[Color::BLUE]]);
+
+        assert_eq!(size, UVec2::new(2, 2));
+        assert_eq!(
+            bytes,
+            [255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 255, 0, 0, 0, 0]
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use crate::common::GameIO;
+use crate::graphics::*;
+use std::sync::Arc;
+
+/// A sprite pipeline with a custom fragment shader and extra uniforms.
+///
+/// The vertex stage is shared with the default sprite shader, see `SpriteMaterialBuilder`.
+pub struct SpriteMaterial {
+    pipeline: SpritePipeline<SpriteInstanceData>,
+    uniforms: Vec<Arc<dyn AsBinding>>,
+}
+
+impl SpriteMaterial {
+    /// WGSL prepended to every material's fragment source
+    pub const FRAGMENT_PRELUDE: &'static str = r#"
+struct SpriteFragmentInput {
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@group(1) @binding(0)
+var sprite_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var sprite_sampler: sampler;
+"#;
+
+    pub fn as_sprite_pipeline(&self) -> &SpritePipeline<SpriteInstanceData> {
+        &self.pipeline
+    }
+
+    /// Extra uniforms in binding order, starting at `@group(0) @binding(1)`
+    pub fn uniforms(&self) -> &[Arc<dyn AsBinding>] {
+        &self.uniforms
+    }
+
+    pub(crate) fn uniform_bindings(&self) -> impl Iterator<Item = BindingResource<'_>> {
+        self.uniforms.iter().map(|uniform| uniform.as_binding())
+    }
+}
+
+/// Builds a `SpriteMaterial` from WGSL containing a `fs_main` fragment entry point.
+///
+/// `SpriteMaterial::FRAGMENT_PRELUDE` is included before the source,
+/// providing `SpriteFragmentInput`, `sprite_texture`, and `sprite_sampler`.
+/// The source is processed with a `WgslPreprocessor`, see `with_preprocessor()`.
+/// The camera occupies `@group(0) @binding(0)`, uniforms added with `with_uniform()` follow it.
+///
+/// ```wgsl
+/// @group(0) @binding(1)
+/// var<uniform> tint: vec4<f32>;
+///
+/// @fragment
+/// fn fs_main(input: SpriteFragmentInput) -> @location(0) vec4<f32> {
+///     return tint * input.color * textureSample(sprite_texture, sprite_sampler, input.uv);
+/// }
+/// ```
+pub struct SpriteMaterialBuilder<'a> {
+    game_io: &'a GameIO,
+    fragment_source: String,
+    preprocessor: WgslPreprocessor,
+    uniform_entries: Vec<BindGroupLayoutEntry>,
+    uniforms: Vec<Arc<dyn AsBinding>>,
+    blend_mode: BlendMode,
+}
+
+impl<'a> SpriteMaterialBuilder<'a> {
+    pub fn new(game_io: &'a GameIO, fragment_source: &str) -> Self {
+        Self {
+            game_io,
+            fragment_source: fragment_source.to_string(),
+            preprocessor: WgslPreprocessor::new(),
+            uniform_entries: vec![BindGroupLayoutEntry {
+                visibility: wgpu::ShaderStages::VERTEX,
+                binding_type: OrthoCamera::binding_type(),
+            }],
+            uniforms: Vec::new(),
+            blend_mode: BlendMode::Alpha,
+        }
+    }
+
+    /// Adds a uniform visible to the fragment shader, bound after previously added uniforms
+    pub fn with_uniform(
+        mut self,
+        binding_type: wgpu::BindingType,
+        resource: Arc<dyn AsBinding>,
+    ) -> Self {
+        self.uniform_entries.push(BindGroupLayoutEntry {
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            binding_type,
+        });
+        self.uniforms.push(resource);
+        self
+    }
+
+    /// Provides defines and snippets for the fragment source
+    pub fn with_preprocessor(mut self, preprocessor: WgslPreprocessor) -> Self {
+        self.preprocessor = preprocessor;
+        self
+    }
+
+    /// `fs_main` must premultiply its output for modes expecting premultiplied colors
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    pub fn build(self) -> Result<SpriteMaterial, String> {
+        let game_io = self.game_io;
+        let graphics = game_io.graphics();
+        let default_pipeline = game_io.resource::<DefaultSpritePipeline>().unwrap();
+
+        let preprocessor = self
+            .preprocessor
+            .with_prelude("framework/sprite_fragment.wgsl");
+
+        let fragment_shader = graphics
+            .load_preprocessed_wgsl_from_str(&preprocessor, &self.fragment_source)
+            .map_err(|err| err.to_string())?;
+
+        let render_pipeline = RenderPipelineBuilder::new(game_io)
+            .with_uniform_bind_group(&self.uniform_entries)
+            .with_instance_bind_group(
+                SpritePipeline::<SpriteInstanceData>::instance_bind_group_layout(),
+            )
+            .with_vertex_shader(default_pipeline.shader_module(), "vs_main")
+            .with_fragment_shader(&fragment_shader, "fs_main")
+            .with_blend_mode(self.blend_mode)
+            .build::<SpriteVertex, SpriteInstanceData>()?;
+
+        Ok(SpriteMaterial {
+            pipeline: SpritePipeline::from_custom_pipeline(render_pipeline),
+            uniforms: self.uniforms,
+        })
+    }
+}
@@ -20,6 +20,46 @@ impl<'a> SpriteQueue<'a, SpriteInstanceData> {
 
         Self::new(game_io, render_pipeline, uniform_resources)
     }
+
+    pub fn new_with_blend_mode<'b, I>(
+        game_io: &'a GameIO,
+        blend_mode: BlendMode,
+        uniform_resources: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = BindingResource<'b>>,
+    {
+        let default_sprite_pipeline = game_io.resource::<DefaultSpritePipeline>().unwrap();
+        let render_pipeline = default_sprite_pipeline.as_blended_sprite_pipeline(blend_mode);
+
+        Self::new(game_io, render_pipeline, uniform_resources)
+    }
+
+    /// For use within a stencil section, see `RenderPass::begin_stencil_section`
+    pub fn new_with_stencil_mode<'b, I>(
+        game_io: &'a GameIO,
+        stencil_mode: StencilMode,
+        uniform_resources: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = BindingResource<'b>>,
+    {
+        let default_sprite_pipeline = game_io.resource::<DefaultSpritePipeline>().unwrap();
+        let render_pipeline = default_sprite_pipeline.as_stencil_sprite_pipeline(stencil_mode);
+
+        Self::new(game_io, render_pipeline, uniform_resources)
+    }
+
+    /// Uses the material's pipeline, the camera is followed by the material's uniforms
+    pub fn new_with_material(
+        game_io: &'a GameIO,
+        material: &'a SpriteMaterial,
+        camera: BindingResource<'_>,
+    ) -> Self {
+        let uniform_resources = std::iter::once(camera).chain(material.uniform_bindings());
+
+        Self::new(game_io, material.as_sprite_pipeline(), uniform_resources)
+    }
 }
 
 impl<'a, InstanceData: self::InstanceData> SpriteQueue<'a, InstanceData> {
@@ -65,6 +105,10 @@ impl<'a, InstanceData: self::InstanceData> SpriteQueue<'a, InstanceData> {
         self.render_queue.set_scissor(rect);
     }
 
+    pub fn set_stencil_reference(&mut self, reference: u32) {
+        self.render_queue.set_stencil_reference(reference);
+    }
+
     pub fn draw_sprite<Instance: self::Instance<InstanceData>>(&mut self, sprite: &Instance) {
         self.render_queue.draw_instance(self.mesh, sprite);
     }
@@ -1,19 +1,4 @@
-struct CameraUniform {
-    view_proj: mat4x4<f32>,
-};
-
-@group(0) @binding(0)
-var<uniform> camera: CameraUniform;
-
-struct VertexInput {
-    @location(0) vertex: vec2<f32>,
-    @location(1) uv: vec2<f32>,
-    @location(2) transform0: vec3<f32>,
-    @location(3) transform1: vec3<f32>,
-    @location(4) transform2: vec3<f32>,
-    @location(5) bounds: vec4<f32>,
-    @location(6) color: vec4<f32>,
-};
+#include <framework/sprite_vertex.wgsl>
 
 struct VertexOutput {
     @builtin(position) position: vec4<f32>,
@@ -22,22 +7,11 @@ struct VertexOutput {
 };
 
 @vertex
-fn vs_main(v_in: VertexInput) -> VertexOutput {
+fn vs_main(v_in: SpriteVertexInput) -> VertexOutput {
     var v_out : VertexOutput;
 
-    let transform = mat3x3<f32>(
-        v_in.transform0,
-        v_in.transform1,
-        v_in.transform2,
-    );
-
-    let transformed_position = transform * vec3<f32>(v_in.vertex.x, v_in.vertex.y, 1.0);
-
-    v_out.position =
-        camera.view_proj * vec4<f32>(transformed_position.x, transformed_position.y, 0.0, 1.0);
-
-    v_out.uv.x = v_in.bounds.x + v_in.uv.x * v_in.bounds.z;
-    v_out.uv.y = v_in.bounds.y + v_in.uv.y * v_in.bounds.w;
+    v_out.position = sprite_position(v_in);
+    v_out.uv = sprite_uv(v_in);
     v_out.color = v_in.color;
 
     return v_out;
@@ -53,3 +27,25 @@ var smplr: sampler;
 fn fs_main(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>) -> @location(0) vec4<f32> {
     return color * textureSample(txture, smplr, uv);
 }
+
+// used for blend modes that weigh the source by alpha through premultiplied colors
+@fragment
+fn fs_premultiplied(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = color * textureSample(txture, smplr, uv);
+
+    return vec4<f32>(sample.rgb * sample.a, sample.a);
+}
+
+// pixels below this alpha are left out of stencil masks
+const ALPHA_CUTOFF: f32 = 0.5;
+
+@fragment
+fn fs_mask(@location(0) uv: vec2<f32>, @location(1) color: vec4<f32>) -> @location(0) vec4<f32> {
+    let sample = color * textureSample(txture, smplr, uv);
+
+    if sample.a < ALPHA_CUTOFF {
+        discard;
+    }
+
+    return sample;
+}
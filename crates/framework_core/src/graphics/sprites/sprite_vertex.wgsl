@@ -0,0 +1,27 @@
+#include <framework/camera.wgsl>
+
+struct SpriteVertexInput {
+    @location(0) vertex: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) transform0: vec3<f32>,
+    @location(3) transform1: vec3<f32>,
+    @location(4) transform2: vec3<f32>,
+    @location(5) bounds: vec4<f32>,
+    @location(6) color: vec4<f32>,
+};
+
+fn sprite_position(v_in: SpriteVertexInput) -> vec4<f32> {
+    let transform = mat3x3<f32>(
+        v_in.transform0,
+        v_in.transform1,
+        v_in.transform2,
+    );
+
+    let transformed_position = transform * vec3<f32>(v_in.vertex.x, v_in.vertex.y, 1.0);
+
+    return camera.view_proj * vec4<f32>(transformed_position.x, transformed_position.y, 0.0, 1.0);
+}
+
+fn sprite_uv(v_in: SpriteVertexInput) -> vec2<f32> {
+    return v_in.bounds.xy + v_in.uv * v_in.bounds.zw;
+}
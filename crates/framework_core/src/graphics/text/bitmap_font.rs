@@ -0,0 +1,159 @@
+use crate::common::GameIO;
+use crate::graphics::*;
+use math::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A monospaced font read from a grid of equally sized glyphs.
+/// Glyphs are ordered left to right, top to bottom, matching the order of the `characters` passed to `new`
+#[derive(Clone)]
+pub struct BitmapFont {
+    sprite: Sprite,
+    glyph_size: Vec2,
+    columns: u32,
+    glyph_indices: HashMap<char, u32>,
+    letter_spacing: f32,
+    line_spacing: f32,
+}
+
+impl BitmapFont {
+    pub fn new(
+        game_io: &GameIO,
+        texture: Arc<Texture>,
+        glyph_size: Vec2,
+        characters: &str,
+    ) -> Self {
+        let columns = (texture.width() as f32 / glyph_size.x).floor().max(1.0) as u32;
+
+        let glyph_indices = characters
+            .chars()
+            .enumerate()
+            .map(|(i, c)| (c, i as u32))
+            .collect();
+
+        Self {
+            sprite: Sprite::new(game_io, texture),
+            glyph_size,
+            columns,
+            glyph_indices,
+            letter_spacing: 0.0,
+            line_spacing: 0.0,
+        }
+    }
+
+    pub fn with_sampler(mut self, sampler: Arc<TextureSampler>) -> Self {
+        self.sprite.set_sampler(sampler);
+        self
+    }
+
+    pub fn with_letter_spacing(mut self, spacing: f32) -> Self {
+        self.letter_spacing = spacing;
+        self
+    }
+
+    pub fn with_line_spacing(mut self, spacing: f32) -> Self {
+        self.line_spacing = spacing;
+        self
+    }
+
+    pub fn glyph_size(&self) -> Vec2 {
+        self.glyph_size
+    }
+
+    pub fn line_height(&self) -> f32 {
+        self.glyph_size.y + self.line_spacing
+    }
+
+    pub fn has_glyph(&self, c: char) -> bool {
+        self.glyph_indices.contains_key(&c)
+    }
+
+    /// The region of the texture used for a character in pixels
+    pub fn glyph_frame(&self, c: char) -> Option<Rect> {
+        let index = *self.glyph_indices.get(&c)?;
+        let col = index % self.columns;
+        let row = index / self.columns;
+
+        Some(Rect::new(
+            col as f32 * self.glyph_size.x,
+            row as f32 * self.glyph_size.y,
+            self.glyph_size.x,
+            self.glyph_size.y,
+        ))
+    }
+
+    /// The unscaled size of the text, lines are separated by '\n'
+    pub fn measure(&self, text: &str) -> Vec2 {
+        let mut size = Vec2::ZERO;
+
+        for (i, line) in text.split('\n').enumerate() {
+            let char_count = line.chars().count();
+            let width = if char_count > 0 {
+                char_count as f32 * (self.glyph_size.x + self.letter_spacing) - self.letter_spacing
+            } else {
+                0.0
+            };
+
+            size.x = size.x.max(width);
+
+            if i > 0 {
+                size.y += self.line_spacing;
+            }
+
+            size.y += self.glyph_size.y;
+        }
+
+        size
+    }
+
+    /// The offset of a character from the start of the text, useful for placing cursors
+    pub fn char_offset(&self, text: &str, char_index: usize) -> Vec2 {
+        let mut offset = Vec2::ZERO;
+
+        for c in text.chars().take(char_index) {
+            if c == '\n' {
+                offset.x = 0.0;
+                offset.y += self.line_height();
+            } else {
+                offset.x += self.glyph_size.x + self.letter_spacing;
+            }
+        }
+
+        offset
+    }
+
+    /// Draws text with the top left at `position`, expects a camera with an inverted y axis.
+    /// Characters missing from the font are skipped but still take up space
+    pub fn draw_text(
+        &self,
+        sprite_queue: &mut SpriteQueue,
+        text: &str,
+        position: Vec2,
+        scale: Vec2,
+        color: Color,
+    ) {
+        let mut sprite = self.sprite.clone();
+        sprite.set_color(color);
+        sprite.set_origin(Vec2::ZERO);
+
+        let advance = (self.glyph_size.x + self.letter_spacing) * scale.x;
+        let mut glyph_position = position;
+
+        for c in text.chars() {
+            if c == '\n' {
+                glyph_position.x = position.x;
+                glyph_position.y += self.line_height() * scale.y;
+                continue;
+            }
+
+            if let Some(frame) = self.glyph_frame(c) {
+                sprite.set_frame(frame);
+                sprite.set_position(glyph_position);
+                sprite.set_size(self.glyph_size * scale);
+                sprite_queue.draw_sprite(&sprite);
+            }
+
+            glyph_position.x += advance;
+        }
+    }
+}
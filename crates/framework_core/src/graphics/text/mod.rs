@@ -0,0 +1,3 @@
+mod bitmap_font;
+
+pub use bitmap_font::*;
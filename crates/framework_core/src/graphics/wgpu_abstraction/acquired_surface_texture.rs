@@ -0,0 +1,8 @@
+/// The result of `GraphicsContext::acquire_surface_texture()`
+pub enum AcquiredSurfaceTexture {
+    Ready(wgpu::SurfaceTexture),
+    /// Nothing can be presented this frame, such as while the window is minimized
+    Skipped,
+    /// The surface must be recreated before presenting again
+    Lost,
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+/// Bind groups unused for this many frames are dropped
+const MAX_UNUSED_FRAMES: u64 = 120;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum BindingKey {
+    Buffer {
+        buffer: wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        size: Option<wgpu::BufferSize>,
+    },
+    Sampler(wgpu::Sampler),
+    TextureView(wgpu::TextureView),
+}
+
+impl BindingKey {
+    fn new(resource: &wgpu::BindingResource) -> Option<Self> {
+        let key = match resource {
+            wgpu::BindingResource::Buffer(binding) => BindingKey::Buffer {
+                buffer: binding.buffer.clone(),
+                offset: binding.offset,
+                size: binding.size,
+            },
+            wgpu::BindingResource::Sampler(sampler) => BindingKey::Sampler((*sampler).clone()),
+            wgpu::BindingResource::TextureView(view) => BindingKey::TextureView((*view).clone()),
+            _ => return None,
+        };
+
+        Some(key)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct BindGroupKey {
+    layout: wgpu::BindGroupLayout,
+    // entries are bound in order, so the binding index is implied
+    resources: Vec<BindingKey>,
+}
+
+struct CachedBindGroup {
+    bind_group: wgpu::BindGroup,
+    last_used: u64,
+}
+
+/// Reuses bind groups with the same layout and resources.
+///
+/// Keys hold handles to the resources, preventing them from being freed and their identity reused
+/// while the bind group is cached.
+pub(crate) struct BindGroupCache {
+    frame: u64,
+    bind_groups: HashMap<BindGroupKey, CachedBindGroup>,
+}
+
+impl BindGroupCache {
+    pub(crate) fn new() -> Self {
+        Self {
+            frame: 0,
+            bind_groups: HashMap::new(),
+        }
+    }
+
+    /// Expects entries to be ordered by binding index, starting from 0
+    pub(crate) fn get_or_create(
+        &mut self,
+        device: &wgpu::Device,
+        label: Option<&str>,
+        layout: &wgpu::BindGroupLayout,
+        entries: &[wgpu::BindGroupEntry],
+    ) -> wgpu::BindGroup {
+        let resources: Option<Vec<_>> = entries
+            .iter()
+            .map(|entry| BindingKey::new(&entry.resource))
+            .collect();
+
+        let create_bind_group = || {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label,
+                layout,
+                entries,
+            })
+        };
+
+        let Some(resources) = resources else {
+            // arrays and other uncommon resources aren't cached
+            return create_bind_group();
+        };
+
+        let key = BindGroupKey {
+            layout: layout.clone(),
+            resources,
+        };
+
+        let cached = self
+            .bind_groups
+            .entry(key)
+            .or_insert_with(|| CachedBindGroup {
+                bind_group: create_bind_group(),
+                last_used: 0,
+            });
+
+        cached.last_used = self.frame;
+        cached.bind_group.clone()
+    }
+
+    /// Drops bind groups that haven't been used recently
+    pub(crate) fn end_frame(&mut self) {
+        let frame = self.frame;
+
+        self.bind_groups
+            .retain(|_, cached| frame - cached.last_used < MAX_UNUSED_FRAMES);
+
+        self.frame += 1;
+    }
+}
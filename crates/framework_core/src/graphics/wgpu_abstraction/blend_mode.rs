@@ -0,0 +1,141 @@
+/// Common blend states, expects colors without premultiplied alpha unless noted
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    #[default]
+    Alpha,
+    /// Expects colors with alpha already multiplied in
+    Premultiplied,
+    Additive,
+    /// Expects colors with alpha already multiplied in, the default sprite pipeline handles this
+    Multiply,
+    /// Expects colors with alpha already multiplied in, the default sprite pipeline handles this
+    Screen,
+    Subtract,
+}
+
+impl BlendMode {
+    pub const ALL: [BlendMode; 6] = [
+        BlendMode::Alpha,
+        BlendMode::Premultiplied,
+        BlendMode::Additive,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::Subtract,
+    ];
+
+    pub fn blend_state(self) -> wgpu::BlendState {
+        use wgpu::{BlendComponent, BlendFactor, BlendOperation};
+
+        let color = match self {
+            BlendMode::Alpha => return wgpu::BlendState::ALPHA_BLENDING,
+            BlendMode::Premultiplied => return wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING,
+            BlendMode::Additive => BlendComponent {
+                src_factor: BlendFactor::SrcAlpha,
+                dst_factor: BlendFactor::One,
+                operation: BlendOperation::Add,
+            },
+            // transparent pixels leave the destination unchanged,
+            // weighing the source by alpha requires premultiplied colors
+            BlendMode::Multiply => BlendComponent {
+                src_factor: BlendFactor::Dst,
+                dst_factor: BlendFactor::OneMinusSrcAlpha,
+                operation: BlendOperation::Add,
+            },
+            BlendMode::Screen => BlendComponent {
+                src_factor: BlendFactor::OneMinusDst,
+                dst_factor: BlendFactor::One,
+                operation: BlendOperation::Add,
+            },
+            BlendMode::Subtract => BlendComponent {
+                src_factor: BlendFactor::SrcAlpha,
+                dst_factor: BlendFactor::One,
+                operation: BlendOperation::ReverseSubtract,
+            },
+        };
+
+        wgpu::BlendState {
+            color,
+            // alpha accumulates coverage the same way for every mode
+            alpha: BlendComponent {
+                src_factor: BlendFactor::One,
+                dst_factor: BlendFactor::OneMinusSrcAlpha,
+                operation: BlendOperation::Add,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use wgpu::{BlendComponent, BlendFactor, BlendOperation};
+
+    fn blend_channel(component: BlendComponent, src: f32, src_a: f32, dst: f32) -> f32 {
+        let factor = |factor: BlendFactor| match factor {
+            BlendFactor::Zero => 0.0,
+            BlendFactor::One => 1.0,
+            BlendFactor::Src => src,
+            BlendFactor::SrcAlpha => src_a,
+            BlendFactor::OneMinusSrcAlpha => 1.0 - src_a,
+            BlendFactor::Dst => dst,
+            BlendFactor::OneMinusDst => 1.0 - dst,
+            _ => unimplemented!("{factor:?}"),
+        };
+
+        let src_term = src * factor(component.src_factor);
+        let dst_term = dst * factor(component.dst_factor);
+
+        match component.operation {
+            BlendOperation::Add => src_term + dst_term,
+            BlendOperation::ReverseSubtract => dst_term - src_term,
+            _ => unimplemented!("{:?}", component.operation),
+        }
+    }
+
+    /// Blends a channel the way the default sprite pipeline does, premultiplying when required
+    fn blend(mode: BlendMode, src: f32, src_a: f32, dst: f32) -> f32 {
+        let src = if matches!(mode, BlendMode::Multiply | BlendMode::Screen) {
+            src * src_a
+        } else {
+            src
+        };
+
+        blend_channel(mode.blend_state().color, src, src_a, dst)
+    }
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 1e-5, "{a} != {b}");
+    }
+
+    #[test]
+    fn multiply() {
+        let dst = 0.8;
+
+        for src in [0.0, 0.25, 1.0] {
+            // a transparent pixel of any color leaves the destination unchanged
+            assert_close(blend(BlendMode::Multiply, src, 0.0, dst), dst);
+            assert_close(blend(BlendMode::Multiply, src, 1.0, dst), src * dst);
+            // halfway between the destination and the fully multiplied result
+            assert_close(
+                blend(BlendMode::Multiply, src, 0.5, dst),
+                (dst + src * dst) * 0.5,
+            );
+        }
+    }
+
+    #[test]
+    fn screen() {
+        let dst = 0.4;
+
+        for src in [0.0, 0.25, 1.0] {
+            let screened = 1.0 - (1.0 - src) * (1.0 - dst);
+
+            assert_close(blend(BlendMode::Screen, src, 0.0, dst), dst);
+            assert_close(blend(BlendMode::Screen, src, 1.0, dst), screened);
+            assert_close(
+                blend(BlendMode::Screen, src, 0.5, dst),
+                (dst + screened) * 0.5,
+            );
+        }
+    }
+}
@@ -61,6 +61,18 @@ impl Color {
         self
     }
 
+    /// Clamps and rounds each component
+    pub fn to_rgba_u8s(self) -> [u8; 4] {
+        let convert = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        [
+            convert(self.r),
+            convert(self.g),
+            convert(self.b),
+            convert(self.a),
+        ]
+    }
+
     pub fn to_srgb(mut self) -> Self {
         self.r = to_srgb(self.r);
         self.g = to_srgb(self.g);
@@ -0,0 +1,241 @@
+use super::instance_buffer_ring::align;
+use crate::graphics::*;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// A mesh with vertices and indices that can be updated after creation.
+///
+/// Writes are applied before the next submission,
+/// so draws queued earlier in the frame will also use the updated data.
+pub struct DynamicMesh<Vertex: super::Vertex> {
+    graphics: GraphicsContext,
+    mesh: Arc<Mesh<Vertex>>,
+    vertex_capacity: usize,
+    index_capacity: usize,
+}
+
+impl<Vertex: super::Vertex> DynamicMesh<Vertex> {
+    pub fn new(graphics: &impl HasGraphicsContext, vertices: &[Vertex], indices: &[u32]) -> Self {
+        let mut mesh = Self::with_capacity(graphics, vertices.len(), indices.len());
+        mesh.set_vertices(vertices);
+        mesh.set_indices(indices);
+        mesh
+    }
+
+    pub fn with_capacity(
+        graphics: &impl HasGraphicsContext,
+        vertex_capacity: usize,
+        index_capacity: usize,
+    ) -> Self {
+        let graphics = graphics.graphics();
+
+        let buffers = (
+            create_buffer::<Vertex>(graphics, vertex_capacity, wgpu::BufferUsages::VERTEX),
+            create_buffer::<u32>(graphics, index_capacity, wgpu::BufferUsages::INDEX),
+        );
+
+        Self {
+            graphics: graphics.clone(),
+            mesh: Arc::new(Mesh {
+                vertices: Vec::new(),
+                indices: Vec::new(),
+                buffers,
+            }),
+            vertex_capacity,
+            index_capacity,
+        }
+    }
+
+    /// The mesh to draw with, replaced after every update
+    pub fn mesh(&self) -> &Arc<Mesh<Vertex>> {
+        &self.mesh
+    }
+
+    pub fn vertices(&self) -> &[Vertex] {
+        self.mesh.vertices()
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        self.mesh.indices()
+    }
+
+    pub fn vertex_capacity(&self) -> usize {
+        self.vertex_capacity
+    }
+
+    pub fn index_capacity(&self) -> usize {
+        self.index_capacity
+    }
+
+    /// Replaces every vertex
+    pub fn set_vertices(&mut self, vertices: &[Vertex]) {
+        let mesh = Arc::make_mut(&mut self.mesh);
+        mesh.vertices.clear();
+        self.update_vertices(0, vertices);
+    }
+
+    /// Replaces every index
+    pub fn set_indices(&mut self, indices: &[u32]) {
+        let mesh = Arc::make_mut(&mut self.mesh);
+        mesh.indices.clear();
+        self.update_indices(0, indices);
+    }
+
+    /// Overwrites vertices starting at `start`, vertices past the end are appended.
+    ///
+    /// Panics if `start` is greater than the vertex count.
+    pub fn update_vertices(&mut self, start: usize, vertices: &[Vertex]) {
+        let mesh = Arc::make_mut(&mut self.mesh);
+        let range = splice(&mut mesh.vertices, start, vertices);
+
+        if mesh.vertices.len() > self.vertex_capacity {
+            self.vertex_capacity = grown_capacity(self.vertex_capacity, mesh.vertices.len());
+            mesh.buffers.0 = create_buffer::<Vertex>(
+                &self.graphics,
+                self.vertex_capacity,
+                wgpu::BufferUsages::VERTEX,
+            );
+            write_range(
+                &self.graphics,
+                &mesh.buffers.0,
+                &mesh.vertices,
+                0..mesh.vertices.len(),
+            );
+        } else {
+            write_range(&self.graphics, &mesh.buffers.0, &mesh.vertices, range);
+        }
+    }
+
+    /// Overwrites indices starting at `start`, indices past the end are appended.
+    ///
+    /// Panics if `start` is greater than the index count.
+    pub fn update_indices(&mut self, start: usize, indices: &[u32]) {
+        let mesh = Arc::make_mut(&mut self.mesh);
+        let range = splice(&mut mesh.indices, start, indices);
+
+        if mesh.indices.len() > self.index_capacity {
+            self.index_capacity = grown_capacity(self.index_capacity, mesh.indices.len());
+            mesh.buffers.1 = create_buffer::<u32>(
+                &self.graphics,
+                self.index_capacity,
+                wgpu::BufferUsages::INDEX,
+            );
+            write_range(
+                &self.graphics,
+                &mesh.buffers.1,
+                &mesh.indices,
+                0..mesh.indices.len(),
+            );
+        } else {
+            write_range(&self.graphics, &mesh.buffers.1, &mesh.indices, range);
+        }
+    }
+
+    /// Shortens the mesh, keeping the capacity
+    pub fn truncate(&mut self, vertex_count: usize, index_count: usize) {
+        let mesh = Arc::make_mut(&mut self.mesh);
+        mesh.vertices.truncate(vertex_count);
+        mesh.indices.truncate(index_count);
+    }
+}
+
+/// Overwrites and extends `list` with `values` from `start`, returns the modified range
+fn splice<T: Copy>(list: &mut Vec<T>, start: usize, values: &[T]) -> Range<usize> {
+    assert!(start <= list.len(), "start is out of bounds");
+
+    let end = start + values.len();
+    let overlap = (list.len() - start).min(values.len());
+
+    list[start..start + overlap].copy_from_slice(&values[..overlap]);
+    list.extend_from_slice(&values[overlap..]);
+
+    start..end
+}
+
+fn grown_capacity(capacity: usize, required: usize) -> usize {
+    (capacity * 2).max(required)
+}
+
+fn create_buffer<T>(
+    graphics: &GraphicsContext,
+    capacity: usize,
+    usage: wgpu::BufferUsages,
+) -> wgpu::Buffer {
+    let size = align((capacity * std::mem::size_of::<T>()) as wgpu::BufferAddress);
+
+    graphics.device().create_buffer(&wgpu::BufferDescriptor {
+        label: Some("dynamic_mesh_buffer"),
+        // avoiding empty buffers
+        size: size.max(wgpu::COPY_BUFFER_ALIGNMENT),
+        usage: usage | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+/// Writes `list[range]` to the buffer, widening the write to satisfy COPY_BUFFER_ALIGNMENT
+fn write_range<T: bytemuck::Pod>(
+    graphics: &GraphicsContext,
+    buffer: &wgpu::Buffer,
+    list: &[T],
+    range: Range<usize>,
+) {
+    if range.is_empty() {
+        return;
+    }
+
+    let bytes: &[u8] = bytemuck::cast_slice(list);
+    let item_size = std::mem::size_of::<T>();
+    let byte_range = aligned_byte_range(range.start * item_size..range.end * item_size);
+
+    let queue = graphics.queue();
+
+    if byte_range.end <= bytes.len() {
+        queue.write_buffer(buffer, byte_range.start as u64, &bytes[byte_range]);
+    } else {
+        // pad the tail, the buffer's size is already aligned
+        let mut padded = bytes[byte_range.start..].to_vec();
+        padded.resize(byte_range.len(), 0);
+        queue.write_buffer(buffer, byte_range.start as u64, &padded);
+    }
+}
+
+fn aligned_byte_range(range: Range<usize>) -> Range<usize> {
+    let alignment = wgpu::COPY_BUFFER_ALIGNMENT as usize;
+    let start = range.start - range.start % alignment;
+    let end = align(range.end as wgpu::BufferAddress) as usize;
+
+    start..end
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn splicing() {
+        let mut list = vec![1, 2, 3];
+
+        assert_eq!(splice(&mut list, 1, &[5]), 1..2);
+        assert_eq!(list, [1, 5, 3]);
+
+        assert_eq!(splice(&mut list, 2, &[6, 7, 8]), 2..5);
+        assert_eq!(list, [1, 5, 6, 7, 8]);
+
+        assert_eq!(splice(&mut list, 5, &[9]), 5..6);
+        assert_eq!(list, [1, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn splicing_out_of_bounds() {
+        let mut list = vec![1, 2, 3];
+        splice(&mut list, 4, &[5]);
+    }
+
+    #[test]
+    fn byte_range_alignment() {
+        assert_eq!(aligned_byte_range(0..4), 0..4);
+        assert_eq!(aligned_byte_range(6..10), 4..12);
+        assert_eq!(aligned_byte_range(8..9), 8..12);
+    }
+}
@@ -1,3 +1,6 @@
+use super::bind_group_cache::BindGroupCache;
+use super::instance_buffer_ring::InstanceBufferRing;
+use super::mipmap_generator::MipmapGenerator;
 use crate::async_task::SyncResultAsyncError;
 use crate::graphics::*;
 use cfg_macros::*;
@@ -5,6 +8,8 @@ use logging::log;
 use std::borrow::Cow;
 use std::future::Future;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 pub trait HasGraphicsContext {
     fn graphics(&self) -> &GraphicsContext;
@@ -17,6 +22,10 @@ pub struct GraphicsContext {
     device: wgpu::Device,
     queue: wgpu::Queue,
     texture_format: wgpu::TextureFormat,
+    instance_buffer_ring: Arc<Mutex<InstanceBufferRing>>,
+    bind_group_cache: Arc<Mutex<BindGroupCache>>,
+    mipmap_generator: Arc<Mutex<MipmapGenerator>>,
+    device_lost: Arc<AtomicBool>,
 }
 
 impl HasGraphicsContext for GraphicsContext {
@@ -55,6 +64,11 @@ impl GraphicsContext {
             }
         };
 
+        // optional features, enabled when available
+        let required_features = adapter.features()
+            & (wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER
+                | wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES);
+
         let mut i = 0;
         let mut last_error: Option<wgpu::RequestDeviceError> = None;
 
@@ -76,7 +90,7 @@ impl GraphicsContext {
                     .request_device(&wgpu::DeviceDescriptor {
                         label: None,
                         required_limits,
-                        required_features: wgpu::Features::empty(),
+                        required_features,
                         memory_hints: wgpu::MemoryHints::default(),
                         trace: wgpu::Trace::Off,
                         experimental_features: wgpu::ExperimentalFeatures::disabled(),
@@ -92,6 +106,17 @@ impl GraphicsContext {
             i += 1;
         };
 
+        let device_lost = Arc::new(AtomicBool::new(false));
+        let device_lost_flag = device_lost.clone();
+
+        device.set_device_lost_callback(move |reason, message| {
+            // destroyed devices are intentional
+            if reason == wgpu::DeviceLostReason::Unknown {
+                log::error!("Graphics device lost: {message}");
+                device_lost_flag.store(true, Ordering::Relaxed);
+            }
+        });
+
         log::trace!("WGPU Initialized");
 
         Ok(GraphicsContext {
@@ -100,9 +125,32 @@ impl GraphicsContext {
             device,
             queue,
             texture_format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            instance_buffer_ring: Arc::new(Mutex::new(InstanceBufferRing::new())),
+            bind_group_cache: Arc::new(Mutex::new(BindGroupCache::new())),
+            mipmap_generator: Arc::new(Mutex::new(MipmapGenerator::new())),
+            device_lost,
         })
     }
 
+    /// Creates a context with a new adapter and device from the same instance, keeping the default texture format.
+    ///
+    /// Resources created from this context are unusable with the new context.
+    pub async fn recreate(
+        &self,
+        surface: Option<&wgpu::Surface<'_>>,
+    ) -> anyhow::Result<GraphicsContext> {
+        let mut graphics = GraphicsContext::new(self.instance.clone(), surface).await?;
+        graphics.set_default_texture_format(self.texture_format);
+
+        Ok(graphics)
+    }
+
+    /// True after the device is lost to a driver reset or similar,
+    /// the runtime recovers by recreating the context, see `GameWindowLifecycle::recreate_graphics()`
+    pub fn device_lost(&self) -> bool {
+        self.device_lost.load(Ordering::Relaxed)
+    }
+
     pub fn wgpu_instance(&self) -> &wgpu::Instance {
         &self.instance
     }
@@ -127,6 +175,109 @@ impl GraphicsContext {
         self.texture_format = format;
     }
 
+    /// The highest sample count up to `requested` usable by multisampled render targets of the format
+    pub fn supported_sample_count(&self, format: wgpu::TextureFormat, requested: u32) -> u32 {
+        let device_features = self.device.features();
+
+        let flags =
+            if device_features.contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES) {
+                self.adapter.get_texture_format_features(format).flags
+            } else {
+                format.guaranteed_format_features(device_features).flags
+            };
+
+        [16, 8, 4, 2]
+            .into_iter()
+            .find(|&count| count <= requested && flags.sample_count_supported(count))
+            .unwrap_or(1)
+    }
+
+    /// Acquires the next texture of a surface configured with this context's device,
+    /// reconfiguring outdated surfaces and logging failures
+    pub fn acquire_surface_texture(
+        &self,
+        surface: &wgpu::Surface<'_>,
+        config: &wgpu::SurfaceConfiguration,
+    ) -> AcquiredSurfaceTexture {
+        // a second attempt is made after reconfiguring
+        for retry in [true, false] {
+            match surface.get_current_texture() {
+                wgpu::CurrentSurfaceTexture::Success(surface_texture) => {
+                    return AcquiredSurfaceTexture::Ready(surface_texture);
+                }
+                wgpu::CurrentSurfaceTexture::Suboptimal(surface_texture) => {
+                    if !retry {
+                        return AcquiredSurfaceTexture::Ready(surface_texture);
+                    }
+
+                    log::debug!("Surface is suboptimal, reconfiguring");
+
+                    // the texture must be released before configuring
+                    drop(surface_texture);
+                    surface.configure(&self.device, config);
+                }
+                wgpu::CurrentSurfaceTexture::Outdated => {
+                    if !retry {
+                        log::warn!("Surface is outdated after reconfiguring, skipping frame");
+                        return AcquiredSurfaceTexture::Skipped;
+                    }
+
+                    log::debug!("Surface is outdated, reconfiguring");
+                    surface.configure(&self.device, config);
+                }
+                wgpu::CurrentSurfaceTexture::Timeout => {
+                    log::warn!("Timed out acquiring surface texture, skipping frame");
+                    return AcquiredSurfaceTexture::Skipped;
+                }
+                wgpu::CurrentSurfaceTexture::Occluded => {
+                    log::trace!("Surface is occluded, skipping frame");
+                    return AcquiredSurfaceTexture::Skipped;
+                }
+                wgpu::CurrentSurfaceTexture::Lost => {
+                    log::warn!("Surface lost");
+                    return AcquiredSurfaceTexture::Lost;
+                }
+                wgpu::CurrentSurfaceTexture::Validation => {
+                    log::error!("Validation error while acquiring surface texture, skipping frame");
+                    return AcquiredSurfaceTexture::Skipped;
+                }
+            }
+        }
+
+        AcquiredSurfaceTexture::Skipped
+    }
+
+    /// Recycles instance buffer space and drops unused bind groups.
+    ///
+    /// Called by the runtime after each frame is submitted.
+    pub fn end_frame(&self) {
+        self.instance_buffer_ring.lock().unwrap().reset();
+        self.bind_group_cache.lock().unwrap().end_frame();
+    }
+
+    /// Writes to the shared instance buffer, returns the buffer and the offset of the data.
+    ///
+    /// The data is valid until the end of the frame.
+    pub(crate) fn write_instance_data(&self, data: &[u8]) -> (wgpu::Buffer, wgpu::BufferAddress) {
+        let mut ring = self.instance_buffer_ring.lock().unwrap();
+        ring.write(&self.device, &self.queue, data)
+    }
+
+    pub(crate) fn cached_bind_group(
+        &self,
+        label: Option<&str>,
+        layout: &wgpu::BindGroupLayout,
+        entries: &[wgpu::BindGroupEntry],
+    ) -> wgpu::BindGroup {
+        let mut cache = self.bind_group_cache.lock().unwrap();
+        cache.get_or_create(&self.device, label, layout, entries)
+    }
+
+    pub(crate) fn generate_mipmaps(&self, texture: &wgpu::Texture) {
+        let mut generator = self.mipmap_generator.lock().unwrap();
+        generator.generate(&self.device, &self.queue, texture);
+    }
+
     pub fn load_wgsl<P: AsRef<Path> + ?Sized>(
         &self,
         path: &P,
@@ -160,6 +311,45 @@ impl GraphicsContext {
         })
     }
 
+    /// Processes the file with the preprocessor and validates the result,
+    /// errors are reported at their location in the original files
+    pub fn load_preprocessed_wgsl<P: AsRef<Path> + ?Sized>(
+        &self,
+        preprocessor: &WgslPreprocessor,
+        path: &P,
+    ) -> Result<wgpu::ShaderModule, WgslPreprocessorError> {
+        let wgsl = preprocessor.process_file(path)?;
+        let label = path.as_ref().to_string_lossy();
+
+        self.create_preprocessed_shader(Some(label.as_ref()), &wgsl)
+    }
+
+    /// See `load_preprocessed_wgsl()`
+    pub fn load_preprocessed_wgsl_from_str(
+        &self,
+        preprocessor: &WgslPreprocessor,
+        source: &str,
+    ) -> Result<wgpu::ShaderModule, WgslPreprocessorError> {
+        let wgsl = preprocessor.process_str(source)?;
+
+        self.create_preprocessed_shader(None, &wgsl)
+    }
+
+    fn create_preprocessed_shader(
+        &self,
+        label: Option<&str>,
+        wgsl: &PreprocessedWgsl,
+    ) -> Result<wgpu::ShaderModule, WgslPreprocessorError> {
+        wgsl.validate()?;
+
+        Ok(self
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label,
+                source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(wgsl.source())),
+            }))
+    }
+
     pub fn load_shader_from_descriptor(
         &self,
         descriptor: wgpu::ShaderModuleDescriptor,
@@ -1,3 +1,4 @@
+use super::render_pipeline_variants::VertexBufferDescription;
 use crate::graphics::*;
 use std::sync::Arc;
 
@@ -51,7 +52,7 @@ impl InstanceLayout {
         }
     }
 
-    pub(super) fn build<Vertex>(&self) -> Result<wgpu::VertexBufferLayout<'_>, String> {
+    pub(super) fn build<Vertex>(self) -> Result<VertexBufferDescription, String> {
         let array_stride = std::mem::size_of::<Vertex>() as wgpu::BufferAddress;
 
         if array_stride < self.size {
@@ -60,10 +61,10 @@ impl InstanceLayout {
             ));
         }
 
-        Ok(wgpu::VertexBufferLayout {
+        Ok(VertexBufferDescription {
             array_stride,
             step_mode: wgpu::VertexStepMode::Instance,
-            attributes: &self.attributes,
+            attributes: self.attributes,
         })
     }
 }
@@ -0,0 +1,97 @@
+const INITIAL_CAPACITY: wgpu::BufferAddress = 1 << 16;
+
+/// Shared vertex buffer for instance data, space is recycled every frame.
+///
+/// Writes are staged with `Queue::write_buffer()`, which applies them before the next submission.
+/// When a frame needs more space a larger buffer replaces the current one,
+/// draw calls recorded earlier in the frame keep the old buffer alive.
+pub(crate) struct InstanceBufferRing {
+    buffer: Option<wgpu::Buffer>,
+    capacity: wgpu::BufferAddress,
+    cursor: wgpu::BufferAddress,
+}
+
+impl InstanceBufferRing {
+    pub(crate) fn new() -> Self {
+        Self {
+            buffer: None,
+            capacity: 0,
+            cursor: 0,
+        }
+    }
+
+    /// Returns the buffer and the offset the data was written to
+    pub(crate) fn write(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        data: &[u8],
+    ) -> (wgpu::Buffer, wgpu::BufferAddress) {
+        let size = align(data.len() as wgpu::BufferAddress);
+
+        if self.buffer.is_none() || self.cursor + size > self.capacity {
+            self.grow(device, size);
+        }
+
+        let buffer = self.buffer.clone().unwrap();
+        let offset = self.cursor;
+
+        if size == data.len() as wgpu::BufferAddress {
+            queue.write_buffer(&buffer, offset, data);
+        } else {
+            // writes must be a multiple of COPY_BUFFER_ALIGNMENT
+            let mut padded = Vec::with_capacity(size as usize);
+            padded.extend_from_slice(data);
+            padded.resize(size as usize, 0);
+            queue.write_buffer(&buffer, offset, &padded);
+        }
+
+        self.cursor += size;
+
+        (buffer, offset)
+    }
+
+    /// Makes all space available again, data from previous frames must already be submitted
+    pub(crate) fn reset(&mut self) {
+        self.cursor = 0;
+    }
+
+    fn grow(&mut self, device: &wgpu::Device, required: wgpu::BufferAddress) {
+        let mut capacity = (self.capacity * 2).max(INITIAL_CAPACITY);
+
+        while capacity < self.cursor + required {
+            capacity *= 2;
+        }
+
+        // the previous buffer is still referenced by draw calls using it, so we start fresh
+        self.buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("instance_buffer_ring"),
+            size: capacity,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+        self.capacity = capacity;
+        self.cursor = 0;
+    }
+}
+
+/// Rounds up to satisfy both COPY_BUFFER_ALIGNMENT and VERTEX_ALIGNMENT
+pub(crate) fn align(size: wgpu::BufferAddress) -> wgpu::BufferAddress {
+    let alignment = wgpu::COPY_BUFFER_ALIGNMENT.max(wgpu::VERTEX_ALIGNMENT);
+
+    size.div_ceil(alignment) * alignment
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn alignment() {
+        assert_eq!(align(0), 0);
+        assert_eq!(align(1), 4);
+        assert_eq!(align(4), 4);
+        assert_eq!(align(6), 8);
+        assert_eq!(align(64), 64);
+    }
+}
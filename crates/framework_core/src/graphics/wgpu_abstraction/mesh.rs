@@ -2,10 +2,11 @@ use super::HasGraphicsContext;
 use std::sync::Arc;
 use wgpu::util::DeviceExt;
 
+#[derive(Clone)]
 pub struct Mesh<Vertex: super::Vertex> {
-    vertices: Vec<Vertex>,
-    indices: Vec<u32>,
-    buffers: (wgpu::Buffer, wgpu::Buffer),
+    pub(super) vertices: Vec<Vertex>,
+    pub(super) indices: Vec<u32>,
+    pub(super) buffers: (wgpu::Buffer, wgpu::Buffer),
 }
 
 impl<Vertex: super::Vertex> Mesh<Vertex> {
@@ -1,25 +1,39 @@
+mod acquired_surface_texture;
+mod bind_group_cache;
 mod bind_group_layout_entry;
 mod binding_resource;
+mod blend_mode;
 mod buffer_resource;
 mod color;
+mod dynamic_mesh;
 mod graphics_context;
 mod instance;
+mod instance_buffer_ring;
 mod mesh;
+mod mipmap_generator;
 mod model;
 mod render_pass;
 mod render_pipeline;
 mod render_pipeline_builder;
+mod render_pipeline_variants;
 mod render_queue;
 mod render_target;
+mod shader_reflection;
+mod stencil_mode;
 mod struct_resource;
 mod texture;
 mod texture_sampler;
 mod vertex;
+mod wgsl_preprocessor;
+mod wgsl_preprocessor_error;
 
+pub use acquired_surface_texture::*;
 pub use bind_group_layout_entry::*;
 pub use binding_resource::*;
+pub use blend_mode::*;
 pub use buffer_resource::*;
 pub use color::*;
+pub use dynamic_mesh::*;
 pub use graphics_context::*;
 pub use instance::*;
 pub use mesh::*;
@@ -27,10 +41,15 @@ pub use model::*;
 pub use render_pass::*;
 pub use render_pipeline::*;
 pub use render_pipeline_builder::*;
+pub use render_pipeline_variants::RenderPipelineVariants;
 pub use render_queue::*;
 pub use render_target::*;
+pub use shader_reflection::ShaderReflection;
+pub use stencil_mode::*;
 pub use struct_resource::*;
 pub use texture::*;
 pub use texture_sampler::*;
 pub use vertex::*;
 pub use wgpu::{include_wgsl, VertexFormat};
+pub use wgsl_preprocessor::*;
+pub use wgsl_preprocessor_error::*;
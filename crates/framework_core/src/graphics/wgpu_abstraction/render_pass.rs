@@ -1,13 +1,26 @@
 use crate::graphics::*;
 use math::*;
 
+struct ClippedQueue {
+    operations: Vec<RenderOperation>,
+    clip: Option<Rect>,
+}
+
+/// Queues sharing a stencil target, each section is rendered as a separate wgpu pass
+#[derive(Default)]
+struct RenderSection {
+    stencil_view: Option<wgpu::TextureView>,
+    queues: Vec<ClippedQueue>,
+}
+
 /// "RenderPasses only render when flushed"
 pub struct RenderPass<'a> {
     encoder: &'a mut wgpu::CommandEncoder,
     label: Option<&'static str>,
     color_targets: Vec<&'a RenderTarget>,
     depth_target: Option<&'a RenderTarget>,
-    queues: Vec<Vec<RenderOperation>>,
+    sections: Vec<RenderSection>,
+    clip_stack: Vec<Rect>,
     texture_size: UVec2,
     clear_color: Option<Color>,
 }
@@ -19,7 +32,8 @@ impl<'a> RenderPass<'a> {
             label: None,
             color_targets: vec![color_target],
             depth_target: None,
-            queues: Vec::new(),
+            sections: vec![RenderSection::default()],
+            clip_stack: Vec::new(),
             texture_size: color_target.size(),
             clear_color: color_target.clear_color(),
         }
@@ -39,6 +53,11 @@ impl<'a> RenderPass<'a> {
         self.texture_size
     }
 
+    /// Pipelines are resolved to variants matching the sample count of the color targets
+    pub fn sample_count(&self) -> u32 {
+        self.color_targets[0].sample_count()
+    }
+
     pub fn clear_color(&self) -> Option<Color> {
         self.clear_color
     }
@@ -49,104 +68,229 @@ impl<'a> RenderPass<'a> {
             label: Some("render_target_pass"),
             color_targets: vec![color_target],
             depth_target: None,
-            queues: Vec::new(),
+            sections: vec![RenderSection::default()],
+            clip_stack: Vec::new(),
             texture_size: color_target.size(),
             clear_color: color_target.clear_color(),
         }
     }
 
+    /// Restricts queues consumed afterwards to the rect, intersected with the current clip.
+    ///
+    /// Uses the same normalized coordinates as `RenderQueue::set_scissor`.
+    pub fn push_clip(&mut self, rect: Rect) {
+        let bounds = self.clip().unwrap_or(Rect::UNIT);
+        self.clip_stack.push(rect.scissor(bounds));
+    }
+
+    pub fn pop_clip(&mut self) -> Option<Rect> {
+        self.clip_stack.pop()
+    }
+
+    /// The intersection of every pushed clip
+    pub fn clip(&self) -> Option<Rect> {
+        self.clip_stack.last().copied()
+    }
+
+    /// Queues consumed afterwards render with the stencil target attached, cleared to 0.
+    ///
+    /// Every pipeline used within the section must be built with a StencilMode,
+    /// and the stencil target must share the sample count of the color targets.
+    /// The stencil target replaces the depth target until the section ends.
+    pub fn begin_stencil_section(&mut self, stencil_target: &RenderTarget) {
+        self.sections.push(RenderSection {
+            stencil_view: Some(stencil_target.texture().view.clone()),
+            queues: Vec::new(),
+        });
+    }
+
+    pub fn end_stencil_section(&mut self) {
+        self.sections.push(RenderSection::default());
+    }
+
     pub fn consume_queue<RenderQueue: RenderQueueTrait>(&mut self, queue: RenderQueue) {
         let operations = queue.into_operation_vec();
 
         if !operations.is_empty() {
-            self.queues.push(operations);
+            let section = self.sections.last_mut().unwrap();
+
+            section.queues.push(ClippedQueue {
+                operations,
+                clip: self.clip_stack.last().copied(),
+            });
         }
     }
 
     pub fn flush(self) {
-        let color_attachments: Vec<_> = self
-            .color_targets
-            .iter()
-            .map(|target| Some(target.color_attachment()))
-            .collect();
-
-        let depth_stencil_attachment = self.depth_target.map(|target| target.depth_attachment());
-
-        let descriptor = wgpu::RenderPassDescriptor {
-            label: self.label,
-            color_attachments: &color_attachments,
-            depth_stencil_attachment,
-            occlusion_query_set: None,
-            timestamp_writes: None,
-            multiview_mask: None,
-        };
-
+        let sample_count = self.sample_count();
         let encoder = self.encoder;
-        let mut render_pass = encoder.begin_render_pass(&descriptor);
 
-        for queue in &self.queues {
-            // println!("RenderOperations: {}", queue.len());
+        for (i, section) in self.sections.iter().enumerate() {
+            // the first section always renders to apply clear colors
+            let is_first = i == 0;
 
-            let mut scissor_set = false;
+            if !is_first && section.queues.is_empty() {
+                continue;
+            }
 
-            for operation in queue {
-                match operation {
-                    RenderOperation::SetPipeline(render_pipeline) => {
-                        render_pass.set_pipeline(render_pipeline);
-                        // println!("set pipeline");
-                    }
-                    RenderOperation::SetScissor(mut rect) => {
-                        rect *= self.texture_size.as_vec2();
-
-                        // rounding to avoid precision issues
-                        let x = (rect.x.round() as u32).min(self.texture_size.x - 1);
-                        let y = (rect.y.round() as u32).min(self.texture_size.y - 1);
-                        // clamping to avoid wgpu complaints
-                        // avoiding .clamp to avoid panics from std
-                        let w = (rect.width.round() as u32)
-                            .min(self.texture_size.x.saturating_sub(x))
-                            .max(1);
-                        let h = (rect.height.round() as u32)
-                            .min(self.texture_size.y.saturating_sub(y))
-                            .max(1);
-
-                        render_pass.set_scissor_rect(x, y, w, h);
-
-                        scissor_set = true;
-                        // println!("set scissor");
-                    }
-                    RenderOperation::SetUniforms(bind_group) => {
-                        render_pass.set_bind_group(0, bind_group, &[]);
-                        // println!("set uniforms");
+            let color_attachments: Vec<_> = self
+                .color_targets
+                .iter()
+                .map(|target| {
+                    let mut attachment = target.color_attachment();
+
+                    if !is_first {
+                        attachment.ops.load = wgpu::LoadOp::Load;
                     }
-                    RenderOperation::SetMesh((vertex_buffer, index_buffer)) => {
-                        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
-                        render_pass
-                            .set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-                        // println!("change mesh");
+
+                    Some(attachment)
+                })
+                .collect();
+
+            let depth_stencil_attachment = match &section.stencil_view {
+                Some(view) => Some(wgpu::RenderPassDepthStencilAttachment {
+                    view,
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(1.0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                    stencil_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(0),
+                        store: wgpu::StoreOp::Discard,
+                    }),
+                }),
+                None => self.depth_target.map(|target| {
+                    let mut attachment = target.depth_attachment();
+
+                    if let (false, Some(ops)) = (is_first, &mut attachment.depth_ops) {
+                        ops.load = wgpu::LoadOp::Load;
                     }
-                    RenderOperation::SetInstanceResources(bind_group) => {
-                        render_pass.set_bind_group(1, bind_group, &[]);
-                        // println!("set instance resources");
+
+                    attachment
+                }),
+            };
+
+            let descriptor = wgpu::RenderPassDescriptor {
+                label: self.label,
+                color_attachments: &color_attachments,
+                depth_stencil_attachment,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+                multiview_mask: None,
+            };
+
+            let mut render_pass = encoder.begin_render_pass(&descriptor);
+
+            for queue in &section.queues {
+                if section.stencil_view.is_some() {
+                    render_pass.set_stencil_reference(StencilMode::DEFAULT_REFERENCE);
+                }
+
+                Self::flush_queue(&mut render_pass, queue, self.texture_size, sample_count);
+            }
+        }
+    }
+
+    fn flush_queue(
+        render_pass: &mut wgpu::RenderPass,
+        queue: &ClippedQueue,
+        texture_size: UVec2,
+        sample_count: u32,
+    ) {
+        // println!("RenderOperations: {}", queue.operations.len());
+
+        let mut scissor_set = false;
+        let mut clipped_out = false;
+        let mut instance_buffer = None;
+
+        if let Some(clip) = queue.clip {
+            clipped_out = !Self::set_scissor(render_pass, clip, texture_size);
+            scissor_set = true;
+        }
+
+        for operation in &queue.operations {
+            match operation {
+                RenderOperation::SetPipeline(variants) => {
+                    render_pass.set_pipeline(&variants.get(sample_count));
+                    // println!("set pipeline");
+                }
+                RenderOperation::SetScissor(rect) => {
+                    let rect = match queue.clip {
+                        Some(clip) => rect.scissor(clip),
+                        None => *rect,
+                    };
+
+                    clipped_out = !Self::set_scissor(render_pass, rect, texture_size);
+                    scissor_set = true;
+                    // println!("set scissor");
+                }
+                RenderOperation::SetStencilReference(reference) => {
+                    render_pass.set_stencil_reference(*reference);
+                }
+                RenderOperation::SetUniforms(bind_group) => {
+                    render_pass.set_bind_group(0, bind_group, &[]);
+                    // println!("set uniforms");
+                }
+                RenderOperation::SetMesh((vertex_buffer, index_buffer)) => {
+                    render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+                    render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    // println!("change mesh");
+                }
+                RenderOperation::SetInstanceResources(bind_group) => {
+                    render_pass.set_bind_group(1, bind_group, &[]);
+                    // println!("set instance resources");
+                }
+                RenderOperation::SetInstanceBuffer { buffer, offset } => {
+                    instance_buffer = Some((buffer, *offset));
+                }
+                RenderOperation::Draw {
+                    instance_range,
+                    index_count,
+                    instance_count,
+                } => {
+                    if clipped_out {
+                        continue;
                     }
-                    RenderOperation::Draw {
-                        instance_buffer,
-                        index_count,
-                        instance_count,
-                    } => {
-                        if let Some(instance_buffer) = instance_buffer {
-                            render_pass.set_vertex_buffer(1, instance_buffer.slice(..));
-                        }
-
-                        render_pass.draw_indexed(0..*index_count, 0, 0..*instance_count);
-                        // println!("draw");
+
+                    if let (Some((buffer, offset)), Some(range)) = (instance_buffer, instance_range)
+                    {
+                        let range = offset + range.start..offset + range.end;
+                        render_pass.set_vertex_buffer(1, buffer.slice(range));
                     }
+
+                    render_pass.draw_indexed(0..*index_count, 0, 0..*instance_count);
+                    // println!("draw");
                 }
             }
+        }
 
-            if scissor_set {
-                render_pass.set_scissor_rect(0, 0, self.texture_size.x, self.texture_size.y);
-            }
+        if scissor_set {
+            render_pass.set_scissor_rect(0, 0, texture_size.x, texture_size.y);
         }
     }
+
+    /// Returns false if the rect has no area, draws should be skipped
+    fn set_scissor(
+        render_pass: &mut wgpu::RenderPass,
+        mut rect: Rect,
+        texture_size: UVec2,
+    ) -> bool {
+        rect *= texture_size.as_vec2();
+
+        // rounding to avoid precision issues
+        let x = (rect.x.round() as u32).min(texture_size.x - 1);
+        let y = (rect.y.round() as u32).min(texture_size.y - 1);
+        // clamping to avoid wgpu complaints
+        // avoiding .clamp to avoid panics from std
+        let w = (rect.width.round() as u32).min(texture_size.x.saturating_sub(x));
+        let h = (rect.height.round() as u32).min(texture_size.y.saturating_sub(y));
+
+        if w == 0 || h == 0 {
+            return false;
+        }
+
+        render_pass.set_scissor_rect(x, y, w, h);
+
+        true
+    }
 }
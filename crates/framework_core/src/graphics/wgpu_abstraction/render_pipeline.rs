@@ -1,8 +1,10 @@
+use super::render_pipeline_variants::RenderPipelineVariants;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct RenderPipeline<Vertex: super::Vertex, InstanceData: super::InstanceData> {
-    pub(super) render_pipeline: wgpu::RenderPipeline,
+    pub(super) variants: Arc<RenderPipelineVariants>,
     pub(super) uniform_bind_group_layout: wgpu::BindGroupLayout,
     pub(super) instance_bind_group_layout: wgpu::BindGroupLayout,
     vertex_phantom: PhantomData<Vertex>,
@@ -13,12 +15,12 @@ impl<Vertex: super::Vertex, InstanceData: super::InstanceData>
     RenderPipeline<Vertex, InstanceData>
 {
     pub(super) fn new(
-        render_pipeline: wgpu::RenderPipeline,
+        variants: Arc<RenderPipelineVariants>,
         uniform_bind_group_layout: wgpu::BindGroupLayout,
         instance_bind_group_layout: wgpu::BindGroupLayout,
     ) -> Self {
         Self {
-            render_pipeline,
+            variants,
             uniform_bind_group_layout,
             instance_bind_group_layout,
             vertex_phantom: PhantomData,
@@ -1,5 +1,10 @@
+use super::render_pipeline_variants::{RenderPipelineVariants, VertexBufferDescription};
+use super::shader_reflection::vertex_format_kind;
+use crate::async_task::{block_on, SyncResultAsyncError};
 use crate::graphics::*;
+use cfg_macros::*;
 use math::*;
+use std::sync::Arc;
 
 pub struct RenderPipelineBuilder<'a> {
     graphics: &'a GraphicsContext,
@@ -7,6 +12,8 @@ pub struct RenderPipelineBuilder<'a> {
     instance_bind_group_layout_entries: Vec<wgpu::BindGroupLayoutEntry>,
     vertex_shader: Option<(&'a wgpu::ShaderModule, String)>,
     fragment_shader: Option<(&'a wgpu::ShaderModule, String)>,
+    vertex_reflection: Option<&'a ShaderReflection>,
+    fragment_reflection: Option<&'a ShaderReflection>,
     color_states: Vec<Option<wgpu::ColorTargetState>>,
     primitive: wgpu::PrimitiveState,
     depth_stencil: Option<wgpu::DepthStencilState>,
@@ -23,6 +30,8 @@ impl<'a> RenderPipelineBuilder<'a> {
             instance_bind_group_layout_entries: Vec::new(),
             vertex_shader: None,
             fragment_shader: None,
+            vertex_reflection: None,
+            fragment_reflection: None,
             color_states: vec![Some(wgpu::ColorTargetState {
                 format: graphics.default_texture_format(),
                 blend: Some(wgpu::BlendState::ALPHA_BLENDING),
@@ -69,11 +78,38 @@ impl<'a> RenderPipelineBuilder<'a> {
         self
     }
 
+    /// Derives bind groups left empty from the vertex shader and validates its `@location` inputs
+    pub fn with_vertex_reflection(mut self, reflection: &'a ShaderReflection) -> Self {
+        self.vertex_reflection = Some(reflection);
+        self
+    }
+
+    /// Derives bind groups left empty from the fragment shader
+    pub fn with_fragment_reflection(mut self, reflection: &'a ShaderReflection) -> Self {
+        self.fragment_reflection = Some(reflection);
+        self
+    }
+
+    /// For modules containing both the vertex and fragment entry points
+    pub fn with_reflection(self, reflection: &'a ShaderReflection) -> Self {
+        self.with_vertex_reflection(reflection)
+            .with_fragment_reflection(reflection)
+    }
+
     pub fn with_color_target(mut self, state: wgpu::ColorTargetState) -> Self {
         self.color_states[0] = Some(state);
         self
     }
 
+    /// Sets the blend state for the first color target
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        if let Some(state) = &mut self.color_states[0] {
+            state.blend = Some(blend_mode.blend_state());
+        }
+
+        self
+    }
+
     pub fn with_additional_color_target(mut self, state: wgpu::ColorTargetState) -> Self {
         self.color_states.push(Some(state));
         self
@@ -84,6 +120,16 @@ impl<'a> RenderPipelineBuilder<'a> {
         self
     }
 
+    /// Sets the stencil state and the write mask for the first color target
+    pub fn with_stencil_mode(mut self, stencil_mode: StencilMode) -> Self {
+        if let Some(state) = &mut self.color_states[0] {
+            state.write_mask = stencil_mode.color_writes();
+        }
+
+        self.depth_stencil = Some(stencil_mode.depth_stencil_state());
+        self
+    }
+
     pub fn with_primitive(mut self, primitive: wgpu::PrimitiveState) -> Self {
         self.primitive = primitive;
         self
@@ -103,16 +149,58 @@ impl<'a> RenderPipelineBuilder<'a> {
     {
         let device = self.graphics.device();
 
+        let (vertex_shader, vertex_entry) = self
+            .vertex_shader
+            .clone()
+            .ok_or_else(|| String::from("Missing vertex shader!"))?;
+
+        let (fragment_shader, fragment_entry) = self
+            .fragment_shader
+            .clone()
+            .ok_or_else(|| String::from("Missing fragment shader!"))?;
+
+        let vertex_layout = Vertex::vertex_layout();
+        let mut instance_layout = InstanceData::instance_layout();
+        instance_layout.offset_attribute_locations(vertex_layout.attribute_len());
+
+        let mut buffer_descriptions = vec![vertex_layout.build::<Vertex>()?];
+
+        if std::mem::size_of::<InstanceData>() > 0 {
+            buffer_descriptions.push(instance_layout.build::<InstanceData>()?);
+        }
+
+        if let Some(reflection) = self.vertex_reflection {
+            Self::validate_vertex_inputs::<Vertex, InstanceData>(
+                reflection,
+                &vertex_entry,
+                &buffer_descriptions,
+            )?;
+        }
+
+        let uniform_bind_group_layout_entries = self.resolve_bind_group_layout_entries(
+            0,
+            &self.uniform_bind_group_layout_entries,
+            &vertex_entry,
+            &fragment_entry,
+        )?;
+
+        let instance_bind_group_layout_entries = self.resolve_bind_group_layout_entries(
+            1,
+            &self.instance_bind_group_layout_entries,
+            &vertex_entry,
+            &fragment_entry,
+        )?;
+
         let uniform_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: None,
-                entries: &self.uniform_bind_group_layout_entries,
+                entries: &uniform_bind_group_layout_entries,
             });
 
         let instance_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: None,
-                entries: &self.instance_bind_group_layout_entries,
+                entries: &instance_bind_group_layout_entries,
             });
 
         let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
@@ -124,54 +212,183 @@ impl<'a> RenderPipelineBuilder<'a> {
             immediate_size: 0,
         });
 
-        let (vertex_shader, vertex_entry) = self
-            .vertex_shader
-            .ok_or_else(|| String::from("Missing vertex shader!"))?;
-
-        let (fragment_shader, fragment_entry) = self
-            .fragment_shader
-            .ok_or_else(|| String::from("Missing fragment shader!"))?;
-
-        let vertex_layout = Vertex::vertex_layout();
-        let mut instance_layout = InstanceData::instance_layout();
-        instance_layout.offset_attribute_locations(vertex_layout.attribute_len());
-
-        let buffer_layouts = [
-            vertex_layout.build::<Vertex>()?,
-            instance_layout.build::<InstanceData>()?,
-        ];
-        let buffer_layouts_slice = if std::mem::size_of::<InstanceData>() > 0 {
-            &buffer_layouts
-        } else {
-            &buffer_layouts[..1]
-        };
-
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: None,
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                buffers: buffer_layouts_slice,
-                module: vertex_shader,
-                entry_point: Some(vertex_entry.as_str()),
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                targets: self.color_states.as_slice(),
-                module: fragment_shader,
-                entry_point: Some(fragment_entry.as_str()),
-                compilation_options: Default::default(),
-            }),
+        let variants = RenderPipelineVariants {
+            device: device.clone(),
+            layout: pipeline_layout,
+            vertex_shader: vertex_shader.clone(),
+            vertex_entry,
+            fragment_shader: fragment_shader.clone(),
+            fragment_entry,
+            buffer_descriptions,
+            color_states: self.color_states,
             primitive: self.primitive,
             depth_stencil: self.depth_stencil,
             multisample: self.multisample,
-            multiview_mask: None,
-            cache: None,
-        });
+            pipelines: Default::default(),
+        };
+
+        // create the default variant early to surface errors at build time
+        let variants = if cfg_web!() {
+            // error scopes resolve asynchronously on web, errors are left to the uncaptured error handler
+            variants.get(self.multisample.count);
+            variants
+        } else {
+            let scope = device.push_error_scope(wgpu::ErrorFilter::Validation);
+            variants.get(self.multisample.count);
+            let validation = SyncResultAsyncError::new(variants, scope.pop());
+
+            block_on(validation.result()).map_err(|err| err.to_string())?
+        };
 
         Ok(super::RenderPipeline::new(
-            render_pipeline,
+            Arc::new(variants),
             uniform_bind_group_layout,
             instance_bind_group_layout,
         ))
     }
+
+    /// Reflected entries when the group wasn't listed manually, otherwise validates the listed entries
+    fn resolve_bind_group_layout_entries(
+        &self,
+        group: u32,
+        listed_entries: &[wgpu::BindGroupLayoutEntry],
+        vertex_entry: &str,
+        fragment_entry: &str,
+    ) -> Result<Vec<wgpu::BindGroupLayoutEntry>, String> {
+        let group_name = if group == 0 { "uniform" } else { "instance" };
+
+        let stages = [
+            (
+                self.vertex_reflection,
+                wgpu::naga::ShaderStage::Vertex,
+                vertex_entry,
+            ),
+            (
+                self.fragment_reflection,
+                wgpu::naga::ShaderStage::Fragment,
+                fragment_entry,
+            ),
+        ];
+
+        let mut reflected_entries: Vec<wgpu::BindGroupLayoutEntry> = Vec::new();
+        let mut reflected = false;
+
+        for (reflection, stage, entry_point) in stages {
+            let Some(reflection) = reflection else {
+                continue;
+            };
+
+            reflected = true;
+
+            for entry in reflection.bind_group_entries(group, stage, entry_point)? {
+                let Some(existing) = reflected_entries
+                    .iter_mut()
+                    .find(|existing| existing.binding == entry.binding)
+                else {
+                    reflected_entries.push(entry);
+                    continue;
+                };
+
+                if existing.ty != entry.ty {
+                    return Err(format!(
+                        "@group({group}) @binding({}) has a different type in the vertex and fragment shaders",
+                        entry.binding
+                    ));
+                }
+
+                existing.visibility |= entry.visibility;
+            }
+        }
+
+        if !reflected {
+            return Ok(listed_entries.to_vec());
+        }
+
+        reflected_entries.sort_by_key(|entry| entry.binding);
+
+        if listed_entries.is_empty() {
+            // RenderQueue binds resources by index
+            for (i, entry) in reflected_entries.iter().enumerate() {
+                if entry.binding != i as u32 {
+                    return Err(format!(
+                        "Expected @group({group}) @binding({i}) in the shader, {group_name} bindings must be contiguous from 0"
+                    ));
+                }
+            }
+
+            return Ok(reflected_entries);
+        }
+
+        for entry in &reflected_entries {
+            let Some(listed_entry) = listed_entries
+                .iter()
+                .find(|listed_entry| listed_entry.binding == entry.binding)
+            else {
+                return Err(format!(
+                    "@group({group}) @binding({}) is used by the shader, but missing from the {group_name} bind group",
+                    entry.binding
+                ));
+            };
+
+            if std::mem::discriminant(&listed_entry.ty) != std::mem::discriminant(&entry.ty) {
+                return Err(format!(
+                    "@group({group}) @binding({}) is {:?} in the shader, but listed as {:?} in the {group_name} bind group",
+                    entry.binding, entry.ty, listed_entry.ty
+                ));
+            }
+
+            if !listed_entry.visibility.contains(entry.visibility) {
+                return Err(format!(
+                    "@group({group}) @binding({}) is used by {:?}, but listed as visible to {:?} in the {group_name} bind group",
+                    entry.binding, entry.visibility, listed_entry.visibility
+                ));
+            }
+        }
+
+        Ok(listed_entries.to_vec())
+    }
+
+    fn validate_vertex_inputs<Vertex, InstanceData>(
+        reflection: &ShaderReflection,
+        vertex_entry: &str,
+        buffer_descriptions: &[VertexBufferDescription],
+    ) -> Result<(), String> {
+        let type_names = [
+            std::any::type_name::<Vertex>(),
+            std::any::type_name::<InstanceData>(),
+        ];
+
+        for input in reflection.vertex_inputs(vertex_entry)? {
+            let attribute =
+                buffer_descriptions
+                    .iter()
+                    .zip(type_names)
+                    .find_map(|(description, type_name)| {
+                        let attribute = description
+                            .attributes
+                            .iter()
+                            .find(|attribute| attribute.shader_location == input.location)?;
+
+                        Some((attribute, type_name))
+                    });
+
+            let Some((attribute, type_name)) = attribute else {
+                return Err(format!(
+                    "Vertex input `{}` @location({}) is not provided by {} or {}",
+                    input.name, input.location, type_names[0], type_names[1]
+                ));
+            };
+
+            let kind = vertex_format_kind(attribute.format);
+
+            if kind != input.kind {
+                return Err(format!(
+                    "Vertex input `{}` @location({}) expects {:?}, but {type_name} provides {:?}",
+                    input.name, input.location, input.kind, attribute.format
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
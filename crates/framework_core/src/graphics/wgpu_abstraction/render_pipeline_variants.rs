@@ -0,0 +1,87 @@
+use std::sync::Mutex;
+
+/// An owned wgpu::VertexBufferLayout
+#[derive(Debug)]
+pub(super) struct VertexBufferDescription {
+    pub(super) array_stride: wgpu::BufferAddress,
+    pub(super) step_mode: wgpu::VertexStepMode,
+    pub(super) attributes: Vec<wgpu::VertexAttribute>,
+}
+
+/// Everything needed to rebuild a pipeline for render targets with a different sample count.
+///
+/// Variants are created on first use and kept for the lifetime of the pipeline.
+#[derive(Debug)]
+pub struct RenderPipelineVariants {
+    pub(super) device: wgpu::Device,
+    pub(super) layout: wgpu::PipelineLayout,
+    pub(super) vertex_shader: wgpu::ShaderModule,
+    pub(super) vertex_entry: String,
+    pub(super) fragment_shader: wgpu::ShaderModule,
+    pub(super) fragment_entry: String,
+    pub(super) buffer_descriptions: Vec<VertexBufferDescription>,
+    pub(super) color_states: Vec<Option<wgpu::ColorTargetState>>,
+    pub(super) primitive: wgpu::PrimitiveState,
+    pub(super) depth_stencil: Option<wgpu::DepthStencilState>,
+    pub(super) multisample: wgpu::MultisampleState,
+    pub(super) pipelines: Mutex<Vec<(u32, wgpu::RenderPipeline)>>,
+}
+
+impl RenderPipelineVariants {
+    /// Creates the variant if it doesn't exist yet
+    pub(crate) fn get(&self, sample_count: u32) -> wgpu::RenderPipeline {
+        let mut pipelines = self.pipelines.lock().unwrap();
+
+        if let Some((_, pipeline)) = pipelines.iter().find(|(count, _)| *count == sample_count) {
+            return pipeline.clone();
+        }
+
+        let pipeline = self.create(sample_count);
+        pipelines.push((sample_count, pipeline.clone()));
+
+        pipeline
+    }
+
+    fn create(&self, sample_count: u32) -> wgpu::RenderPipeline {
+        let buffer_layouts: Vec<_> = self
+            .buffer_descriptions
+            .iter()
+            .map(|description| wgpu::VertexBufferLayout {
+                array_stride: description.array_stride,
+                step_mode: description.step_mode,
+                attributes: &description.attributes,
+            })
+            .collect();
+
+        let multisample = wgpu::MultisampleState {
+            count: sample_count,
+            // alpha to coverage is invalid without multisampling
+            alpha_to_coverage_enabled: self.multisample.alpha_to_coverage_enabled
+                && sample_count > 1,
+            ..self.multisample
+        };
+
+        self.device
+            .create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: None,
+                layout: Some(&self.layout),
+                vertex: wgpu::VertexState {
+                    buffers: &buffer_layouts,
+                    module: &self.vertex_shader,
+                    entry_point: Some(self.vertex_entry.as_str()),
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    targets: self.color_states.as_slice(),
+                    module: &self.fragment_shader,
+                    entry_point: Some(self.fragment_entry.as_str()),
+                    compilation_options: Default::default(),
+                }),
+                primitive: self.primitive,
+                depth_stencil: self.depth_stencil.clone(),
+                multisample,
+                multiview_mask: None,
+                cache: None,
+            })
+    }
+}
@@ -1,16 +1,24 @@
 use crate::graphics::*;
 use math::*;
+use std::ops::Range;
 use std::sync::Arc;
 
 #[derive(Debug)]
 pub enum RenderOperation {
-    SetPipeline(wgpu::RenderPipeline),
+    /// Resolved to a variant matching the sample count of the RenderPass
+    SetPipeline(Arc<RenderPipelineVariants>),
     SetScissor(Rect),
+    SetStencilReference(u32),
     SetUniforms(wgpu::BindGroup),
     SetMesh((wgpu::Buffer, wgpu::Buffer)),
     SetInstanceResources(wgpu::BindGroup),
+    /// Instance ranges in following draw calls are relative to the offset
+    SetInstanceBuffer {
+        buffer: wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+    },
     Draw {
-        instance_buffer: Option<wgpu::Buffer>,
+        instance_range: Option<Range<wgpu::BufferAddress>>,
         index_count: u32,
         instance_count: u32,
     },
@@ -24,6 +32,7 @@ pub struct RenderQueue<'a, Vertex: super::Vertex, InstanceData: super::InstanceD
     latest_mesh: Option<Arc<super::Mesh<Vertex>>>,
     latest_data: Vec<InstanceData>,
     latest_resources: Option<Vec<Arc<dyn AsBinding>>>,
+    instance_bytes: Vec<u8>,
     operations: Vec<RenderOperation>,
 }
 
@@ -40,7 +49,6 @@ impl<'a, Vertex: super::Vertex, InstanceData: super::InstanceData>
         I: IntoIterator<Item = BindingResource<'b>>,
     {
         let render_pipeline = render_pipeline.as_ref();
-        let wgpu_render_pipeline = render_pipeline.render_pipeline.clone();
 
         let mut render_queue = Self {
             graphics: graphics.graphics(),
@@ -49,7 +57,10 @@ impl<'a, Vertex: super::Vertex, InstanceData: super::InstanceData>
             latest_mesh: None,
             latest_data: Vec::new(),
             latest_resources: None,
-            operations: vec![RenderOperation::SetPipeline(wgpu_render_pipeline)],
+            instance_bytes: Vec::new(),
+            operations: vec![RenderOperation::SetPipeline(
+                render_pipeline.variants.clone(),
+            )],
         };
 
         render_queue.set_uniforms(uniform_resources);
@@ -80,12 +91,11 @@ impl<'a, Vertex: super::Vertex, InstanceData: super::InstanceData>
     }
 
     fn create_uniform_bind_group(&mut self, uniform_entries: Vec<wgpu::BindGroupEntry>) {
-        let device = self.graphics.device();
-        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("uniforms"),
-            layout: self.uniform_bind_group_layout,
-            entries: &uniform_entries,
-        });
+        let uniform_bind_group = self.graphics.cached_bind_group(
+            Some("uniforms"),
+            self.uniform_bind_group_layout,
+            &uniform_entries,
+        );
 
         // draw anything that relies on previous uniforms
         self.try_create_draw_call();
@@ -100,6 +110,15 @@ impl<'a, Vertex: super::Vertex, InstanceData: super::InstanceData>
         self.operations.push(RenderOperation::SetScissor(rect));
     }
 
+    /// Value written and compared against by stencil pipelines. Applies to subsequent draw calls.
+    ///
+    /// Starts as `StencilMode::DEFAULT_REFERENCE`.
+    pub fn set_stencil_reference(&mut self, reference: u32) {
+        self.try_create_draw_call();
+        self.operations
+            .push(RenderOperation::SetStencilReference(reference));
+    }
+
     pub fn draw_model<Model: super::Model<Vertex, InstanceData>>(&mut self, model: &Model) {
         let mesh = model.mesh();
         let data = model.instance_data();
@@ -192,12 +211,11 @@ impl<'a, Vertex: super::Vertex, InstanceData: super::InstanceData>
             })
             .collect();
 
-        let device = self.graphics.device();
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("instance_resources"),
-            layout: self.instance_bind_group_layout,
-            entries: &resource_entries,
-        });
+        let bind_group = self.graphics.cached_bind_group(
+            Some("instance_resources"),
+            self.instance_bind_group_layout,
+            &resource_entries,
+        );
 
         self.operations
             .push(RenderOperation::SetInstanceResources(bind_group));
@@ -211,18 +229,14 @@ impl<'a, Vertex: super::Vertex, InstanceData: super::InstanceData>
             return;
         }
 
-        // create buffer for instance data
-        use wgpu::util::DeviceExt;
-
-        let device = self.graphics.device();
-        let instance_buffer = if std::mem::size_of::<InstanceData>() > 0 {
-            Some(
-                device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                    label: Some("instance_buffer"),
-                    contents: bytemuck::cast_slice(&self.latest_data[..]),
-                    usage: wgpu::BufferUsages::VERTEX,
-                }),
-            )
+        // instance data is uploaded in one write when the queue is consumed
+        let instance_range = if std::mem::size_of::<InstanceData>() > 0 {
+            let start = super::instance_buffer_ring::align(self.instance_bytes.len() as u64);
+            self.instance_bytes.resize(start as usize, 0);
+            self.instance_bytes
+                .extend_from_slice(bytemuck::cast_slice(&self.latest_data[..]));
+
+            Some(start..self.instance_bytes.len() as u64)
         } else {
             None
         };
@@ -232,12 +246,12 @@ impl<'a, Vertex: super::Vertex, InstanceData: super::InstanceData>
         let instance_count = self.latest_data.len() as u32;
 
         self.operations.push(RenderOperation::Draw {
-            instance_buffer,
+            instance_range,
             index_count,
             instance_count,
         });
 
-        // clear the data now that we moved it into a buffer and used it to calculate instance count
+        // clear the data now that we moved it into the byte buffer and used it to calculate instance count
         self.latest_data.clear();
     }
 }
@@ -248,6 +262,13 @@ impl<Vertex: super::Vertex, InstanceData: super::InstanceData> RenderQueueTrait
     fn into_operation_vec(mut self) -> Vec<RenderOperation> {
         self.try_create_draw_call();
 
+        if !self.instance_bytes.is_empty() {
+            let (buffer, offset) = self.graphics.write_instance_data(&self.instance_bytes);
+
+            self.operations
+                .insert(0, RenderOperation::SetInstanceBuffer { buffer, offset });
+        }
+
         self.operations
     }
 }
@@ -5,8 +5,11 @@ use std::sync::Arc;
 pub struct RenderTarget {
     clear_color: Option<Color>,
     texture: Arc<Texture>,
+    /// Rendered to in place of `texture` and resolved into it when multisampled
+    multisampled_texture: Option<Arc<Texture>>,
     usage: wgpu::TextureUsages,
     format: wgpu::TextureFormat,
+    sample_count: u32,
 }
 
 impl RenderTarget {
@@ -15,6 +18,9 @@ impl RenderTarget {
     pub const DEFAULT_USAGE: wgpu::TextureUsages =
         wgpu::TextureUsages::from_bits_retain((1 << 0) | (1 << 2) | (1 << 4));
 
+    /// Format used by stencil targets and pipelines built with a StencilMode
+    pub const STENCIL_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth24PlusStencil8;
+
     pub fn new(graphics: &impl HasGraphicsContext, size: UVec2) -> Self {
         Self::new_with_usage(graphics, size, Self::DEFAULT_USAGE)
     }
@@ -24,15 +30,22 @@ impl RenderTarget {
         size: UVec2,
         usage: wgpu::TextureUsages,
     ) -> Self {
-        let graphics = graphics.graphics();
-        let format = graphics.default_texture_format();
+        let format = graphics.graphics().default_texture_format();
 
-        Self {
-            texture: RenderTarget::create_texture(graphics, size, usage, format),
-            clear_color: Some(Color::TRANSPARENT),
-            usage,
-            format,
-        }
+        Self::create(graphics, size, usage, format, 1)
+    }
+
+    /// Draws are rendered with `sample_count` samples per pixel and resolved into `texture()` when a RenderPass flushes.
+    ///
+    /// See `GraphicsContext::supported_sample_count()`
+    pub fn new_multisampled(
+        graphics: &impl HasGraphicsContext,
+        size: UVec2,
+        sample_count: u32,
+    ) -> Self {
+        let format = graphics.graphics().default_texture_format();
+
+        Self::create(graphics, size, Self::DEFAULT_USAGE, format, sample_count)
     }
 
     pub fn new_with_format(
@@ -40,22 +53,55 @@ impl RenderTarget {
         size: UVec2,
         format: wgpu::TextureFormat,
     ) -> Self {
-        let usage = Self::DEFAULT_USAGE;
+        Self::create(graphics, size, Self::DEFAULT_USAGE, format, 1)
+    }
+
+    /// Creates a target for `RenderPass::begin_stencil_section`, should match the size of the pass' color target
+    pub fn new_stencil(graphics: &impl HasGraphicsContext, size: UVec2) -> Self {
+        Self::new_multisampled_stencil(graphics, size, 1)
+    }
+
+    /// A stencil target for passes with multisampled color targets, sample counts must match
+    pub fn new_multisampled_stencil(
+        graphics: &impl HasGraphicsContext,
+        size: UVec2,
+        sample_count: u32,
+    ) -> Self {
+        let usage = wgpu::TextureUsages::RENDER_ATTACHMENT;
+        let mut target = Self::create(graphics, size, usage, Self::STENCIL_FORMAT, sample_count);
+        target.clear_color = None;
+        target
+    }
+
+    fn create(
+        graphics: &impl HasGraphicsContext,
+        size: UVec2,
+        usage: wgpu::TextureUsages,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        let sample_count = sample_count.max(1);
+        let (texture, multisampled_texture) =
+            Self::create_textures(graphics, size, usage, format, sample_count);
 
         Self {
-            texture: RenderTarget::create_texture(graphics, size, usage, format),
+            texture,
+            multisampled_texture,
             clear_color: Some(Color::TRANSPARENT),
             usage,
             format,
+            sample_count,
         }
     }
 
     pub fn from_view(view: wgpu::TextureView) -> Self {
         Self {
             texture: Arc::new(Texture { view }),
+            multisampled_texture: None,
             clear_color: Some(Color::TRANSPARENT),
             usage: Self::DEFAULT_USAGE,
             format: Texture::DEFAULT_FORMAT,
+            sample_count: 1,
         }
     }
 
@@ -72,7 +118,12 @@ impl RenderTarget {
             return;
         }
 
-        self.texture = RenderTarget::create_texture(graphics, size, self.usage, self.format);
+        (self.texture, self.multisampled_texture) =
+            Self::create_textures(graphics, size, self.usage, self.format, self.sample_count);
+    }
+
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
     }
 
     pub fn clear_color(&self) -> Option<Color> {
@@ -85,10 +136,15 @@ impl RenderTarget {
     }
 
     pub(crate) fn color_attachment(&self) -> wgpu::RenderPassColorAttachment<'_> {
+        let (view, resolve_target) = match &self.multisampled_texture {
+            Some(multisampled_texture) => (&multisampled_texture.view, Some(&self.texture.view)),
+            None => (&self.texture.view, None),
+        };
+
         wgpu::RenderPassColorAttachment {
-            view: &self.texture.view,
+            view,
             depth_slice: None,
-            resolve_target: None,
+            resolve_target,
             ops: wgpu::Operations {
                 load: match self.clear_color {
                     Some(color) => wgpu::LoadOp::Clear(color.into()),
@@ -113,11 +169,37 @@ impl RenderTarget {
         }
     }
 
+    fn create_textures(
+        graphics: &impl HasGraphicsContext,
+        size: UVec2,
+        usage: wgpu::TextureUsages,
+        format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> (Arc<Texture>, Option<Arc<Texture>>) {
+        // depth and stencil data is never resolved
+        if sample_count == 1 || format.is_depth_stencil_format() {
+            let texture = Self::create_texture(graphics, size, usage, format, sample_count);
+            return (texture, None);
+        }
+
+        let texture = Self::create_texture(graphics, size, usage, format, 1);
+        let multisampled_texture = Self::create_texture(
+            graphics,
+            size,
+            wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            sample_count,
+        );
+
+        (texture, Some(multisampled_texture))
+    }
+
     fn create_texture(
         graphics: &impl HasGraphicsContext,
         size: UVec2,
         usage: wgpu::TextureUsages,
         format: wgpu::TextureFormat,
+        sample_count: u32,
     ) -> Arc<Texture> {
         let graphics = graphics.graphics();
         let device = graphics.device();
@@ -129,7 +211,7 @@ impl RenderTarget {
                 depth_or_array_layers: 1,
             },
             mip_level_count: 1,
-            sample_count: 1,
+            sample_count,
             dimension: wgpu::TextureDimension::D2,
             format,
             usage,
@@ -0,0 +1,374 @@
+use wgpu::naga;
+
+/// Bind groups and vertex inputs read from a shader module, see `RenderPipelineBuilder::with_reflection()`
+pub struct ShaderReflection {
+    module: naga::Module,
+    info: naga::valid::ModuleInfo,
+}
+
+/// A `@location` input of a vertex entry point
+pub(super) struct VertexInput {
+    pub(super) name: String,
+    pub(super) location: u32,
+    pub(super) kind: naga::ScalarKind,
+}
+
+impl ShaderReflection {
+    pub fn new(module: naga::Module) -> Result<Self, String> {
+        let mut validator = naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        );
+
+        let info = validator
+            .validate(&module)
+            .map_err(|err| err.emit_to_string(""))?;
+
+        Ok(Self { module, info })
+    }
+
+    /// Use `PreprocessedWgsl::validate()` with `new()` for sources using preprocessor directives
+    pub fn from_wgsl(source: &str) -> Result<Self, String> {
+        let module =
+            naga::front::wgsl::parse_str(source).map_err(|err| err.emit_to_string(source))?;
+
+        Self::new(module)
+    }
+
+    pub fn module(&self) -> &naga::Module {
+        &self.module
+    }
+
+    fn entry_point_index(&self, stage: naga::ShaderStage, name: &str) -> Result<usize, String> {
+        self.module
+            .entry_points
+            .iter()
+            .position(|entry_point| entry_point.stage == stage && entry_point.name == name)
+            .ok_or_else(|| format!("Missing {stage:?} entry point `{name}`"))
+    }
+
+    /// Entries for the group's bindings used by the entry point, in binding order
+    pub(super) fn bind_group_entries(
+        &self,
+        group: u32,
+        stage: naga::ShaderStage,
+        entry_point: &str,
+    ) -> Result<Vec<wgpu::BindGroupLayoutEntry>, String> {
+        let function_info = self
+            .info
+            .get_entry_point(self.entry_point_index(stage, entry_point)?);
+
+        let visibility = match stage {
+            naga::ShaderStage::Vertex => wgpu::ShaderStages::VERTEX,
+            naga::ShaderStage::Fragment => wgpu::ShaderStages::FRAGMENT,
+            _ => wgpu::ShaderStages::COMPUTE,
+        };
+
+        let mut entries = Vec::new();
+
+        for (handle, global) in self.module.global_variables.iter() {
+            let Some(binding) = &global.binding else {
+                continue;
+            };
+
+            if binding.group != group || function_info[handle].is_empty() {
+                continue;
+            }
+
+            let name = global.name.as_deref().unwrap_or("_");
+
+            entries.push(wgpu::BindGroupLayoutEntry {
+                binding: binding.binding,
+                visibility,
+                ty: self.binding_type(handle, global).map_err(|err| {
+                    format!(
+                        "@group({}) @binding({}) `{name}`: {err}",
+                        binding.group, binding.binding
+                    )
+                })?,
+                count: None,
+            });
+        }
+
+        entries.sort_by_key(|entry| entry.binding);
+
+        Ok(entries)
+    }
+
+    fn binding_type(
+        &self,
+        handle: naga::Handle<naga::GlobalVariable>,
+        global: &naga::GlobalVariable,
+    ) -> Result<wgpu::BindingType, String> {
+        let buffer_binding_type = match global.space {
+            naga::AddressSpace::Uniform => Some(wgpu::BufferBindingType::Uniform),
+            naga::AddressSpace::Storage { access } => Some(wgpu::BufferBindingType::Storage {
+                read_only: !access.contains(naga::StorageAccess::STORE),
+            }),
+            _ => None,
+        };
+
+        if let Some(ty) = buffer_binding_type {
+            return Ok(wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            });
+        }
+
+        match &self.module.types[global.ty].inner {
+            naga::TypeInner::Sampler { comparison } => {
+                Ok(wgpu::BindingType::Sampler(if *comparison {
+                    wgpu::SamplerBindingType::Comparison
+                } else {
+                    wgpu::SamplerBindingType::Filtering
+                }))
+            }
+            naga::TypeInner::Image {
+                dim,
+                arrayed,
+                class,
+            } => {
+                let view_dimension = match (dim, arrayed) {
+                    (naga::ImageDimension::D1, _) => wgpu::TextureViewDimension::D1,
+                    (naga::ImageDimension::D2, false) => wgpu::TextureViewDimension::D2,
+                    (naga::ImageDimension::D2, true) => wgpu::TextureViewDimension::D2Array,
+                    (naga::ImageDimension::D3, _) => wgpu::TextureViewDimension::D3,
+                    (naga::ImageDimension::Cube, false) => wgpu::TextureViewDimension::Cube,
+                    (naga::ImageDimension::Cube, true) => wgpu::TextureViewDimension::CubeArray,
+                };
+
+                let (sample_type, multisampled) = match class {
+                    naga::ImageClass::Sampled { kind, multi } => {
+                        let sample_type = match kind {
+                            naga::ScalarKind::Sint => wgpu::TextureSampleType::Sint,
+                            naga::ScalarKind::Uint => wgpu::TextureSampleType::Uint,
+                            // textureLoad() also accepts textures with unfilterable formats
+                            _ => wgpu::TextureSampleType::Float {
+                                filterable: self.is_sampled(handle),
+                            },
+                        };
+
+                        (sample_type, *multi)
+                    }
+                    naga::ImageClass::Depth { multi } => (wgpu::TextureSampleType::Depth, *multi),
+                    _ => {
+                        return Err(String::from(
+                            "Unsupported texture type, list the layout manually",
+                        ))
+                    }
+                };
+
+                Ok(wgpu::BindingType::Texture {
+                    sample_type,
+                    view_dimension,
+                    multisampled,
+                })
+            }
+            _ => Err(String::from(
+                "Unsupported binding type, list the layout manually",
+            )),
+        }
+    }
+
+    /// Whether the texture is read with a sampler, which requires a filterable layout
+    fn is_sampled(&self, handle: naga::Handle<naga::GlobalVariable>) -> bool {
+        let functions = self
+            .module
+            .functions
+            .iter()
+            .map(|(_, function)| function)
+            .chain(self.module.entry_points.iter().map(|entry| &entry.function));
+
+        for function in functions {
+            for (_, expression) in function.expressions.iter() {
+                let naga::Expression::ImageSample { image, .. } = expression else {
+                    continue;
+                };
+
+                match function.expressions[*image] {
+                    naga::Expression::GlobalVariable(global) if global != handle => {}
+                    // textures passed through function arguments are assumed to be sampled
+                    _ => return true,
+                }
+            }
+        }
+
+        false
+    }
+
+    pub(super) fn vertex_inputs(&self, entry_point: &str) -> Result<Vec<VertexInput>, String> {
+        let index = self.entry_point_index(naga::ShaderStage::Vertex, entry_point)?;
+        let function = &self.module.entry_points[index].function;
+
+        let mut inputs = Vec::new();
+
+        for argument in &function.arguments {
+            let name = argument.name.as_deref().unwrap_or("_");
+
+            match &self.module.types[argument.ty].inner {
+                naga::TypeInner::Struct { members, .. } => {
+                    for member in members {
+                        let name = member.name.as_deref().unwrap_or("_");
+                        self.push_vertex_input(&mut inputs, name, member.ty, &member.binding);
+                    }
+                }
+                _ => self.push_vertex_input(&mut inputs, name, argument.ty, &argument.binding),
+            }
+        }
+
+        Ok(inputs)
+    }
+
+    fn push_vertex_input(
+        &self,
+        inputs: &mut Vec<VertexInput>,
+        name: &str,
+        ty: naga::Handle<naga::Type>,
+        binding: &Option<naga::Binding>,
+    ) {
+        // builtins such as vertex_index aren't provided by buffers
+        let Some(naga::Binding::Location { location, .. }) = binding else {
+            return;
+        };
+
+        let Some(scalar) = self.module.types[ty].inner.scalar() else {
+            return;
+        };
+
+        inputs.push(VertexInput {
+            name: name.to_string(),
+            location: *location,
+            kind: scalar.kind,
+        });
+    }
+}
+
+/// The scalar kind a shader must use to read the format
+pub(super) fn vertex_format_kind(format: wgpu::VertexFormat) -> naga::ScalarKind {
+    use wgpu::VertexFormat;
+
+    match format {
+        VertexFormat::Uint8
+        | VertexFormat::Uint8x2
+        | VertexFormat::Uint8x4
+        | VertexFormat::Uint16
+        | VertexFormat::Uint16x2
+        | VertexFormat::Uint16x4
+        | VertexFormat::Uint32
+        | VertexFormat::Uint32x2
+        | VertexFormat::Uint32x3
+        | VertexFormat::Uint32x4 => naga::ScalarKind::Uint,
+        VertexFormat::Sint8
+        | VertexFormat::Sint8x2
+        | VertexFormat::Sint8x4
+        | VertexFormat::Sint16
+        | VertexFormat::Sint16x2
+        | VertexFormat::Sint16x4
+        | VertexFormat::Sint32
+        | VertexFormat::Sint32x2
+        | VertexFormat::Sint32x3
+        | VertexFormat::Sint32x4 => naga::ScalarKind::Sint,
+        // normalized formats are read as floats
+        _ => naga::ScalarKind::Float,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SOURCE: &str = r#"
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @builtin(vertex_index) index: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> transform: mat4x4<f32>;
+@group(1) @binding(1)
+var smplr: sampler;
+@group(1) @binding(0)
+var txture: texture_2d<f32>;
+@group(1) @binding(2)
+var<storage, read> unused: array<f32>;
+@group(1) @binding(3)
+var loaded: texture_2d<f32>;
+
+@vertex
+fn vs_main(v_in: VertexInput, @location(1) layer: u32) -> @builtin(position) vec4<f32> {
+    return transform * vec4<f32>(v_in.position, f32(layer), 1.0);
+}
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return textureSample(txture, smplr, vec2<f32>(0.0)) + textureLoad(loaded, vec2<i32>(0), 0);
+}
+"#;
+
+    #[test]
+    fn bind_group_entries() {
+        let reflection = ShaderReflection::from_wgsl(SOURCE).unwrap();
+
+        let vertex_entries = reflection
+            .bind_group_entries(0, naga::ShaderStage::Vertex, "vs_main")
+            .unwrap();
+
+        assert_eq!(vertex_entries.len(), 1);
+        assert!(matches!(
+            vertex_entries[0].ty,
+            wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                ..
+            }
+        ));
+
+        let fragment_entries = reflection
+            .bind_group_entries(1, naga::ShaderStage::Fragment, "fs_main")
+            .unwrap();
+
+        let bindings: Vec<_> = fragment_entries.iter().map(|entry| entry.binding).collect();
+        assert_eq!(bindings, [0, 1, 3]);
+        assert!(matches!(
+            fragment_entries[0].ty,
+            wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                ..
+            }
+        ));
+        assert!(matches!(
+            fragment_entries[1].ty,
+            wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering)
+        ));
+        assert!(matches!(
+            fragment_entries[2].ty,
+            wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                ..
+            }
+        ));
+
+        assert!(reflection
+            .bind_group_entries(0, naga::ShaderStage::Vertex, "missing")
+            .is_err());
+    }
+
+    #[test]
+    fn vertex_inputs() {
+        let reflection = ShaderReflection::from_wgsl(SOURCE).unwrap();
+
+        let inputs = reflection.vertex_inputs("vs_main").unwrap();
+        let inputs: Vec<_> = inputs
+            .iter()
+            .map(|input| (input.name.as_str(), input.location, input.kind))
+            .collect();
+
+        assert_eq!(
+            inputs,
+            [
+                ("position", 0, naga::ScalarKind::Float),
+                ("layer", 1, naga::ScalarKind::Uint)
+            ]
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use crate::graphics::*;
+
+/// How a pipeline interacts with the stencil target of a stencil section, see `RenderPass::begin_stencil_section`
+///
+/// Pipelines used within a stencil section must be built with a StencilMode.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StencilMode {
+    /// Draws normally, ignoring the mask
+    #[default]
+    Ignore,
+    /// Writes the stencil reference where fragments pass, without writing color
+    Write,
+    /// Draws only where the mask matches the stencil reference
+    Equal,
+    /// Draws only where the mask differs from the stencil reference
+    NotEqual,
+}
+
+impl StencilMode {
+    pub const ALL: [StencilMode; 4] = [
+        StencilMode::Ignore,
+        StencilMode::Write,
+        StencilMode::Equal,
+        StencilMode::NotEqual,
+    ];
+
+    /// The reference value each queue starts with in a stencil section
+    pub const DEFAULT_REFERENCE: u32 = 1;
+
+    pub fn depth_stencil_state(self) -> wgpu::DepthStencilState {
+        let (compare, pass_op) = match self {
+            StencilMode::Ignore => (wgpu::CompareFunction::Always, wgpu::StencilOperation::Keep),
+            StencilMode::Write => (
+                wgpu::CompareFunction::Always,
+                wgpu::StencilOperation::Replace,
+            ),
+            StencilMode::Equal => (wgpu::CompareFunction::Equal, wgpu::StencilOperation::Keep),
+            StencilMode::NotEqual => (
+                wgpu::CompareFunction::NotEqual,
+                wgpu::StencilOperation::Keep,
+            ),
+        };
+
+        let face = wgpu::StencilFaceState {
+            compare,
+            fail_op: wgpu::StencilOperation::Keep,
+            depth_fail_op: wgpu::StencilOperation::Keep,
+            pass_op,
+        };
+
+        wgpu::DepthStencilState {
+            format: RenderTarget::STENCIL_FORMAT,
+            depth_write_enabled: Some(false),
+            depth_compare: Some(wgpu::CompareFunction::Always),
+            stencil: wgpu::StencilState {
+                // flipped sprites are back facing
+                front: face,
+                back: face,
+                read_mask: !0,
+                write_mask: !0,
+            },
+            bias: Default::default(),
+        }
+    }
+
+    /// Masks are invisible, other modes write every channel
+    pub fn color_writes(self) -> wgpu::ColorWrites {
+        match self {
+            StencilMode::Write => wgpu::ColorWrites::empty(),
+            _ => wgpu::ColorWrites::ALL,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use super::mipmap_generator::mip_level_count;
 use crate::{async_task::promise_future, graphics::*};
 use cfg_macros::{cfg_native, cfg_web};
 use math::*;
@@ -24,48 +25,150 @@ impl Texture {
     ) -> image::ImageResult<Arc<Self>> {
         let image = image::load_from_memory(bytes)?;
         let rgba_image = image.to_rgba8();
-        let size = rgba_image.dimensions();
-        let (width, height) = size;
+        let (width, height) = rgba_image.dimensions();
+        let size = UVec2::new(width, height);
 
-        let graphics = graphics.graphics();
-        let device = graphics.device();
-        let queue = graphics.queue();
+        let texture = Self::create(graphics, size, format, 1, wgpu::TextureUsages::empty());
+        texture.write_region(graphics, UVec2::ZERO, size, &rgba_image);
 
-        let extent = wgpu::Extent3d {
-            width,
-            height,
-            depth_or_array_layers: 1,
-        };
+        Ok(Arc::new(texture))
+    }
+
+    /// Loads with a full mip chain generated on the GPU, reducing shimmering when drawn scaled down.
+    ///
+    /// Mipmaps are used with any filter, see `TextureSamplerOptions::with_mipmap_filter()` for blending between levels.
+    pub fn load_from_memory_with_mipmaps(
+        graphics: &impl HasGraphicsContext,
+        bytes: &[u8],
+    ) -> image::ImageResult<Arc<Self>> {
+        let image = image::load_from_memory(bytes)?;
+        let rgba_image = image.to_rgba8();
+        let (width, height) = rgba_image.dimensions();
+        let size = UVec2::new(width, height);
+
+        let texture = Self::create(
+            graphics,
+            size,
+            Self::DEFAULT_FORMAT,
+            mip_level_count(width, height),
+            wgpu::TextureUsages::RENDER_ATTACHMENT,
+        );
+        texture.write_region(graphics, UVec2::ZERO, size, &rgba_image);
+        texture.generate_mipmaps(graphics);
+
+        Ok(Arc::new(texture))
+    }
+
+    /// Creates a texture with uninitialized contents, to be filled with `write_region()`
+    pub fn new_empty(
+        graphics: &impl HasGraphicsContext,
+        size: UVec2,
+        format: wgpu::TextureFormat,
+    ) -> Arc<Self> {
+        Arc::new(Self::create(
+            graphics,
+            size,
+            format,
+            1,
+            wgpu::TextureUsages::COPY_SRC,
+        ))
+    }
+
+    /// Creates a texture from tightly packed RGBA8 pixels, expects `size.x * size.y * 4` bytes
+    pub fn from_rgba(graphics: &impl HasGraphicsContext, size: UVec2, rgba: &[u8]) -> Arc<Self> {
+        let texture = Self::create(
+            graphics,
+            size,
+            Self::DEFAULT_FORMAT,
+            1,
+            wgpu::TextureUsages::COPY_SRC,
+        );
+        texture.write_region(graphics, UVec2::ZERO, size, rgba);
+
+        Arc::new(texture)
+    }
+
+    fn create(
+        graphics: &impl HasGraphicsContext,
+        size: UVec2,
+        format: wgpu::TextureFormat,
+        mip_level_count: u32,
+        additional_usage: wgpu::TextureUsages,
+    ) -> Self {
+        let device = graphics.graphics().device();
 
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label: None,
-            size: extent,
-            mip_level_count: 1,
+            size: wgpu::Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST
+                | additional_usage,
             view_formats: &[],
         });
 
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self { view }
+    }
+
+    /// Replaces the pixels in a rectangle, expects tightly packed rows in the texture's format.
+    /// Only supports uncompressed texture formats.
+    ///
+    /// Only the base level is written, call `generate_mipmaps()` afterwards to update mipmaps.
+    ///
+    /// Writes are applied before the next submission, so draws queued earlier in the frame also see the new pixels.
+    pub fn write_region(
+        &self,
+        graphics: &impl HasGraphicsContext,
+        position: UVec2,
+        size: UVec2,
+        bytes: &[u8],
+    ) {
+        let texture = self.view.texture();
+        let bytes_per_pixel = texture.format().block_copy_size(None).unwrap_or(4);
+
         let image_copy_texture = wgpu::TexelCopyTextureInfo {
-            texture: &texture,
+            texture,
             mip_level: 0,
-            origin: wgpu::Origin3d::ZERO,
+            origin: wgpu::Origin3d {
+                x: position.x,
+                y: position.y,
+                z: 0,
+            },
             aspect: wgpu::TextureAspect::All,
         };
 
         let image_data_layout = wgpu::TexelCopyBufferLayout {
             offset: 0,
-            bytes_per_row: Some(4 * width),
-            rows_per_image: Some(height),
+            bytes_per_row: Some(bytes_per_pixel * size.x),
+            rows_per_image: Some(size.y),
         };
 
-        queue.write_texture(image_copy_texture, &rgba_image, image_data_layout, extent);
+        let extent = wgpu::Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        };
 
-        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let queue = graphics.graphics().queue();
+        queue.write_texture(image_copy_texture, bytes, image_data_layout, extent);
+    }
+
+    /// Regenerates mip levels from the base level, does nothing for textures loaded without mipmaps
+    pub fn generate_mipmaps(&self, graphics: &impl HasGraphicsContext) {
+        graphics.graphics().generate_mipmaps(self.view.texture());
+    }
 
-        Ok(Arc::new(Self { view }))
+    pub fn mip_level_count(&self) -> u32 {
+        self.view.texture().mip_level_count()
     }
 
     pub fn width(&self) -> u32 {
@@ -1,14 +1,113 @@
 use crate::graphics::*;
+use logging::log;
 use std::sync::Arc;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SamplingFilter {
     Linear,
     Nearest,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EdgeSampling {
     Clamp,
     Repeat,
+    MirrorRepeat,
+    /// Samples outside of the texture use the border color, see `TextureSamplerOptions::with_border_color()`
+    ///
+    /// Falls back to `Clamp` when unsupported by the device.
+    Border,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderColor {
+    TransparentBlack,
+    OpaqueBlack,
+    OpaqueWhite,
+}
+
+#[derive(Debug, Clone)]
+pub struct TextureSamplerOptions {
+    pub mag_filter: SamplingFilter,
+    pub min_filter: SamplingFilter,
+    /// Filtering between mip levels, only affects textures with mipmaps
+    pub mipmap_filter: SamplingFilter,
+    pub edge_sampling_u: EdgeSampling,
+    pub edge_sampling_v: EdgeSampling,
+    pub border_color: BorderColor,
+    /// 1 to disable, values above 1 require every filter to be `SamplingFilter::Linear`
+    pub anisotropy: u16,
+}
+
+impl Default for TextureSamplerOptions {
+    fn default() -> Self {
+        Self {
+            mag_filter: SamplingFilter::Nearest,
+            min_filter: SamplingFilter::Nearest,
+            mipmap_filter: SamplingFilter::Nearest,
+            edge_sampling_u: EdgeSampling::Clamp,
+            edge_sampling_v: EdgeSampling::Clamp,
+            border_color: BorderColor::TransparentBlack,
+            anisotropy: 1,
+        }
+    }
+}
+
+impl TextureSamplerOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the mag, min, and mipmap filters
+    pub fn with_filter(mut self, filter: SamplingFilter) -> Self {
+        self.mag_filter = filter;
+        self.min_filter = filter;
+        self.mipmap_filter = filter;
+        self
+    }
+
+    pub fn with_mag_filter(mut self, filter: SamplingFilter) -> Self {
+        self.mag_filter = filter;
+        self
+    }
+
+    pub fn with_min_filter(mut self, filter: SamplingFilter) -> Self {
+        self.min_filter = filter;
+        self
+    }
+
+    pub fn with_mipmap_filter(mut self, filter: SamplingFilter) -> Self {
+        self.mipmap_filter = filter;
+        self
+    }
+
+    /// Sets edge sampling for both axes
+    pub fn with_edge_sampling(mut self, edge_sampling: EdgeSampling) -> Self {
+        self.edge_sampling_u = edge_sampling;
+        self.edge_sampling_v = edge_sampling;
+        self
+    }
+
+    pub fn with_edge_sampling_u(mut self, edge_sampling: EdgeSampling) -> Self {
+        self.edge_sampling_u = edge_sampling;
+        self
+    }
+
+    pub fn with_edge_sampling_v(mut self, edge_sampling: EdgeSampling) -> Self {
+        self.edge_sampling_v = edge_sampling;
+        self
+    }
+
+    pub fn with_border_color(mut self, color: BorderColor) -> Self {
+        self.border_color = color;
+        self
+    }
+
+    /// Clamped to 1..=16
+    pub fn with_anisotropy(mut self, anisotropy: u16) -> Self {
+        self.anisotropy = anisotropy.clamp(1, 16);
+        self
+    }
 }
 
 pub struct TextureSampler {
@@ -21,25 +120,83 @@ impl TextureSampler {
         sampling_filter: SamplingFilter,
         edge_sampling: EdgeSampling,
     ) -> Arc<Self> {
-        let address_mode = match edge_sampling {
+        Self::new_with_options(
+            graphics,
+            TextureSamplerOptions::new()
+                .with_mag_filter(sampling_filter)
+                .with_min_filter(sampling_filter)
+                .with_edge_sampling(edge_sampling),
+        )
+    }
+
+    pub fn new_with_options(
+        graphics: &impl HasGraphicsContext,
+        options: TextureSamplerOptions,
+    ) -> Arc<Self> {
+        let device = graphics.graphics().device();
+
+        let border_supported = device
+            .features()
+            .contains(wgpu::Features::ADDRESS_MODE_CLAMP_TO_BORDER);
+
+        let address_mode = |edge_sampling| match edge_sampling {
             EdgeSampling::Clamp => wgpu::AddressMode::ClampToEdge,
             EdgeSampling::Repeat => wgpu::AddressMode::Repeat,
+            EdgeSampling::MirrorRepeat => wgpu::AddressMode::MirrorRepeat,
+            EdgeSampling::Border if border_supported => wgpu::AddressMode::ClampToBorder,
+            EdgeSampling::Border => {
+                log::warn!("EdgeSampling::Border is unsupported on this device, using Clamp");
+                wgpu::AddressMode::ClampToEdge
+            }
         };
 
-        let zoom_filter = match sampling_filter {
+        let address_mode_u = address_mode(options.edge_sampling_u);
+        let address_mode_v = address_mode(options.edge_sampling_v);
+
+        let uses_border = address_mode_u == wgpu::AddressMode::ClampToBorder
+            || address_mode_v == wgpu::AddressMode::ClampToBorder;
+
+        let border_color = uses_border.then_some(match options.border_color {
+            BorderColor::TransparentBlack => wgpu::SamplerBorderColor::TransparentBlack,
+            BorderColor::OpaqueBlack => wgpu::SamplerBorderColor::OpaqueBlack,
+            BorderColor::OpaqueWhite => wgpu::SamplerBorderColor::OpaqueWhite,
+        });
+
+        let filter_mode = |filter| match filter {
             SamplingFilter::Linear => wgpu::FilterMode::Linear,
             SamplingFilter::Nearest => wgpu::FilterMode::Nearest,
         };
 
-        let device = graphics.graphics().device();
+        let mipmap_filter = match options.mipmap_filter {
+            SamplingFilter::Linear => wgpu::MipmapFilterMode::Linear,
+            SamplingFilter::Nearest => wgpu::MipmapFilterMode::Nearest,
+        };
+
+        let all_linear = [
+            options.mag_filter,
+            options.min_filter,
+            options.mipmap_filter,
+        ]
+        .iter()
+        .all(|filter| *filter == SamplingFilter::Linear);
+
+        let anisotropy_clamp = if options.anisotropy > 1 && !all_linear {
+            log::warn!("Anisotropic filtering requires linear filters, disabling anisotropy");
+            1
+        } else {
+            options.anisotropy.clamp(1, 16)
+        };
 
         Arc::new(Self {
             sampler: device.create_sampler(&wgpu::SamplerDescriptor {
-                address_mode_u: address_mode,
-                address_mode_v: address_mode,
-                address_mode_w: address_mode,
-                mag_filter: zoom_filter,
-                min_filter: zoom_filter,
+                address_mode_u,
+                address_mode_v,
+                address_mode_w: address_mode_u,
+                mag_filter: filter_mode(options.mag_filter),
+                min_filter: filter_mode(options.min_filter),
+                mipmap_filter,
+                anisotropy_clamp,
+                border_color,
                 ..Default::default()
             }),
         })
@@ -1,3 +1,4 @@
+use super::render_pipeline_variants::VertexBufferDescription;
 use crate::graphics::*;
 use math::*;
 use wgpu::VertexFormat;
@@ -39,7 +40,7 @@ impl VertexLayout {
         self.attributes.len() as u32
     }
 
-    pub(super) fn build<Vertex>(&self) -> Result<wgpu::VertexBufferLayout<'_>, String> {
+    pub(super) fn build<Vertex>(self) -> Result<VertexBufferDescription, String> {
         let array_stride = std::mem::size_of::<Vertex>() as wgpu::BufferAddress;
 
         if array_stride < self.size {
@@ -48,10 +49,10 @@ impl VertexLayout {
             ));
         }
 
-        Ok(wgpu::VertexBufferLayout {
+        Ok(VertexBufferDescription {
             array_stride,
             step_mode: wgpu::VertexStepMode::Vertex,
-            attributes: &self.attributes,
+            attributes: self.attributes,
         })
     }
 }
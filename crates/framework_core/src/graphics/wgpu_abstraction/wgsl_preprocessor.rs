@@ -0,0 +1,558 @@
+use crate::graphics::*;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use wgpu::naga;
+
+/// Expands directives in WGSL before it's handed to wgpu:
+///
+/// - `#include <name>` inserts a snippet added with `with_snippet()` or one of `BUILT_IN_SNIPPETS`
+/// - `#include "path"` inserts a file, relative to the including file
+/// - `#define NAME value` replaces `NAME` in following lines, the value is optional
+/// - `#undef NAME`
+/// - `#ifdef NAME`, `#ifndef NAME`, `#else`, `#endif`
+///
+/// Snippets and files are only included once.
+#[derive(Clone)]
+pub struct WgslPreprocessor {
+    snippets: HashMap<String, String>,
+    defines: HashMap<String, String>,
+    preludes: Vec<String>,
+}
+
+impl WgslPreprocessor {
+    /// - `framework/camera.wgsl`: `CameraUniform` and `camera` at `@group(0) @binding(0)`
+    /// - `framework/sprite_vertex.wgsl`: `SpriteVertexInput`, `sprite_position()`, and `sprite_uv()`, includes the camera
+    /// - `framework/sprite_fragment.wgsl`: `SpriteMaterial::FRAGMENT_PRELUDE`
+    pub const BUILT_IN_SNIPPETS: [(&'static str, &'static str); 3] = [
+        (
+            "framework/camera.wgsl",
+            include_str!("../cameras/camera.wgsl"),
+        ),
+        (
+            "framework/sprite_vertex.wgsl",
+            include_str!("../sprites/sprite_vertex.wgsl"),
+        ),
+        (
+            "framework/sprite_fragment.wgsl",
+            SpriteMaterial::FRAGMENT_PRELUDE,
+        ),
+    ];
+
+    pub fn new() -> Self {
+        let snippets = Self::BUILT_IN_SNIPPETS
+            .iter()
+            .map(|(name, source)| (name.to_string(), source.to_string()))
+            .collect();
+
+        Self {
+            snippets,
+            defines: HashMap::new(),
+            preludes: Vec::new(),
+        }
+    }
+
+    /// Makes the source available to `#include <name>`
+    pub fn with_snippet(mut self, name: &str, source: &str) -> Self {
+        self.snippets.insert(name.to_string(), source.to_string());
+        self
+    }
+
+    /// Defined before processing begins, use an empty value for names only checked with `#ifdef`
+    pub fn with_define(mut self, name: &str, value: &str) -> Self {
+        self.defines.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Includes the snippet before the processed source, as if the source started with `#include <name>`
+    pub fn with_prelude(mut self, snippet_name: &str) -> Self {
+        self.preludes.push(snippet_name.to_string());
+        self
+    }
+
+    pub fn process_str(&self, source: &str) -> Result<PreprocessedWgsl, WgslPreprocessorError> {
+        let file: Arc<str> = Arc::from("wgsl");
+
+        let mut state = ProcessState::new(self);
+        state.include_preludes(&self.preludes, &file)?;
+        state.process(file, source, None)?;
+
+        Ok(state.finish())
+    }
+
+    /// Reads and processes the file, `#include "path"` is resolved relative to it
+    pub fn process_file<P: AsRef<Path> + ?Sized>(
+        &self,
+        path: &P,
+    ) -> Result<PreprocessedWgsl, WgslPreprocessorError> {
+        let path = path.as_ref();
+        let file = Arc::from(path.to_string_lossy().as_ref());
+
+        let mut state = ProcessState::new(self);
+        state.include_preludes(&self.preludes, &file)?;
+        state.process_file(path, None)?;
+
+        Ok(state.finish())
+    }
+}
+
+impl Default for WgslPreprocessor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Output of the `WgslPreprocessor`, tracks the origin of every line
+#[derive(Debug, Clone)]
+pub struct PreprocessedWgsl {
+    source: String,
+    line_map: Vec<WgslSourceLocation>,
+}
+
+impl PreprocessedWgsl {
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Maps a line in the processed source, starting at 1, to the file or snippet it came from
+    pub fn original_location(&self, line: usize) -> Option<&WgslSourceLocation> {
+        self.line_map.get(line.checked_sub(1)?)
+    }
+
+    /// Parses and validates the source with naga, errors are reported at their original location.
+    ///
+    /// The column is relative to the processed line.
+    pub fn validate(&self) -> Result<naga::Module, WgslPreprocessorError> {
+        let module = naga::front::wgsl::parse_str(&self.source).map_err(|error| {
+            self.shader_error(error.location(&self.source), error.message().to_string())
+        })?;
+
+        let mut validator = naga::valid::Validator::new(
+            naga::valid::ValidationFlags::all(),
+            naga::valid::Capabilities::all(),
+        );
+
+        validator.validate(&module).map_err(|error| {
+            // the outer error only names the function or global
+            let mut message = error.as_inner().to_string();
+            let mut cause = std::error::Error::source(error.as_inner());
+
+            while let Some(error) = cause {
+                message += &format!(": {error}");
+                cause = error.source();
+            }
+
+            // the narrowest span points at the expression rather than the whole function
+            let location = error
+                .spans()
+                .filter(|(span, _)| span.is_defined())
+                .map(|(span, _)| span.location(&self.source))
+                .min_by_key(|location| location.length);
+
+            self.shader_error(location, message)
+        })?;
+
+        Ok(module)
+    }
+
+    fn shader_error(
+        &self,
+        location: Option<naga::SourceLocation>,
+        message: String,
+    ) -> WgslPreprocessorError {
+        let Some(location) = location else {
+            return WgslPreprocessorError::Shader {
+                location: None,
+                column: 0,
+                message,
+            };
+        };
+
+        WgslPreprocessorError::Shader {
+            location: self
+                .original_location(location.line_number as usize)
+                .cloned(),
+            column: location.line_position as usize,
+            message,
+        }
+    }
+}
+
+struct Conditional {
+    active: bool,
+    parent_active: bool,
+    has_else: bool,
+    location: WgslSourceLocation,
+}
+
+struct ProcessState<'a> {
+    snippets: &'a HashMap<String, String>,
+    defines: HashMap<String, String>,
+    /// `<name>` for snippets, paths for files
+    included: HashSet<String>,
+    source: String,
+    line_map: Vec<WgslSourceLocation>,
+}
+
+impl<'a> ProcessState<'a> {
+    fn new(preprocessor: &'a WgslPreprocessor) -> Self {
+        Self {
+            snippets: &preprocessor.snippets,
+            defines: preprocessor.defines.clone(),
+            included: HashSet::new(),
+            source: String::new(),
+            line_map: Vec::new(),
+        }
+    }
+
+    fn finish(self) -> PreprocessedWgsl {
+        PreprocessedWgsl {
+            source: self.source,
+            line_map: self.line_map,
+        }
+    }
+
+    fn process_file(
+        &mut self,
+        path: &Path,
+        location: Option<&WgslSourceLocation>,
+    ) -> Result<(), WgslPreprocessorError> {
+        let key = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+        if !self.included.insert(key.to_string_lossy().into_owned()) {
+            return Ok(());
+        }
+
+        let source = std::fs::read_to_string(path).map_err(|error| WgslPreprocessorError::Io {
+            path: path.to_path_buf(),
+            location: location.cloned(),
+            error,
+        })?;
+
+        let file = Arc::from(path.to_string_lossy().as_ref());
+        self.process(file, &source, path.parent())
+    }
+
+    fn process(
+        &mut self,
+        file: Arc<str>,
+        source: &str,
+        directory: Option<&Path>,
+    ) -> Result<(), WgslPreprocessorError> {
+        let mut conditionals: Vec<Conditional> = Vec::new();
+
+        for (i, line) in source.lines().enumerate() {
+            let location = WgslSourceLocation {
+                file: file.clone(),
+                line: i + 1,
+            };
+
+            let active = conditionals.last().is_none_or(|c| c.active);
+
+            let Some(directive_line) = line.trim().strip_prefix('#') else {
+                if active {
+                    self.push_line(line, location);
+                }
+                continue;
+            };
+
+            let (directive, argument) = directive_line
+                .split_once(char::is_whitespace)
+                .map(|(directive, argument)| (directive, argument.trim()))
+                .unwrap_or((directive_line, ""));
+
+            let invalid_directive = |location| WgslPreprocessorError::InvalidDirective {
+                directive: line.trim().to_string(),
+                location,
+            };
+
+            match directive {
+                "ifdef" | "ifndef" => {
+                    if !is_identifier(argument) {
+                        return Err(invalid_directive(location));
+                    }
+
+                    let expected = directive == "ifdef";
+                    let defined = self.defines.contains_key(argument);
+
+                    conditionals.push(Conditional {
+                        active: active && defined == expected,
+                        parent_active: active,
+                        has_else: false,
+                        location,
+                    });
+                }
+                "else" | "endif" if conditionals.is_empty() => {
+                    return Err(WgslPreprocessorError::UnmatchedDirective {
+                        directive: format!("#{directive}"),
+                        location,
+                    });
+                }
+                "else" => {
+                    let conditional = conditionals.last_mut().unwrap();
+
+                    if conditional.has_else {
+                        return Err(invalid_directive(location));
+                    }
+
+                    conditional.has_else = true;
+                    conditional.active = conditional.parent_active && !conditional.active;
+                }
+                "endif" => {
+                    conditionals.pop();
+                }
+                // skipped branches may use directives that would fail, such as includes for other platforms
+                _ if !active => {}
+                "define" => {
+                    let (name, value) = argument
+                        .split_once(char::is_whitespace)
+                        .unwrap_or((argument, ""));
+
+                    if !is_identifier(name) {
+                        return Err(invalid_directive(location));
+                    }
+
+                    self.defines
+                        .insert(name.to_string(), value.trim().to_string());
+                }
+                "undef" => {
+                    if !is_identifier(argument) {
+                        return Err(invalid_directive(location));
+                    }
+
+                    self.defines.remove(argument);
+                }
+                "include" => {
+                    if let Some(name) = argument
+                        .strip_prefix('<')
+                        .and_then(|argument| argument.strip_suffix('>'))
+                    {
+                        self.include_snippet(name, location)?;
+                    } else if let Some(relative_path) = argument
+                        .strip_prefix('"')
+                        .and_then(|argument| argument.strip_suffix('"'))
+                    {
+                        let path = match directory {
+                            Some(directory) => directory.join(relative_path),
+                            None => PathBuf::from(relative_path),
+                        };
+
+                        self.process_file(&path, Some(&location))?;
+                    } else {
+                        return Err(invalid_directive(location));
+                    }
+                }
+                _ => return Err(invalid_directive(location)),
+            }
+        }
+
+        if let Some(conditional) = conditionals.pop() {
+            return Err(WgslPreprocessorError::UnterminatedConditional {
+                location: conditional.location,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn include_preludes(
+        &mut self,
+        preludes: &[String],
+        file: &Arc<str>,
+    ) -> Result<(), WgslPreprocessorError> {
+        for name in preludes {
+            let location = WgslSourceLocation {
+                file: file.clone(),
+                line: 1,
+            };
+
+            self.include_snippet(name, location)?;
+        }
+
+        Ok(())
+    }
+
+    fn include_snippet(
+        &mut self,
+        name: &str,
+        location: WgslSourceLocation,
+    ) -> Result<(), WgslPreprocessorError> {
+        let snippets = self.snippets;
+
+        let Some(source) = snippets.get(name) else {
+            return Err(WgslPreprocessorError::UnknownSnippet {
+                name: name.to_string(),
+                location,
+            });
+        };
+
+        if !self.included.insert(format!("<{name}>")) {
+            return Ok(());
+        }
+
+        self.process(Arc::from(name), source, None)
+    }
+
+    fn push_line(&mut self, line: &str, location: WgslSourceLocation) {
+        substitute_defines(line, &self.defines, &mut self.source);
+        self.source.push('\n');
+        self.line_map.push(location);
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    let Some(first) = chars.next() else {
+        return false;
+    };
+
+    (first.is_alphabetic() || first == '_') && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn substitute_defines(line: &str, defines: &HashMap<String, String>, output: &mut String) {
+    let mut word_start = None;
+
+    // a trailing space to flush the last word
+    for (i, c) in line
+        .char_indices()
+        .chain(std::iter::once((line.len(), ' ')))
+    {
+        let is_word_char = c.is_alphanumeric() || c == '_';
+
+        match (word_start, is_word_char) {
+            (None, true) => word_start = Some(i),
+            (Some(start), false) => {
+                let word = &line[start..i];
+
+                match defines.get(word) {
+                    Some(value) if !value.is_empty() => output.push_str(value),
+                    _ => output.push_str(word),
+                }
+
+                word_start = None;
+            }
+            _ => {}
+        }
+
+        if !is_word_char && i < line.len() {
+            output.push(c);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn process(preprocessor: &WgslPreprocessor, source: &str) -> String {
+        preprocessor
+            .process_str(source)
+            .unwrap()
+            .source()
+            .to_string()
+    }
+
+    #[test]
+    fn defines() {
+        let preprocessor = WgslPreprocessor::new().with_define("SIZE", "4");
+
+        let source =
+            "#define SCALE 2.0\nlet a = SIZE * SCALE;\n#undef SCALE\nlet b = SCALE_X + SCALE;";
+
+        assert_eq!(
+            process(&preprocessor, source),
+            "let a = 4 * 2.0;\nlet b = SCALE_X + SCALE;\n"
+        );
+    }
+
+    #[test]
+    fn conditionals() {
+        let preprocessor = WgslPreprocessor::new().with_define("A", "");
+
+        let source = "\
+#ifdef A
+a
+#ifndef B
+not b
+#else
+b
+#endif
+#else
+not a
+#include <missing>
+#endif";
+
+        assert_eq!(process(&preprocessor, source), "a\nnot b\n");
+
+        assert!(matches!(
+            preprocessor.process_str("#ifdef A"),
+            Err(WgslPreprocessorError::UnterminatedConditional { .. })
+        ));
+
+        assert!(matches!(
+            preprocessor.process_str("#endif"),
+            Err(WgslPreprocessorError::UnmatchedDirective { .. })
+        ));
+    }
+
+    #[test]
+    fn includes() {
+        let preprocessor = WgslPreprocessor::new()
+            .with_snippet("a", "a")
+            .with_snippet("b", "#include <a>\nb");
+
+        let processed = preprocessor
+            .process_str("#include <b>\n#include <a>\nsource")
+            .unwrap();
+
+        assert_eq!(processed.source(), "a\nb\nsource\n");
+
+        let location = processed.original_location(2).unwrap();
+        assert_eq!(&*location.file, "b");
+        assert_eq!(location.line, 2);
+
+        let processed = preprocessor
+            .clone()
+            .with_prelude("a")
+            .process_str("#include <a>\nsource")
+            .unwrap();
+
+        assert_eq!(processed.source(), "a\nsource\n");
+        assert_eq!(processed.original_location(2).unwrap().line, 2);
+
+        assert!(matches!(
+            preprocessor.process_str("\n#include <c>"),
+            Err(WgslPreprocessorError::UnknownSnippet { location, .. }) if location.line == 2
+        ));
+    }
+
+    #[test]
+    fn shader_error_location() {
+        let preprocessor = WgslPreprocessor::new()
+            .with_snippet("broken", "\nfn broken() -> f32 {\n  return 1u;\n}");
+
+        let processed = preprocessor
+            .process_str("#include <framework/camera.wgsl>\n#include <broken>")
+            .unwrap();
+
+        let Err(WgslPreprocessorError::Shader { location, .. }) = processed.validate() else {
+            panic!("expected a shader error");
+        };
+
+        let location = location.unwrap();
+        assert_eq!(&*location.file, "broken");
+        assert_eq!(location.line, 3);
+    }
+
+    #[test]
+    fn built_in_snippets() {
+        for (name, _) in WgslPreprocessor::BUILT_IN_SNIPPETS {
+            let processed = WgslPreprocessor::new()
+                .process_str(&format!("#include <{name}>"))
+                .unwrap();
+
+            processed.validate().unwrap();
+        }
+    }
+}
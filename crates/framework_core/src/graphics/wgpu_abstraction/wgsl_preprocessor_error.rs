@@ -0,0 +1,99 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// A line in a file or snippet passed to the `WgslPreprocessor`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WgslSourceLocation {
+    pub file: Arc<str>,
+    /// Starts at 1
+    pub line: usize,
+}
+
+impl fmt::Display for WgslSourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+#[derive(Debug)]
+pub enum WgslPreprocessorError {
+    /// The location is the `#include` reading the file, if any
+    Io {
+        path: PathBuf,
+        location: Option<WgslSourceLocation>,
+        error: std::io::Error,
+    },
+    UnknownSnippet {
+        name: String,
+        location: WgslSourceLocation,
+    },
+    InvalidDirective {
+        directive: String,
+        location: WgslSourceLocation,
+    },
+    /// An `#else` or `#endif` without an `#ifdef` or `#ifndef`
+    UnmatchedDirective {
+        directive: String,
+        location: WgslSourceLocation,
+    },
+    /// An `#ifdef` or `#ifndef` without an `#endif`
+    UnterminatedConditional { location: WgslSourceLocation },
+    /// Parsing or validation failed, mapped back to the original source when possible
+    Shader {
+        location: Option<WgslSourceLocation>,
+        /// Starts at 1
+        column: usize,
+        message: String,
+    },
+}
+
+impl fmt::Display for WgslPreprocessorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WgslPreprocessorError::Io {
+                path,
+                location: Some(location),
+                error,
+            } => write!(f, "{location}: Failed to include {path:?}: {error}"),
+            WgslPreprocessorError::Io {
+                path,
+                location: None,
+                error,
+            } => write!(f, "Failed to read {path:?}: {error}"),
+            WgslPreprocessorError::UnknownSnippet { name, location } => {
+                write!(f, "{location}: Unknown snippet <{name}>")
+            }
+            WgslPreprocessorError::InvalidDirective {
+                directive,
+                location,
+            } => write!(f, "{location}: Invalid directive {directive:?}"),
+            WgslPreprocessorError::UnmatchedDirective {
+                directive,
+                location,
+            } => write!(f, "{location}: {directive} without #ifdef or #ifndef"),
+            WgslPreprocessorError::UnterminatedConditional { location } => {
+                write!(f, "{location}: Missing #endif")
+            }
+            WgslPreprocessorError::Shader {
+                location: Some(location),
+                column,
+                message,
+            } => write!(f, "{location}:{column}: {message}"),
+            WgslPreprocessorError::Shader {
+                location: None,
+                message,
+                ..
+            } => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for WgslPreprocessorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            WgslPreprocessorError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -2,6 +2,8 @@ pub mod async_task;
 pub mod common;
 pub mod graphics;
 pub mod runtime;
+pub mod storage;
+pub mod ui;
 
 pub use image;
 pub use raw_window_handle;
@@ -1,8 +1,19 @@
 use crate::common::*;
 use crate::graphics::*;
 use crate::runtime::*;
-use math::{Instant, Vec2};
+use crate::storage::Storage;
+use input::ControllerMappingDatabase;
+use logging::log;
+use math::Instant;
 use std::any::TypeId;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Limits how often window state is written while the window is being dragged or resized
+const WINDOW_STATE_SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Limits how often a lost graphics device is recreated after a failed attempt
+const DEVICE_RECOVERY_INTERVAL: Duration = Duration::from_secs(1);
 
 pub type SceneConstructor = Box<dyn FnOnce(&mut GameIO) -> Box<dyn Scene>>;
 pub type ServiceConstructor = Box<dyn FnOnce(&mut GameIO) -> Box<dyn GameService>>;
@@ -15,17 +26,74 @@ pub(crate) enum GameRuntimeRequest {
     RenderOverlay(Box<dyn GameOverlay>),
     WindowOverlay(Box<dyn GameOverlay>),
     PostProcess(TypeId, Box<dyn PostProcess>),
+    OpenWindow(GameWindowId, SecondaryWindowConfig, WindowContent),
+    CloseWindow(GameWindowId),
 }
 
 pub struct GameRuntimeCoreParams {
     pub scene_constructor: SceneConstructor,
+    pub app_id: String,
+    pub window_state_path: Option<PathBuf>,
+    pub logical_resolution: bool,
+    pub controller_mappings: ControllerMappingDatabase,
     pub target_fps: u16,
+    pub msaa_sample_count: u32,
     pub service_constructors: Vec<ServiceConstructor>,
     pub overlay_constructors: Vec<(GameOverlayTarget, OverlayConstructor)>,
     pub setup_callbacks: Vec<SetupCallback>,
     pub post_process_constructors: Vec<PostProcessConstructor>,
 }
 
+struct WindowStatePersistence {
+    path: PathBuf,
+    state: WindowState,
+    dirty: bool,
+    last_save: Instant,
+}
+
+impl WindowStatePersistence {
+    fn new(path: PathBuf, window: &dyn GameWindow) -> Self {
+        let mut state = WindowState {
+            position: None,
+            size: window.size(),
+            fullscreen: false,
+        };
+
+        state.capture(window);
+
+        Self {
+            path,
+            state,
+            dirty: false,
+            last_save: Instant::now(),
+        }
+    }
+
+    fn update(&mut self, game_io: &GameIO) {
+        let window = game_io.window();
+
+        // fullscreen can change without a resize event
+        self.dirty |= window.fullscreen() != self.state.fullscreen;
+
+        if self.dirty
+            && (game_io.quitting() || self.last_save.elapsed() >= WINDOW_STATE_SAVE_INTERVAL)
+        {
+            self.save(window);
+        }
+    }
+
+    fn save(&mut self, window: &dyn GameWindow) {
+        self.state.capture(window);
+
+        if let Err(err) = self.state.save(&self.path) {
+            log::error!("Failed to save window state to {:?}: {err}", self.path);
+        }
+
+        self.dirty = false;
+        self.last_save = Instant::now();
+    }
+}
+
 pub struct GameRuntimeCore {
     event_buffer: Vec<GameWindowEvent>,
     scene_manager: SceneManager,
@@ -36,10 +104,17 @@ pub struct GameRuntimeCore {
     window_overlays: Vec<Box<dyn GameOverlay>>,
     post_processes: Vec<(TypeId, Box<dyn PostProcess>)>,
     post_model: TextureSourceModel,
-    render_sprite: Sprite,
     render_target: RenderTarget,
     render_target_b: RenderTarget,
-    camera: OrthoCamera,
+    sample_count: u32,
+    presenter: WindowPresenter,
+    last_device_recovery: Option<Instant>,
+    device_recovery_unsupported: bool,
+    window_requests: Vec<WindowRequest>,
+    pending_windows: Vec<(GameWindowId, Option<WindowContent>)>,
+    secondary_windows: Vec<(GameWindowId, SecondaryWindow)>,
+    closed_windows: Vec<GameWindowId>,
+    window_state_persistence: Option<WindowStatePersistence>,
 }
 
 impl GameRuntimeCore {
@@ -49,9 +124,19 @@ impl GameRuntimeCore {
     ) -> anyhow::Result<Self> {
         let window_size = window.size();
 
-        let mut game_io = GameIO::new(window);
+        let window_state_persistence = params
+            .window_state_path
+            .map(|path| WindowStatePersistence::new(path, window.as_ref()));
+
+        let storage = Storage::new_for_app(&params.app_id, window.storage_directory());
+
+        let mut game_io = GameIO::new(window, storage);
         game_io.set_target_fps(params.target_fps);
 
+        if params.logical_resolution {
+            game_io.set_logical_resolution(true);
+        }
+
         crate::common::default_resources::inject(&mut game_io);
 
         for callback in params.setup_callbacks {
@@ -88,10 +173,20 @@ impl GameRuntimeCore {
             .map(|constructor| constructor(&mut game_io))
             .collect();
 
-        let render_target = RenderTarget::new(&game_io, window_size);
-        let render_target_b = RenderTarget::new(&game_io, window_size);
-        let render_sprite = Sprite::new(&game_io, render_target.texture().clone());
-        let camera = OrthoCamera::new(&game_io, window_size.as_vec2());
+        let graphics = game_io.graphics();
+        let sample_count = graphics
+            .supported_sample_count(graphics.default_texture_format(), params.msaa_sample_count);
+
+        if sample_count != params.msaa_sample_count.max(1) {
+            log::warn!(
+                "MSAA with {} samples is unsupported, using {sample_count}",
+                params.msaa_sample_count
+            );
+        }
+
+        let render_target = RenderTarget::new_multisampled(&game_io, window_size, sample_count);
+        let render_target_b = RenderTarget::new_multisampled(&game_io, window_size, sample_count);
+        let presenter = WindowPresenter::new(&game_io, &render_target);
         let post_model = TextureSourceModel::new(&game_io, render_target.texture().clone());
 
         Ok(Self {
@@ -104,10 +199,17 @@ impl GameRuntimeCore {
             window_overlays,
             post_processes,
             post_model,
-            render_sprite,
             render_target,
             render_target_b,
-            camera,
+            sample_count,
+            presenter,
+            last_device_recovery: None,
+            device_recovery_unsupported: false,
+            window_requests: Vec::new(),
+            pending_windows: Vec::new(),
+            secondary_windows: Vec::new(),
+            closed_windows: Vec::new(),
+            window_state_persistence,
         })
     }
 
@@ -145,82 +247,182 @@ impl GameRuntimeCore {
         self.event_buffer.push(event)
     }
 
-    pub fn tick(&mut self) {
-        for request in self.game_io.runtime_requests.drain(..) {
-            match request {
-                GameRuntimeRequest::Service(service) => self.services.push(service),
-                GameRuntimeRequest::RenderOverlay(overlay) => self.render_overlays.push(overlay),
-                GameRuntimeRequest::WindowOverlay(overlay) => self.window_overlays.push(overlay),
-                GameRuntimeRequest::PostProcess(t, post_process) => {
-                    self.post_processes.push((t, post_process))
-                }
-            }
+    /// Windows requested through `GameIO::open_window()` since the last call,
+    /// each request should be answered with `attach_window()` or `reject_window()`
+    pub fn take_window_requests(&mut self) -> Vec<WindowRequest> {
+        std::mem::take(&mut self.window_requests)
+    }
+
+    pub fn attach_window(&mut self, id: GameWindowId, window: Box<dyn GameWindowLifecycle>) {
+        let content = self
+            .pending_windows
+            .iter()
+            .position(|(pending_id, _)| *pending_id == id)
+            .and_then(|index| self.pending_windows.remove(index).1);
+
+        let Some(content) = content else {
+            // closed before the backend could create it
+            self.closed_windows.push(id);
+            return;
+        };
+
+        let context = WindowContext::new(id, window);
+        let window = SecondaryWindow::new(&mut self.game_io, context, content);
+        self.secondary_windows.push((id, window));
+    }
+
+    /// Closes a requested window the backend is unable to create
+    pub fn reject_window(&mut self, id: GameWindowId) {
+        self.pending_windows
+            .retain(|(pending_id, _)| *pending_id != id);
+        self.game_io.forget_window(id);
+    }
+
+    pub fn window(&self, id: GameWindowId) -> Option<&dyn GameWindowLifecycle> {
+        if id == GameWindowId::MAIN {
+            return Some(self.game_io.window());
         }
 
-        if self.frame_end.elapsed() < self.game_io.target_sleep_duration() {
-            // running too fast skip tick (this issue should only occur on web)
+        self.secondary_windows
+            .iter()
+            .find(|(window_id, _)| *window_id == id)
+            .map(|(_, window)| window.window())
+    }
+
+    pub fn push_window_event(&mut self, id: GameWindowId, event: GameWindowEvent) {
+        if id == GameWindowId::MAIN {
+            self.push_event(event);
             return;
         }
 
-        let start_instant = Instant::now();
-        let game_io = &mut self.game_io;
-        game_io.set_frame_start_instant(start_instant);
+        if let Some((_, window)) = self
+            .secondary_windows
+            .iter_mut()
+            .find(|(window_id, _)| *window_id == id)
+        {
+            window.push_event(event);
+        }
+    }
 
-        // update the previous timing with new info before updates start
-        let lost_duration = start_instant - self.frame_end - game_io.target_sleep_duration();
-        game_io.set_lost_duration(lost_duration);
+    /// Windows dropped by the runtime since the last call, allowing backends to release associated resources
+    pub fn take_closed_windows(&mut self) -> Vec<GameWindowId> {
+        std::mem::take(&mut self.closed_windows)
+    }
 
-        // queue a new task
-        let mut events = Vec::new();
-        std::mem::swap(&mut events, &mut self.event_buffer);
+    fn close_window(&mut self, id: GameWindowId) {
+        if let Some(index) = self.window_requests.iter().position(|r| r.id == id) {
+            // the backend hasn't seen the request yet
+            self.window_requests.remove(index);
+            self.pending_windows
+                .retain(|(pending_id, _)| *pending_id != id);
+            return;
+        }
 
-        // update
-        game_io.handle_tasks();
-        game_io.handle_events(events);
+        if let Some((_, content)) = self
+            .pending_windows
+            .iter_mut()
+            .find(|(pending_id, _)| *pending_id == id)
+        {
+            // wait for the backend to respond before reporting the window as closed
+            *content = None;
+            return;
+        }
 
-        // pre_updates
-        for service in &mut self.services {
-            service.pre_update(game_io);
+        if let Some(index) = self
+            .secondary_windows
+            .iter()
+            .position(|(window_id, _)| *window_id == id)
+        {
+            self.secondary_windows.remove(index);
+            self.closed_windows.push(id);
         }
+    }
 
-        for overlay in &mut self.window_overlays {
-            overlay.pre_update(game_io);
+    /// False while the graphics device is lost, updates continue without drawing
+    fn drawing_enabled(&mut self) -> bool {
+        if !self.game_io.graphics().device_lost() {
+            return true;
         }
 
-        for overlay in &mut self.render_overlays {
-            overlay.pre_update(game_io);
+        if self.device_recovery_unsupported {
+            return false;
         }
 
-        // scene update
-        self.scene_manager.update(game_io);
+        if !self.game_io.window().supports_graphics_recreation() {
+            log::error!("Graphics device lost, recreating it is unsupported on this platform. Drawing is disabled");
+            self.device_recovery_unsupported = true;
+            return false;
+        }
 
-        // post_updates
-        for overlay in &mut self.render_overlays {
-            overlay.post_update(game_io);
+        self.recover_lost_device()
+    }
+
+    /// Recreates the graphics context and notifies everything holding graphics resources,
+    /// returns false if the device is still lost
+    fn recover_lost_device(&mut self) -> bool {
+        if self
+            .last_device_recovery
+            .is_some_and(|instant| instant.elapsed() < DEVICE_RECOVERY_INTERVAL)
+        {
+            return false;
         }
 
-        for (id, post_process) in &mut self.post_processes {
-            if !game_io.internal_is_post_process_enabled(*id) {
-                continue;
-            }
+        self.last_device_recovery = Some(Instant::now());
 
-            post_process.update(game_io);
+        log::warn!("Recreating lost graphics device");
+
+        let game_io = &mut self.game_io;
+
+        if let Err(err) = game_io.window_mut().recreate_graphics() {
+            log::error!("Failed to recreate graphics device: {err}");
+            return false;
         }
 
-        for overlay in &mut self.window_overlays {
-            overlay.post_update(game_io);
+        self.last_device_recovery = None;
+
+        crate::common::default_resources::inject(game_io);
+
+        let resolution = game_io.window().resolution();
+        let sample_count = self.sample_count;
+
+        self.render_target = RenderTarget::new_multisampled(game_io, resolution, sample_count);
+        self.render_target_b = RenderTarget::new_multisampled(game_io, resolution, sample_count);
+        self.presenter = WindowPresenter::new(game_io, &self.render_target);
+        self.post_model = TextureSourceModel::new(game_io, self.render_target.texture().clone());
+
+        let graphics = game_io.graphics().clone();
+
+        for (_, window) in &mut self.secondary_windows {
+            window.graphics_recreated(game_io, graphics.clone());
         }
 
         for service in &mut self.services {
-            service.post_update(game_io);
+            service.graphics_recreated(game_io);
         }
 
-        // kick off new tasks
-        game_io.handle_tasks();
+        for overlay in &mut self.window_overlays {
+            overlay.graphics_recreated(game_io);
+        }
 
-        let update_instant = Instant::now();
+        for overlay in &mut self.render_overlays {
+            overlay.graphics_recreated(game_io);
+        }
+
+        for (_, post_process) in &mut self.post_processes {
+            post_process.graphics_recreated(game_io);
+        }
+
+        self.scene_manager.graphics_recreated(game_io);
+
+        log::info!("Graphics device recreated");
+
+        true
+    }
+
+    /// Draws the main window, returns the time spent acquiring the window's buffer
+    fn draw(&mut self) -> Duration {
+        let game_io = &mut self.game_io;
 
-        // draw
         let window = game_io.window();
         let graphics = game_io.graphics();
         let device = graphics.device();
@@ -277,40 +479,19 @@ impl GameRuntimeCore {
             post_process.draw(game_io, render_pass, &self.post_model);
         }
 
-        // update camera
-        let window = game_io.window();
-        let window_size = window.size().as_vec2();
-        let render_scale = window.render_scale();
-        let inverted_render_scale = 1.0 / render_scale;
-        self.camera.resize(window_size);
-        self.camera.set_scale(Vec2::splat(render_scale));
-        // extra positioning math to avoid fractional placement with integer scaling
-        self.camera
-            .set_position((window_size * 0.5 * inverted_render_scale).extend(0.0));
-
         // render to window
+        self.presenter.update_camera(game_io.window());
+
         let buffer_aquire_start = Instant::now();
         let mut buffer_aquire_end = buffer_aquire_start;
-        let window = game_io.window_mut();
 
-        if let Some(target) = window.acquire_render_target() {
+        if let Some(target) = game_io.window_mut().acquire_render_target() {
             buffer_aquire_end = Instant::now();
 
             let mut render_pass = RenderPass::new(&mut encoder, &target);
 
-            // render as a sprite
-            self.render_sprite
-                .set_texture(self.render_target.texture().clone());
-            self.render_sprite.set_origin(Vec2::ZERO);
-            // extra positioning math to avoid fractional placement with integer scaling
-            self.render_sprite
-                .set_position(window.render_offset() * inverted_render_scale);
-
-            let uniforms = [self.camera.as_binding()];
-            let mut sprite_queue = SpriteQueue::new_with_default_pipeline(game_io, uniforms);
-            sprite_queue.draw_sprite(&self.render_sprite);
-
-            render_pass.consume_queue(sprite_queue);
+            self.presenter
+                .draw(game_io, &mut render_pass, &self.render_target);
 
             for overlay in &mut self.window_overlays {
                 overlay.draw(game_io, &mut render_pass);
@@ -322,6 +503,134 @@ impl GameRuntimeCore {
             queue.submit([encoder.finish()]);
 
             game_io.window_mut().present_frame(target);
+        } else {
+            // submit offscreen work even when the frame can't be presented
+            let queue = game_io.graphics().queue();
+            queue.submit([encoder.finish()]);
+        }
+
+        buffer_aquire_end - buffer_aquire_start
+    }
+
+    pub fn tick(&mut self) {
+        let mut closed_requests = Vec::new();
+
+        for request in self.game_io.runtime_requests.drain(..) {
+            match request {
+                GameRuntimeRequest::Service(service) => self.services.push(service),
+                GameRuntimeRequest::RenderOverlay(overlay) => self.render_overlays.push(overlay),
+                GameRuntimeRequest::WindowOverlay(overlay) => self.window_overlays.push(overlay),
+                GameRuntimeRequest::PostProcess(t, post_process) => {
+                    self.post_processes.push((t, post_process))
+                }
+                GameRuntimeRequest::OpenWindow(id, config, content) => {
+                    self.window_requests.push(WindowRequest { id, config });
+                    self.pending_windows.push((id, Some(content)));
+                }
+                GameRuntimeRequest::CloseWindow(id) => closed_requests.push(id),
+            }
+        }
+
+        for id in closed_requests {
+            self.close_window(id);
+        }
+
+        if self.frame_end.elapsed() < self.game_io.target_sleep_duration() {
+            // running too fast skip tick (this issue should only occur on web)
+            return;
+        }
+
+        // recovers from device loss before updates, so scenes can rebuild resources first
+        let drawing = self.drawing_enabled();
+
+        let start_instant = Instant::now();
+        let game_io = &mut self.game_io;
+        game_io.set_frame_start_instant(start_instant);
+
+        // update the previous timing with new info before updates start
+        let lost_duration = start_instant - self.frame_end - game_io.target_sleep_duration();
+        game_io.set_lost_duration(lost_duration);
+
+        // queue a new task
+        let mut events = Vec::new();
+        std::mem::swap(&mut events, &mut self.event_buffer);
+
+        if let Some(persistence) = &mut self.window_state_persistence {
+            persistence.dirty |= events.iter().any(|event| {
+                matches!(
+                    event,
+                    GameWindowEvent::Moved(_) | GameWindowEvent::Resized(_)
+                )
+            });
+        }
+
+        // update
+        game_io.handle_tasks();
+        game_io.handle_events(events);
+
+        // pre_updates
+        for service in &mut self.services {
+            service.pre_update(game_io);
+        }
+
+        for overlay in &mut self.window_overlays {
+            overlay.pre_update(game_io);
+        }
+
+        for overlay in &mut self.render_overlays {
+            overlay.pre_update(game_io);
+        }
+
+        // scene update
+        self.scene_manager.update(game_io);
+
+        // post_updates
+        for overlay in &mut self.render_overlays {
+            overlay.post_update(game_io);
+        }
+
+        for (id, post_process) in &mut self.post_processes {
+            if !game_io.internal_is_post_process_enabled(*id) {
+                continue;
+            }
+
+            post_process.update(game_io);
+        }
+
+        for overlay in &mut self.window_overlays {
+            overlay.post_update(game_io);
+        }
+
+        for service in &mut self.services {
+            service.post_update(game_io);
+        }
+
+        // kick off new tasks
+        game_io.handle_tasks();
+
+        if let Some(persistence) = &mut self.window_state_persistence {
+            persistence.update(game_io);
+        }
+
+        let update_instant = Instant::now();
+
+        // draw
+        let mut buffer_aquire_duration = Duration::ZERO;
+
+        if drawing {
+            buffer_aquire_duration = self.draw();
+        }
+
+        let game_io = &mut self.game_io;
+
+        // secondary windows
+        for (_, window) in &mut self.secondary_windows {
+            window.tick(game_io, drawing);
+        }
+
+        if drawing {
+            // everything for this frame is submitted, buffers and bind groups can be recycled
+            game_io.graphics().end_frame();
         }
 
         let end_instant = Instant::now();
@@ -331,9 +640,20 @@ impl GameRuntimeCore {
         game_io.set_update_duration(update_instant - start_instant);
         game_io.set_draw_duration(draw_duration);
         game_io.set_frame_duration(end_instant - start_instant);
-        game_io.set_buffer_aquire_duration(buffer_aquire_end - buffer_aquire_start);
+        game_io.set_buffer_aquire_duration(buffer_aquire_duration);
         game_io.update_sleep_duration();
 
         self.frame_end = end_instant;
     }
 }
+
+impl Drop for GameRuntimeCore {
+    fn drop(&mut self) {
+        // catch exits that skip the final tick, such as the window closing
+        if let Some(persistence) = &mut self.window_state_persistence {
+            if persistence.dirty {
+                persistence.save(self.game_io.window());
+            }
+        }
+    }
+}
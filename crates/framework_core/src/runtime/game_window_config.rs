@@ -1,3 +1,4 @@
+use super::WindowState;
 use math::UVec2;
 
 pub struct GameWindowConfig<PlatformApp> {
@@ -10,6 +11,8 @@ pub struct GameWindowConfig<PlatformApp> {
     pub resizable: bool,
     pub always_on_top: bool,
     pub transparent: bool,
+    /// State restored by `Game::with_window_state_persistence`, backends should validate it against the monitor layout
+    pub window_state: Option<WindowState>,
     pub platform_app: Option<PlatformApp>,
 }
 
@@ -25,6 +28,7 @@ impl<T> GameWindowConfig<T> {
             resizable: false,
             always_on_top: false,
             transparent: false,
+            window_state: None,
             platform_app: None,
         }
     }
@@ -6,6 +6,9 @@ pub enum GameWindowEvent {
     CloseRequested,
     Resized(UVec2),
     Moved(IVec2),
+    ScaleFactorChanged(f32),
+    /// Sent by backends that can detect the window switching monitors without moving
+    MonitorChanged,
     InputEvent(InputEvent),
 }
 
@@ -1,10 +1,22 @@
-use crate::common::GameWindow;
-use crate::graphics::{HasGraphicsContext, RenderTarget};
+use crate::common::{default_clipboard, ClipboardBackend, GameWindow};
+use crate::graphics::{GraphicsContext, HasGraphicsContext, RenderTarget};
 use math::*;
+use std::path::PathBuf;
 
 pub trait GameWindowLifecycle: GameWindow + HasGraphicsContext {
     fn rebuild_surface(&mut self);
 
+    /// Called by the runtime after the device is lost, replaces the graphics context with a new one
+    fn recreate_graphics(&mut self) -> anyhow::Result<()>;
+
+    /// When false, the runtime stops drawing after the device is lost instead of calling `recreate_graphics()`
+    fn supports_graphics_recreation(&self) -> bool {
+        true
+    }
+
+    /// Called by the runtime with the context recreated by the main window, for windows sharing its device
+    fn replace_graphics(&mut self, graphics: GraphicsContext);
+
     fn acquire_render_target(&mut self) -> Option<RenderTarget>;
 
     fn present_frame(&mut self, render_target: RenderTarget);
@@ -20,4 +32,14 @@ pub trait GameWindowLifecycle: GameWindow + HasGraphicsContext {
 
     /// Relative to the render. Top left is (-1.0, 1.0), bottom right is (1.0, -1.0)
     fn set_ime_cursor_area(&mut self, area: Rect);
+
+    /// Overrides the default Storage directory, for platforms that provide an app specific folder
+    fn storage_directory(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Overrides the default clipboard, for platforms that require the app or window to access it
+    fn create_clipboard(&self) -> Box<dyn ClipboardBackend> {
+        default_clipboard()
+    }
 }
@@ -37,6 +37,11 @@ impl HeadlessGameLoop {
 
         while !game_runtime.quitting() {
             game_runtime.tick();
+
+            for request in game_runtime.take_window_requests() {
+                game_runtime.reject_window(request.id);
+            }
+
             game_runtime.sleep().await;
         }
 
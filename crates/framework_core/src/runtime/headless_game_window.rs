@@ -44,6 +44,17 @@ impl HasGraphicsContext for HeadlessGameWindow {
 impl GameWindowLifecycle for HeadlessGameWindow {
     fn rebuild_surface(&mut self) {}
 
+    fn recreate_graphics(&mut self) -> anyhow::Result<()> {
+        let graphics = crate::async_task::block_on(self.graphics.recreate(None))?;
+        self.replace_graphics(graphics);
+        Ok(())
+    }
+
+    fn replace_graphics(&mut self, graphics: GraphicsContext) {
+        self.render_target = Some(RenderTarget::new(&graphics, self.resolution));
+        self.graphics = graphics;
+    }
+
     fn acquire_render_target(&mut self) -> Option<RenderTarget> {
         self.render_target.take()
     }
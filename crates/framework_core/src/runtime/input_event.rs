@@ -12,13 +12,20 @@ pub enum InputEvent {
     DroppedText(String),
     Touch(Touch),
     MouseMoved(Vec2),
+    MouseWheel(MouseWheelDelta),
+    /// Raw relative motion in device units, unaffected by acceleration or cursor confinement. Positive y is up
+    MouseMotion(Vec2),
     MouseButtonDown(MouseButton),
     MouseButtonUp(MouseButton),
-    KeyDown(Key),
-    KeyUp(Key),
+    KeyDown(KeyInput),
+    KeyUp(KeyInput),
+    /// Connecting an existing id updates the controller's info, rumble pack, and device
     ControllerConnected {
         controller_id: usize,
+        info: ControllerInfo,
         rumble_pack: Box<dyn RumblePack>,
+        /// None for backends that can't query device state
+        device: Option<Box<dyn ControllerDevice>>,
     },
     ControllerDisconnected(usize),
     ControllerButtonDown {
@@ -6,8 +6,13 @@ mod game_window_loop;
 mod headless_game_loop;
 mod headless_game_window;
 mod input_event;
+mod secondary_window;
+mod window_presenter;
+mod window_state;
 
 use headless_game_window::*;
+use secondary_window::*;
+use window_presenter::*;
 
 pub use game_runtime_core::*;
 pub use game_window_config::*;
@@ -16,3 +21,4 @@ pub use game_window_lifecycle::*;
 pub use game_window_loop::*;
 pub use headless_game_loop::*;
 pub use input_event::*;
+pub use window_state::*;
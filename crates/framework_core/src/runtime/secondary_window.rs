@@ -0,0 +1,173 @@
+use super::WindowPresenter;
+use crate::common::*;
+use crate::graphics::*;
+use crate::runtime::*;
+
+struct SceneContent {
+    scene_manager: SceneManager,
+    render_target: RenderTarget,
+    render_target_b: RenderTarget,
+    presenter: WindowPresenter,
+}
+
+enum SecondaryContent {
+    Scene(Box<SceneContent>),
+    Overlay(Box<dyn GameOverlay>),
+}
+
+/// A window opened through `GameIO::open_window()`
+pub(crate) struct SecondaryWindow {
+    context: WindowContext,
+    content: SecondaryContent,
+    event_buffer: Vec<GameWindowEvent>,
+}
+
+impl SecondaryWindow {
+    pub(crate) fn new(
+        game_io: &mut GameIO,
+        mut context: WindowContext,
+        content: WindowContent,
+    ) -> Self {
+        game_io.swap_window_context(&mut context);
+
+        let content = match content {
+            WindowContent::Scene(constructor) => {
+                let initial_scene = constructor(game_io);
+                let resolution = game_io.window().resolution();
+
+                let render_target = RenderTarget::new(game_io, resolution);
+                let render_target_b = RenderTarget::new(game_io, resolution);
+                let presenter = WindowPresenter::new(game_io, &render_target);
+
+                SecondaryContent::Scene(Box::new(SceneContent {
+                    scene_manager: SceneManager::new(game_io, initial_scene),
+                    render_target,
+                    render_target_b,
+                    presenter,
+                }))
+            }
+            WindowContent::Overlay(constructor) => SecondaryContent::Overlay(constructor(game_io)),
+        };
+
+        game_io.swap_window_context(&mut context);
+
+        Self {
+            context,
+            content,
+            event_buffer: Vec::new(),
+        }
+    }
+
+    pub(crate) fn window(&self) -> &dyn GameWindowLifecycle {
+        self.context.window()
+    }
+
+    /// Switches to the context recreated by the main window and rebuilds window resources
+    pub(crate) fn graphics_recreated(&mut self, game_io: &mut GameIO, graphics: GraphicsContext) {
+        game_io.swap_window_context(&mut self.context);
+        game_io.window_mut().replace_graphics(graphics);
+
+        match &mut self.content {
+            SecondaryContent::Scene(content) => {
+                let resolution = game_io.window().resolution();
+
+                content.render_target = RenderTarget::new(game_io, resolution);
+                content.render_target_b = RenderTarget::new(game_io, resolution);
+                content.presenter = WindowPresenter::new(game_io, &content.render_target);
+                content.scene_manager.graphics_recreated(game_io);
+            }
+            SecondaryContent::Overlay(overlay) => overlay.graphics_recreated(game_io),
+        }
+
+        game_io.swap_window_context(&mut self.context);
+    }
+
+    pub(crate) fn push_event(&mut self, event: GameWindowEvent) {
+        self.event_buffer.push(event);
+    }
+
+    /// Drawing is skipped while the graphics device is lost
+    pub(crate) fn tick(&mut self, game_io: &mut GameIO, drawing: bool) {
+        game_io.swap_window_context(&mut self.context);
+
+        let events = std::mem::take(&mut self.event_buffer);
+        game_io.handle_events(events);
+
+        // update
+        match &mut self.content {
+            SecondaryContent::Scene(content) => content.scene_manager.update(game_io),
+            SecondaryContent::Overlay(overlay) => {
+                overlay.pre_update(game_io);
+                overlay.post_update(game_io);
+            }
+        }
+
+        // draw, skipped if the window was closed during the update
+        if drawing && game_io.is_window_open(game_io.window_id()) {
+            self.draw(game_io);
+        }
+
+        game_io.swap_window_context(&mut self.context);
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO) {
+        let device = game_io.graphics().device();
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("secondary_window_command_encoder"),
+        });
+
+        let window = game_io.window();
+        let clear_color = window.clear_color();
+
+        if let SecondaryContent::Scene(content) = &mut self.content {
+            let SceneContent {
+                scene_manager,
+                render_target,
+                render_target_b,
+                presenter,
+            } = &mut **content;
+
+            let resolution = window.resolution();
+
+            render_target.resize(game_io, resolution);
+            render_target_b.resize(game_io, resolution);
+
+            render_target.set_clear_color(clear_color);
+            render_target_b.set_clear_color(clear_color);
+
+            scene_manager.draw(game_io, &mut encoder, render_target, render_target_b);
+
+            presenter.update_camera(game_io.window());
+        }
+
+        let Some(mut target) = game_io.window_mut().acquire_render_target() else {
+            // submit offscreen work even when the frame can't be presented
+            let queue = game_io.graphics().queue();
+            queue.submit([encoder.finish()]);
+            return;
+        };
+
+        match &mut self.content {
+            SecondaryContent::Scene(content) => {
+                let mut render_pass = RenderPass::new(&mut encoder, &target);
+                content
+                    .presenter
+                    .draw(game_io, &mut render_pass, &content.render_target);
+                render_pass.flush();
+            }
+            SecondaryContent::Overlay(overlay) => {
+                target.set_clear_color(clear_color);
+
+                let mut render_pass = RenderPass::new(&mut encoder, &target);
+                overlay.draw(game_io, &mut render_pass);
+                render_pass.flush();
+            }
+        }
+
+        let queue = game_io.graphics().queue();
+        queue.submit([encoder.finish()]);
+
+        game_io.window_mut().present_frame(target);
+    }
+}
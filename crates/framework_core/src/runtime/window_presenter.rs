@@ -0,0 +1,55 @@
+use crate::common::*;
+use crate::graphics::*;
+use math::Vec2;
+
+/// Scales a render to fit the window
+pub(crate) struct WindowPresenter {
+    render_sprite: Sprite,
+    camera: OrthoCamera,
+}
+
+impl WindowPresenter {
+    pub(crate) fn new(game_io: &GameIO, render_target: &RenderTarget) -> Self {
+        let window_size = game_io.window().size().as_vec2();
+
+        Self {
+            render_sprite: Sprite::new(game_io, render_target.texture().clone()),
+            camera: OrthoCamera::new(game_io, window_size),
+        }
+    }
+
+    pub(crate) fn update_camera(&mut self, window: &dyn GameWindow) {
+        let window_size = window.size().as_vec2();
+        let render_scale = window.render_scale();
+        let inverted_render_scale = 1.0 / render_scale;
+        self.camera.resize(window_size);
+        self.camera.set_scale(Vec2::splat(render_scale));
+        // extra positioning math to avoid fractional placement with integer scaling
+        self.camera
+            .set_position((window_size * 0.5 * inverted_render_scale).extend(0.0));
+    }
+
+    /// Draws the render as a sprite, expects `update_camera()` to be called first
+    pub(crate) fn draw(
+        &mut self,
+        game_io: &GameIO,
+        render_pass: &mut RenderPass,
+        render_target: &RenderTarget,
+    ) {
+        let window = game_io.window();
+        let inverted_render_scale = 1.0 / window.render_scale();
+
+        self.render_sprite
+            .set_texture(render_target.texture().clone());
+        self.render_sprite.set_origin(Vec2::ZERO);
+        // extra positioning math to avoid fractional placement with integer scaling
+        self.render_sprite
+            .set_position(window.render_offset() * inverted_render_scale);
+
+        let uniforms = [self.camera.as_binding()];
+        let mut sprite_queue = SpriteQueue::new_with_default_pipeline(game_io, uniforms);
+        sprite_queue.draw_sprite(&self.render_sprite);
+
+        render_pass.consume_queue(sprite_queue);
+    }
+}
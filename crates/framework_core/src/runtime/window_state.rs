@@ -0,0 +1,153 @@
+use crate::common::GameWindow;
+use crate::storage::SettingsMap;
+use cfg_macros::*;
+use math::*;
+use std::path::Path;
+
+/// Window placement saved by `Game::with_window_state_persistence`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowState {
+    /// None to let the platform place the window
+    pub position: Option<IVec2>,
+    pub size: UVec2,
+    pub fullscreen: bool,
+}
+
+impl WindowState {
+    /// Returns None if the file is missing or invalid
+    pub fn load(path: &Path) -> Option<Self> {
+        let text = std::fs::read_to_string(path).ok()?;
+        let map = SettingsMap::parse(&text);
+
+        let position = map
+            .get("x")
+            .zip(map.get("y"))
+            .map(|(x, y)| IVec2::new(x, y));
+
+        let size = UVec2::new(map.get("width")?, map.get("height")?);
+
+        if size.x == 0 || size.y == 0 {
+            return None;
+        }
+
+        Some(Self {
+            position,
+            size,
+            fullscreen: map.get_or("fullscreen", false),
+        })
+    }
+
+    /// Writes atomically, does nothing on web
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let mut map = SettingsMap::new();
+
+        if let Some(position) = self.position {
+            map.set("x", position.x);
+            map.set("y", position.y);
+        }
+
+        map.set("width", self.size.x);
+        map.set("height", self.size.y);
+        map.set("fullscreen", self.fullscreen);
+
+        cfg_web! {
+            {
+                let _ = (path, map);
+                Ok(())
+            }
+        }
+        cfg_native! {
+            crate::storage::write_file_atomically(path, map.to_string().as_bytes())
+        }
+    }
+
+    /// Updates the state to match the window.
+    /// Position and size are left untouched while fullscreen to remember the windowed placement.
+    pub fn capture(&mut self, window: &dyn GameWindow) {
+        self.fullscreen = window.fullscreen();
+
+        // minimized windows report a size of zero
+        if !self.fullscreen && window.size().min_element() > 0 {
+            self.position = Some(window.position());
+            self.size = window.size();
+        }
+    }
+
+    /// Adjusts the state to fit the monitor it overlaps the most, shrinking the window if necessary.
+    /// The position is dropped if the window is no longer on any monitor.
+    pub fn validate(mut self, monitors: &[IRect]) -> Self {
+        if monitors.is_empty() {
+            return self;
+        }
+
+        let overlap = |monitor: &IRect, position: IVec2| {
+            let window_rect = IRect::new(
+                position.x,
+                position.y,
+                self.size.x as i32,
+                self.size.y as i32,
+            );
+            let intersection = window_rect.scissor(*monitor);
+
+            intersection.width as i64 * intersection.height as i64
+        };
+
+        let target_monitor = self.position.and_then(|position| {
+            monitors
+                .iter()
+                .map(|monitor| (monitor, overlap(monitor, position)))
+                .filter(|(_, area)| *area > 0)
+                .max_by_key(|(_, area)| *area)
+                .map(|(monitor, _)| *monitor)
+        });
+
+        let Some(monitor) = target_monitor else {
+            // fit to the primary monitor and let the platform place the window
+            let monitor_size = monitors[0].size().max(IVec2::ONE).as_uvec2();
+            self.position = None;
+            self.size = self.size.min(monitor_size);
+            return self;
+        };
+
+        self.size = self.size.min(monitor.size().max(IVec2::ONE).as_uvec2());
+
+        if let Some(position) = &mut self.position {
+            let size = self.size.as_ivec2();
+            position.x = position.x.clamp(monitor.left(), monitor.right() - size.x);
+            position.y = position.y.clamp(monitor.top(), monitor.bottom() - size.y);
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate_against_monitors() {
+        let monitors = [IRect::new(0, 0, 1920, 1080), IRect::new(1920, 0, 1280, 720)];
+
+        let state = WindowState {
+            position: Some(IVec2::new(3000, 600)),
+            size: UVec2::new(800, 600),
+            fullscreen: false,
+        };
+
+        // mostly on the second monitor, pulled back inside
+        let validated = state.validate(&monitors);
+        assert_eq!(validated.position, Some(IVec2::new(2400, 120)));
+        assert_eq!(validated.size, UVec2::new(800, 600));
+
+        // monitor was disconnected
+        let validated = WindowState {
+            size: UVec2::new(2560, 1440),
+            ..state
+        }
+        .validate(&monitors[..1]);
+
+        assert_eq!(validated.position, None);
+        assert_eq!(validated.size, UVec2::new(1920, 1080));
+    }
+}
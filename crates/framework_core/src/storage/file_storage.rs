@@ -0,0 +1,136 @@
+use super::*;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// `~` can't appear in keys, so temporary files never collide with stored data
+const TEMP_SUFFIX: &str = ".~tmp";
+
+/// Stores each key as a file under a root directory, used on desktop and Android
+pub struct FileStorage {
+    root: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Resolves the per user data directory for the app:
+    ///
+    /// - Windows: `%APPDATA%\<app_id>`
+    /// - macOS: `~/Library/Application Support/<app_id>`
+    /// - Others: `$XDG_DATA_HOME/<app_id>` or `~/.local/share/<app_id>`
+    ///
+    /// Falls back to a folder in the working directory if the environment is missing these variables.
+    pub fn default_app_directory(app_id: &str) -> PathBuf {
+        let env_path = |name: &str| {
+            std::env::var_os(name)
+                .filter(|value| !value.is_empty())
+                .map(PathBuf::from)
+        };
+
+        let base = if cfg!(target_os = "windows") {
+            env_path("APPDATA")
+        } else if cfg!(target_os = "macos") {
+            env_path("HOME").map(|home| home.join("Library").join("Application Support"))
+        } else {
+            env_path("XDG_DATA_HOME")
+                .or_else(|| env_path("HOME").map(|home| home.join(".local").join("share")))
+        };
+
+        base.unwrap_or_default().join(app_id)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path_for(&self, key: &str) -> PathBuf {
+        let mut path = self.root.clone();
+        path.extend(key.split('/'));
+        path
+    }
+
+    fn collect_keys(&self, directory: &Path, prefix: &str, keys: &mut Vec<String>) {
+        let Ok(entries) = fs::read_dir(directory) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+
+            if name.ends_with(TEMP_SUFFIX) {
+                continue;
+            }
+
+            let key = format!("{prefix}{name}");
+            let path = entry.path();
+
+            if path.is_dir() {
+                self.collect_keys(&path, &format!("{key}/"), keys);
+            } else {
+                keys.push(key);
+            }
+        }
+    }
+}
+
+impl StorageBackend for FileStorage {
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        match fs::read(self.path_for(key)) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn write(&mut self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+        let path = self.path_for(key);
+
+        Ok(write_file_atomically(&path, data)?)
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StorageError> {
+        match fs::remove_file(self.path_for(key)) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        let mut keys = Vec::new();
+        self.collect_keys(&self.root, "", &mut keys);
+        keys.sort();
+
+        Ok(keys)
+    }
+}
+
+/// Writes to a temporary file and swaps it in, to avoid leaving partial data behind.
+/// Parent directories are created as needed.
+pub(crate) fn write_file_atomically(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut temp_path = path.to_path_buf().into_os_string();
+    temp_path.push(TEMP_SUFFIX);
+    let temp_path = PathBuf::from(temp_path);
+
+    let result = (|| {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    result
+}
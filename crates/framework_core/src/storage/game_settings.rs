@@ -0,0 +1,95 @@
+use super::*;
+use crate::common::GameWindow;
+use math::UVec2;
+
+/// Common settings for games, stored under the `settings` key.
+///
+/// Games with additional settings can implement `VersionedSettings` on their own struct.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameSettings {
+    /// None to use the size provided to Game::new
+    pub window_size: Option<UVec2>,
+    pub fullscreen: bool,
+    pub vsync: bool,
+    /// Range [0.0, 1.0]
+    pub master_volume: f32,
+    /// Range [0.0, 1.0]
+    pub music_volume: f32,
+    /// Range [0.0, 1.0]
+    pub sfx_volume: f32,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        Self {
+            window_size: None,
+            fullscreen: false,
+            vsync: true,
+            master_volume: 1.0,
+            music_volume: 1.0,
+            sfx_volume: 1.0,
+        }
+    }
+}
+
+impl GameSettings {
+    /// Copies window size, fullscreen, and vsync from the window
+    pub fn capture_window(&mut self, window: &dyn GameWindow) {
+        self.fullscreen = window.fullscreen();
+        self.vsync = window.vsync_enabled();
+
+        if !self.fullscreen {
+            self.window_size = Some(window.size());
+        }
+    }
+
+    pub fn apply_to_window(&self, window: &mut dyn GameWindow) {
+        if let Some(size) = self.window_size {
+            window.request_size(size);
+        }
+
+        window.set_fullscreen(self.fullscreen);
+        window.set_vsync_enabled(self.vsync);
+    }
+}
+
+impl VersionedSettings for GameSettings {
+    const KEY: &'static str = "settings";
+    const VERSION: u32 = 1;
+
+    fn from_map(map: &SettingsMap) -> Self {
+        let default = Self::default();
+
+        let window_size = map
+            .get("window_width")
+            .zip(map.get("window_height"))
+            .map(|(width, height)| UVec2::new(width, height))
+            .filter(|size| size.x > 0 && size.y > 0);
+
+        Self {
+            window_size,
+            fullscreen: map.get_or("fullscreen", default.fullscreen),
+            vsync: map.get_or("vsync", default.vsync),
+            master_volume: map
+                .get_or("master_volume", default.master_volume)
+                .clamp(0.0, 1.0),
+            music_volume: map
+                .get_or("music_volume", default.music_volume)
+                .clamp(0.0, 1.0),
+            sfx_volume: map.get_or("sfx_volume", default.sfx_volume).clamp(0.0, 1.0),
+        }
+    }
+
+    fn to_map(&self, map: &mut SettingsMap) {
+        if let Some(size) = self.window_size {
+            map.set("window_width", size.x);
+            map.set("window_height", size.y);
+        }
+
+        map.set("fullscreen", self.fullscreen);
+        map.set("vsync", self.vsync);
+        map.set("master_volume", self.master_volume);
+        map.set("music_volume", self.music_volume);
+        map.set("sfx_volume", self.sfx_volume);
+    }
+}
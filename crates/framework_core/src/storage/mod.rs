@@ -0,0 +1,27 @@
+use cfg_macros::*;
+
+mod game_settings;
+mod memory_storage;
+mod settings_map;
+mod storage_backend;
+mod storage_error;
+mod storage_service;
+
+pub use game_settings::*;
+pub use memory_storage::*;
+pub use settings_map::*;
+pub use storage_backend::*;
+pub use storage_error::*;
+pub use storage_service::*;
+
+cfg_native! {
+  mod file_storage;
+
+  pub use file_storage::*;
+}
+
+cfg_web! {
+  mod web_storage;
+
+  pub use web_storage::*;
+}
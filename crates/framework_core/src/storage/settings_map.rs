@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// Text based `key=value` map used to persist settings, one entry per line
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SettingsMap {
+    values: BTreeMap<String, String>,
+}
+
+impl SettingsMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lines without `=` are ignored
+    pub fn parse(text: &str) -> Self {
+        let values = text
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+
+        Self { values }
+    }
+
+    pub fn get<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.values.get(key)?.parse().ok()
+    }
+
+    /// Returns the parsed value, or the fallback if the key is missing or fails to parse
+    pub fn get_or<T: FromStr>(&self, key: &str, fallback: T) -> T {
+        self.get(key).unwrap_or(fallback)
+    }
+
+    /// Newlines in keys and values are replaced with spaces, and `=` in keys are replaced with `_`
+    pub fn set(&mut self, key: &str, value: impl ToString) {
+        let key = key.replace(['\n', '\r'], " ").replace('=', "_");
+        let value = value.to_string().replace(['\n', '\r'], " ");
+
+        self.values.insert(key, value);
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.values.remove(key);
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+}
+
+impl std::fmt::Display for SettingsMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (key, value) in &self.values {
+            writeln!(f, "{key}={value}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Settings stored with a version number, allowing older saves to be migrated.
+///
+/// Loaded through `Storage::load_settings` and saved through `Storage::save_settings`.
+pub trait VersionedSettings: Default {
+    /// The storage key
+    const KEY: &'static str;
+    const VERSION: u32;
+
+    /// Called before `from_map` when loading settings saved with an older version
+    fn migrate(_map: &mut SettingsMap, _from_version: u32) {}
+
+    /// Missing values should fall back to defaults
+    fn from_map(map: &SettingsMap) -> Self;
+
+    fn to_map(&self, map: &mut SettingsMap);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut map = SettingsMap::new();
+        map.set("volume", 0.5);
+        map.set("fullscreen", true);
+        map.set("name", "line\nbreak");
+
+        let parsed = SettingsMap::parse(&map.to_string());
+
+        assert_eq!(parsed, map);
+        assert_eq!(parsed.get::<f32>("volume"), Some(0.5));
+        assert_eq!(parsed.get::<bool>("fullscreen"), Some(true));
+        assert_eq!(parsed.get::<String>("name").as_deref(), Some("line break"));
+        assert_eq!(parsed.get_or("missing", 3), 3);
+    }
+}
@@ -0,0 +1,32 @@
+use super::StorageError;
+
+/// Platform specific persistence used by Storage.
+///
+/// Keys are validated by Storage before reaching the backend.
+pub trait StorageBackend {
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError>;
+
+    /// Writes must be atomic, a failed write should leave the previous data intact
+    fn write(&mut self, key: &str, data: &[u8]) -> Result<(), StorageError>;
+
+    fn remove(&mut self, key: &str) -> Result<(), StorageError>;
+
+    fn keys(&self) -> Result<Vec<String>, StorageError>;
+}
+
+pub(super) fn validate_key(key: &str) -> Result<(), StorageError> {
+    let valid_segment = |segment: &str| {
+        !segment.is_empty()
+            && segment != "."
+            && segment != ".."
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+
+    if key.split('/').all(valid_segment) {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey(key.to_string()))
+    }
+}
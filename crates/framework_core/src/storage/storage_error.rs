@@ -0,0 +1,35 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum StorageError {
+    /// Keys are made of `/` separated segments using ASCII letters, digits, `-`, `_`, and `.`
+    InvalidKey(String),
+    /// The platform refused access to storage, such as a browser blocking localStorage
+    Unavailable(String),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::InvalidKey(key) => write!(f, "Invalid storage key: {key:?}"),
+            StorageError::Unavailable(reason) => write!(f, "Storage unavailable: {reason}"),
+            StorageError::Io(err) => write!(f, "Storage IO error: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StorageError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for StorageError {
+    fn from(err: std::io::Error) -> Self {
+        StorageError::Io(err)
+    }
+}
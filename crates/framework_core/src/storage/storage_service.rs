@@ -0,0 +1,138 @@
+use super::*;
+use logging::log;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+const SETTINGS_VERSION_KEY: &str = "settings_version";
+
+/// Persistent key/value and blob storage, accessed through `GameIO::storage()`
+pub struct Storage {
+    backend: Box<dyn StorageBackend>,
+}
+
+impl Storage {
+    pub fn new(backend: impl StorageBackend + 'static) -> Self {
+        Self {
+            backend: Box::new(backend),
+        }
+    }
+
+    /// Uses the platform's default storage for the app, or the directory if provided on native platforms
+    pub fn new_for_app(app_id: &str, directory: Option<PathBuf>) -> Self {
+        let app_id: String = app_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+
+        cfg_web! {
+            {
+                // browsers don't expose directories
+                let _ = directory;
+
+                match WebStorage::new(&app_id) {
+                    Ok(web_storage) => Self::new(web_storage),
+                    Err(err) => {
+                        log::error!("{err}, falling back to memory storage");
+                        Self::new(MemoryStorage::new())
+                    }
+                }
+            }
+        }
+        cfg_native! {
+            Self::new(FileStorage::new(
+                directory.unwrap_or_else(|| FileStorage::default_app_directory(&app_id)),
+            ))
+        }
+    }
+
+    pub fn blob(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        validate_key(key)?;
+        self.backend.read(key)
+    }
+
+    /// Replaces the stored data atomically
+    pub fn set_blob(&mut self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+        validate_key(key)?;
+        self.backend.write(key, data)
+    }
+
+    /// Returns None if the key is missing or the data is not UTF-8, errors are logged
+    pub fn string(&self, key: &str) -> Option<String> {
+        match self.blob(key) {
+            Ok(data) => String::from_utf8(data?).ok(),
+            Err(err) => {
+                log::error!("{err}");
+                None
+            }
+        }
+    }
+
+    pub fn set_string(&mut self, key: &str, value: &str) -> Result<(), StorageError> {
+        self.set_blob(key, value.as_bytes())
+    }
+
+    /// Returns None if the key is missing or the value fails to parse, errors are logged
+    pub fn value<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.string(key)?.parse().ok()
+    }
+
+    pub fn set_value(&mut self, key: &str, value: impl ToString) -> Result<(), StorageError> {
+        self.set_string(key, &value.to_string())
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        matches!(self.blob(key), Ok(Some(_)))
+    }
+
+    pub fn remove(&mut self, key: &str) -> Result<(), StorageError> {
+        validate_key(key)?;
+        self.backend.remove(key)
+    }
+
+    pub fn keys(&self) -> Result<Vec<String>, StorageError> {
+        self.backend.keys()
+    }
+
+    /// Returns defaults if the settings are missing, unreadable, or saved by a newer version
+    pub fn load_settings<S: VersionedSettings>(&self) -> S {
+        let Some(text) = self.string(S::KEY) else {
+            return S::default();
+        };
+
+        let mut map = SettingsMap::parse(&text);
+        let version = map.get(SETTINGS_VERSION_KEY).unwrap_or(0);
+        map.remove(SETTINGS_VERSION_KEY);
+
+        if version > S::VERSION {
+            log::warn!(
+                "{:?} was saved with version {version}, expected {} or older, using defaults",
+                S::KEY,
+                S::VERSION
+            );
+            return S::default();
+        }
+
+        if version < S::VERSION {
+            S::migrate(&mut map, version);
+        }
+
+        S::from_map(&map)
+    }
+
+    pub fn save_settings<S: VersionedSettings>(
+        &mut self,
+        settings: &S,
+    ) -> Result<(), StorageError> {
+        let mut map = SettingsMap::new();
+        settings.to_map(&mut map);
+        map.set(SETTINGS_VERSION_KEY, S::VERSION);
+
+        self.set_string(S::KEY, &map.to_string())
+    }
+}
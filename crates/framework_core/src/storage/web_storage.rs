@@ -0,0 +1,114 @@
+use super::*;
+use wasm_forward::web_sys;
+
+/// Stores data in the browser's localStorage, with keys prefixed by the app id.
+///
+/// localStorage only holds strings, so blobs are base64 encoded.
+pub struct WebStorage {
+    local_storage: web_sys::Storage,
+    prefix: String,
+}
+
+impl WebStorage {
+    pub fn new(app_id: &str) -> Result<Self, StorageError> {
+        let window = web_sys::window()
+            .ok_or_else(|| StorageError::Unavailable(String::from("No window")))?;
+
+        let local_storage = window
+            .local_storage()
+            .map_err(|err| StorageError::Unavailable(format!("{err:?}")))?
+            .ok_or_else(|| StorageError::Unavailable(String::from("No localStorage")))?;
+
+        Ok(Self {
+            local_storage,
+            prefix: format!("{app_id}/"),
+        })
+    }
+}
+
+impl StorageBackend for WebStorage {
+    fn read(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        let value = self
+            .local_storage
+            .get_item(&format!("{}{key}", self.prefix))
+            .map_err(|err| StorageError::Unavailable(format!("{err:?}")))?;
+
+        Ok(value.and_then(|value| base64::decode(&value)))
+    }
+
+    fn write(&mut self, key: &str, data: &[u8]) -> Result<(), StorageError> {
+        // setItem either stores the full value or throws, leaving the previous value intact
+        self.local_storage
+            .set_item(&format!("{}{key}", self.prefix), &base64::encode(data))
+            .map_err(|err| StorageError::Unavailable(format!("{err:?}")))
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StorageError> {
+        self.local_storage
+            .remove_item(&format!("{}{key}", self.prefix))
+            .map_err(|err| StorageError::Unavailable(format!("{err:?}")))
+    }
+
+    fn keys(&self) -> Result<Vec<String>, StorageError> {
+        let length = self
+            .local_storage
+            .length()
+            .map_err(|err| StorageError::Unavailable(format!("{err:?}")))?;
+
+        let mut keys: Vec<_> = (0..length)
+            .filter_map(|i| self.local_storage.key(i).ok().flatten())
+            .filter_map(|key| key.strip_prefix(&self.prefix).map(String::from))
+            .collect();
+
+        keys.sort();
+
+        Ok(keys)
+    }
+}
+
+mod base64 {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    pub fn encode(data: &[u8]) -> String {
+        let mut output = String::with_capacity(data.len().div_ceil(3) * 4);
+
+        for chunk in data.chunks(3) {
+            let bytes = [
+                chunk[0],
+                *chunk.get(1).unwrap_or(&0),
+                *chunk.get(2).unwrap_or(&0),
+            ];
+            let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    output.push(ALPHABET[(n >> (18 - i * 6)) as usize & 63] as char);
+                } else {
+                    output.push('=');
+                }
+            }
+        }
+
+        output
+    }
+
+    pub fn decode(text: &str) -> Option<Vec<u8>> {
+        let text = text.trim_end_matches('=');
+        let mut output = Vec::with_capacity(text.len() * 3 / 4);
+        let mut buffer = 0u32;
+        let mut bits = 0;
+
+        for c in text.bytes() {
+            let value = ALPHABET.iter().position(|a| *a == c)? as u32;
+            buffer = (buffer << 6 | value) & 0xFFFF;
+            bits += 6;
+
+            if bits >= 8 {
+                bits -= 8;
+                output.push((buffer >> bits) as u8);
+            }
+        }
+
+        Some(output)
+    }
+}
@@ -0,0 +1,28 @@
+mod ui_button;
+mod ui_event;
+mod ui_focus;
+mod ui_label;
+mod ui_layout;
+mod ui_node;
+mod ui_style;
+mod ui_text_input;
+mod ui_theme;
+mod ui_toggle;
+mod ui_tree;
+mod ui_widget;
+
+pub use ui_button::*;
+pub use ui_event::*;
+pub use ui_focus::UiNavDirection;
+pub use ui_label::*;
+pub use ui_node::UiNodeKey;
+pub use ui_style::*;
+pub use ui_text_input::*;
+pub use ui_theme::*;
+pub use ui_toggle::*;
+pub use ui_tree::*;
+pub use ui_widget::*;
+
+use ui_focus::*;
+use ui_layout::*;
+use ui_node::*;
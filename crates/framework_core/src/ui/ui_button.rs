@@ -0,0 +1,71 @@
+use super::*;
+use math::*;
+
+pub struct UiButton {
+    label: String,
+    enabled: bool,
+}
+
+impl UiButton {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            enabled: true,
+        }
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = label.into();
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+}
+
+impl UiWidget for UiButton {
+    fn measure(&self, theme: &UiTheme) -> Vec2 {
+        theme.measure_padded_text(&self.label)
+    }
+
+    fn focusable(&self) -> bool {
+        self.enabled
+    }
+
+    fn activate(&mut self, context: &mut UiContext) {
+        if self.enabled {
+            context.emit(UiEventKind::Activated);
+        }
+    }
+
+    fn draw(&self, context: &mut UiDrawContext, rect: Rect, state: UiWidgetState) {
+        let theme = context.theme();
+
+        let text_color = if !self.enabled {
+            theme.disabled_text_color
+        } else if state.focused {
+            theme.focused_text_color
+        } else {
+            theme.text_color
+        };
+
+        if let Some(background) = theme.button_background(state.focused, state.pressed) {
+            context.draw_nine_slice(background, rect, crate::graphics::Color::WHITE);
+        }
+
+        context.draw_centered_text(&self.label, rect, text_color);
+    }
+}
@@ -0,0 +1,19 @@
+use super::UiNodeKey;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiEventKind {
+    FocusGained,
+    FocusLost,
+    /// Buttons were clicked, tapped, or confirmed
+    Activated,
+    Toggled(bool),
+    TextChanged,
+    /// Return was pressed while editing a text input
+    TextSubmitted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiEvent {
+    pub node: UiNodeKey,
+    pub kind: UiEventKind,
+}
@@ -0,0 +1,91 @@
+use super::UiNodeKey;
+use math::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiNavDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl UiNavDirection {
+    fn as_vec(self) -> Vec2 {
+        match self {
+            UiNavDirection::Up => Vec2::NEG_Y,
+            UiNavDirection::Down => Vec2::Y,
+            UiNavDirection::Left => Vec2::NEG_X,
+            UiNavDirection::Right => Vec2::X,
+        }
+    }
+}
+
+/// Picks the closest candidate in the direction, favoring candidates that line up with the current rect
+pub(super) fn find_next(
+    candidates: &[(UiNodeKey, Rect)],
+    from: Rect,
+    direction: UiNavDirection,
+) -> Option<UiNodeKey> {
+    let direction_vec = direction.as_vec();
+    let orthogonal_vec = direction_vec.perp().abs();
+
+    candidates
+        .iter()
+        .filter_map(|(key, rect)| {
+            // compare edges facing each other to allow navigation between rects of different sizes
+            let primary = match direction {
+                UiNavDirection::Up => from.top() - rect.bottom(),
+                UiNavDirection::Down => rect.top() - from.bottom(),
+                UiNavDirection::Left => from.left() - rect.right(),
+                UiNavDirection::Right => rect.left() - from.right(),
+            };
+
+            let center_delta = rect.center() - from.center();
+
+            if center_delta.dot(direction_vec) <= 0.0 {
+                return None;
+            }
+
+            let orthogonal = center_delta.dot(orthogonal_vec).abs();
+            let score = primary.max(0.0) + orthogonal * 2.0;
+
+            Some((*key, score))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(key, _)| key)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use slotmap::SlotMap;
+
+    #[test]
+    fn prefers_aligned_candidates() {
+        let mut keys = SlotMap::<UiNodeKey, ()>::with_key();
+        let below = keys.insert(());
+        let diagonal = keys.insert(());
+        let above = keys.insert(());
+
+        let from = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let candidates = [
+            (below, Rect::new(0.0, 30.0, 10.0, 10.0)),
+            (diagonal, Rect::new(20.0, 15.0, 10.0, 10.0)),
+            (above, Rect::new(0.0, -20.0, 10.0, 10.0)),
+        ];
+
+        assert_eq!(
+            find_next(&candidates, from, UiNavDirection::Down),
+            Some(below)
+        );
+        assert_eq!(
+            find_next(&candidates, from, UiNavDirection::Up),
+            Some(above)
+        );
+        assert_eq!(
+            find_next(&candidates, from, UiNavDirection::Right),
+            Some(diagonal)
+        );
+        assert_eq!(find_next(&candidates, from, UiNavDirection::Left), None);
+    }
+}
@@ -0,0 +1,42 @@
+use super::*;
+use crate::graphics::Color;
+use math::*;
+
+pub struct UiLabel {
+    text: String,
+    color: Option<Color>,
+}
+
+impl UiLabel {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            color: None,
+        }
+    }
+
+    /// Overrides the text color from the theme
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+    }
+}
+
+impl UiWidget for UiLabel {
+    fn measure(&self, theme: &UiTheme) -> Vec2 {
+        theme.measure_text(&self.text)
+    }
+
+    fn draw(&self, context: &mut UiDrawContext, rect: Rect, _state: UiWidgetState) {
+        let color = self.color.unwrap_or(context.theme().text_color);
+        context.draw_text(&self.text, rect.position().floor(), color);
+    }
+}
@@ -0,0 +1,340 @@
+use super::*;
+use math::*;
+use slotmap::SlotMap;
+
+pub(super) type UiNodes = SlotMap<UiNodeKey, UiNode>;
+
+/// Resolves the rect of every visible node under the root
+pub(super) fn layout(
+    nodes: &mut UiNodes,
+    root: UiNodeKey,
+    bounds: Rect,
+    measure_widget: &dyn Fn(&dyn UiWidget) -> Vec2,
+) {
+    measure(nodes, root, measure_widget);
+    arrange(nodes, root, bounds);
+}
+
+fn measure(
+    nodes: &mut UiNodes,
+    key: UiNodeKey,
+    measure_widget: &dyn Fn(&dyn UiWidget) -> Vec2,
+) -> Vec2 {
+    let children = nodes[key].children.clone();
+    let mut child_sizes = Vec::with_capacity(children.len());
+
+    for child in children {
+        if nodes[child].style.visible {
+            child_sizes.push(measure(nodes, child, measure_widget));
+        }
+    }
+
+    let node = &mut nodes[key];
+    let style = &node.style;
+    let gap_total = style.gap * child_sizes.len().saturating_sub(1) as f32;
+
+    let mut content = match style.direction {
+        UiDirection::Stack => child_sizes
+            .iter()
+            .fold(Vec2::ZERO, |acc, size| acc.max(*size)),
+        UiDirection::Row => Vec2::new(
+            child_sizes.iter().map(|size| size.x).sum::<f32>() + gap_total,
+            child_sizes.iter().fold(0.0, |acc, size| size.y.max(acc)),
+        ),
+        UiDirection::Column => Vec2::new(
+            child_sizes.iter().fold(0.0, |acc, size| size.x.max(acc)),
+            child_sizes.iter().map(|size| size.y).sum::<f32>() + gap_total,
+        ),
+    };
+
+    if let Some(scroll) = &mut node.scroll {
+        scroll.content_size = content;
+    }
+
+    if let Some(widget) = &node.widget {
+        content = content.max(measure_widget(widget.as_ref()));
+    }
+
+    let padding = style.padding;
+    let padded = content + Vec2::new(padding.horizontal(), padding.vertical());
+
+    let resolve = |length: UiLength, content: f32| match length {
+        UiLength::Px(value) => value,
+        UiLength::Auto | UiLength::Grow(_) => content,
+    };
+
+    node.intrinsic_size = Vec2::new(
+        resolve(style.width, padded.x),
+        resolve(style.height, padded.y),
+    );
+
+    node.intrinsic_size
+}
+
+fn arrange(nodes: &mut UiNodes, key: UiNodeKey, rect: Rect) {
+    nodes[key].rect = rect;
+
+    let node = &mut nodes[key];
+    let mut inner = node.content_rect();
+
+    // scroll views lay out children using the full content size, then shift by the offset
+    if let Some(scroll) = &mut node.scroll {
+        let viewport = inner.size();
+        scroll.offset = scroll.offset.clamp(Vec2::ZERO, scroll.max_offset(viewport));
+
+        let content_size = scroll.content_size.max(viewport);
+        inner = Rect::new(
+            inner.x - scroll.offset.x,
+            inner.y - scroll.offset.y,
+            content_size.x,
+            content_size.y,
+        );
+    }
+
+    let style = &node.style;
+    let direction = style.direction;
+    let gap = style.gap;
+    let align = style.align_items;
+    let justify = style.justify_content;
+
+    let children = node.children.clone();
+    let children: Vec<_> = children
+        .into_iter()
+        .filter(|child| nodes[*child].style.visible)
+        .collect();
+
+    let main_axis = match direction {
+        UiDirection::Stack => {
+            for child in children {
+                let child_node = &nodes[child];
+                let lengths = [child_node.style.width, child_node.style.height];
+                let intrinsic_size = child_node.intrinsic_size;
+
+                let mut child_rect = Rect::ZERO;
+
+                for axis in 0..2 {
+                    let available = inner.size()[axis];
+                    let size = resolve_cross(lengths[axis], available, intrinsic_size[axis], align);
+
+                    let mut position = child_rect.position();
+                    position[axis] = inner.position()[axis] + align_offset(align, available - size);
+                    child_rect.set_position(position);
+
+                    let mut rect_size = child_rect.size();
+                    rect_size[axis] = size;
+                    child_rect.set_size(rect_size);
+                }
+
+                arrange(nodes, child, child_rect);
+            }
+
+            return;
+        }
+        UiDirection::Row => 0,
+        UiDirection::Column => 1,
+    };
+
+    let cross_axis = 1 - main_axis;
+    let available_main = inner.size()[main_axis];
+    let available_cross = inner.size()[cross_axis];
+
+    let lengths = |node: &UiNode| {
+        let lengths = [node.style.width, node.style.height];
+        (lengths[main_axis], lengths[cross_axis])
+    };
+
+    let gap_total = gap * children.len().saturating_sub(1) as f32;
+    let mut fixed_total = 0.0;
+    let mut grow_total = 0.0;
+
+    for child in &children {
+        let child_node = &nodes[*child];
+
+        match lengths(child_node).0 {
+            UiLength::Grow(weight) if weight > 0.0 => grow_total += weight,
+            _ => fixed_total += child_node.intrinsic_size[main_axis],
+        }
+    }
+
+    let remaining = (available_main - fixed_total - gap_total).max(0.0);
+    let used = fixed_total + gap_total + if grow_total > 0.0 { remaining } else { 0.0 };
+    let free = (available_main - used).max(0.0);
+
+    let mut cursor = inner.position()[main_axis] + align_offset(justify, free);
+
+    for child in children {
+        let child_node = &nodes[child];
+        let (main_length, cross_length) = lengths(child_node);
+        let intrinsic_size = child_node.intrinsic_size;
+
+        let main_size = match main_length {
+            UiLength::Grow(weight) if grow_total > 0.0 && weight > 0.0 => {
+                remaining * weight / grow_total
+            }
+            _ => intrinsic_size[main_axis],
+        };
+
+        let cross_size = resolve_cross(
+            cross_length,
+            available_cross,
+            intrinsic_size[cross_axis],
+            align,
+        );
+
+        let mut position = Vec2::ZERO;
+        position[main_axis] = cursor;
+        position[cross_axis] =
+            inner.position()[cross_axis] + align_offset(align, available_cross - cross_size);
+
+        let mut size = Vec2::ZERO;
+        size[main_axis] = main_size;
+        size[cross_axis] = cross_size;
+
+        arrange(
+            nodes,
+            child,
+            Rect::new(position.x, position.y, size.x, size.y),
+        );
+
+        cursor += main_size + gap;
+    }
+}
+
+fn resolve_cross(length: UiLength, available: f32, intrinsic: f32, align: UiAlign) -> f32 {
+    match length {
+        UiLength::Px(value) => value,
+        UiLength::Grow(_) => available,
+        UiLength::Auto if align == UiAlign::Stretch => available,
+        UiLength::Auto => intrinsic,
+    }
+}
+
+fn align_offset(align: UiAlign, free: f32) -> f32 {
+    match align {
+        UiAlign::Start | UiAlign::Stretch => 0.0,
+        UiAlign::Center => free * 0.5,
+        UiAlign::End => free,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn add(nodes: &mut UiNodes, parent: Option<UiNodeKey>, style: UiStyle) -> UiNodeKey {
+        let mut node = UiNode::new(style);
+        node.parent = parent;
+
+        let key = nodes.insert(node);
+
+        if let Some(parent) = parent {
+            nodes[parent].children.push(key);
+        }
+
+        key
+    }
+
+    fn no_widgets(_: &dyn UiWidget) -> Vec2 {
+        Vec2::ZERO
+    }
+
+    #[test]
+    fn column_grow() {
+        let mut nodes = UiNodes::with_key();
+        let root = add(
+            &mut nodes,
+            None,
+            UiStyle::column()
+                .with_padding(UiEdges::all(10.0))
+                .with_gap(5.0)
+                .with_align_items(UiAlign::Stretch),
+        );
+
+        let fixed = add(
+            &mut nodes,
+            Some(root),
+            UiStyle::stack().with_height(UiLength::Px(20.0)),
+        );
+        let grow_a = add(
+            &mut nodes,
+            Some(root),
+            UiStyle::stack().with_height(UiLength::Grow(1.0)),
+        );
+        let grow_b = add(
+            &mut nodes,
+            Some(root),
+            UiStyle::stack().with_height(UiLength::Grow(3.0)),
+        );
+
+        layout(
+            &mut nodes,
+            root,
+            Rect::new(0.0, 0.0, 100.0, 130.0),
+            &no_widgets,
+        );
+
+        // 130 - 20 padding - 20 fixed - 10 gaps = 80 remaining
+        assert_eq!(nodes[fixed].rect, Rect::new(10.0, 10.0, 80.0, 20.0));
+        assert_eq!(nodes[grow_a].rect, Rect::new(10.0, 35.0, 80.0, 20.0));
+        assert_eq!(nodes[grow_b].rect, Rect::new(10.0, 60.0, 80.0, 60.0));
+    }
+
+    #[test]
+    fn row_alignment() {
+        let mut nodes = UiNodes::with_key();
+        let root = add(
+            &mut nodes,
+            None,
+            UiStyle::row()
+                .with_align_items(UiAlign::Center)
+                .with_justify_content(UiAlign::End),
+        );
+
+        let child = add(
+            &mut nodes,
+            Some(root),
+            UiStyle::stack().with_size(UiLength::Px(10.0), UiLength::Px(10.0)),
+        );
+
+        layout(
+            &mut nodes,
+            root,
+            Rect::new(0.0, 0.0, 50.0, 30.0),
+            &no_widgets,
+        );
+
+        assert_eq!(nodes[child].rect, Rect::new(40.0, 10.0, 10.0, 10.0));
+    }
+
+    #[test]
+    fn scroll_offset_is_clamped() {
+        let mut nodes = UiNodes::with_key();
+        let root = add(&mut nodes, None, UiStyle::column());
+        nodes[root].scroll = Some(UiScroll {
+            offset: Vec2::new(0.0, 1000.0),
+            ..Default::default()
+        });
+
+        let mut children = Vec::new();
+
+        for _ in 0..4 {
+            children.push(add(
+                &mut nodes,
+                Some(root),
+                UiStyle::stack().with_size(UiLength::Px(10.0), UiLength::Px(25.0)),
+            ));
+        }
+
+        layout(
+            &mut nodes,
+            root,
+            Rect::new(0.0, 0.0, 10.0, 50.0),
+            &no_widgets,
+        );
+
+        let scroll = nodes[root].scroll.unwrap();
+        assert_eq!(scroll.content_size, Vec2::new(10.0, 100.0));
+        assert_eq!(scroll.offset, Vec2::new(0.0, 50.0));
+        assert_eq!(nodes[children[2]].rect, Rect::new(0.0, 0.0, 10.0, 25.0));
+    }
+}
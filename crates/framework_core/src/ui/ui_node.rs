@@ -0,0 +1,66 @@
+use super::{UiStyle, UiWidget};
+use math::*;
+
+slotmap::new_key_type! {
+    pub struct UiNodeKey;
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(super) struct UiScroll {
+    pub offset: Vec2,
+    pub content_size: Vec2,
+    /// Lists wrap focus navigation from the last item to the first and back
+    pub wrap_focus: bool,
+}
+
+impl UiScroll {
+    pub fn max_offset(&self, viewport: Vec2) -> Vec2 {
+        (self.content_size - viewport).max(Vec2::ZERO)
+    }
+}
+
+pub(super) struct UiNode {
+    pub style: UiStyle,
+    pub widget: Option<Box<dyn UiWidget>>,
+    pub scroll: Option<UiScroll>,
+    pub parent: Option<UiNodeKey>,
+    pub children: Vec<UiNodeKey>,
+    /// Size requested by the node before constraints are applied, resolved during layout
+    pub intrinsic_size: Vec2,
+    /// Final placement, resolved during layout
+    pub rect: Rect,
+}
+
+impl UiNode {
+    pub fn new(style: UiStyle) -> Self {
+        Self {
+            style,
+            widget: None,
+            scroll: None,
+            parent: None,
+            children: Vec::new(),
+            intrinsic_size: Vec2::ZERO,
+            rect: Rect::ZERO,
+        }
+    }
+
+    pub fn focusable(&self) -> bool {
+        self.style.visible
+            && self
+                .widget
+                .as_ref()
+                .is_some_and(|widget| widget.focusable())
+    }
+
+    /// The rect with padding removed
+    pub fn content_rect(&self) -> Rect {
+        let padding = self.style.padding;
+
+        Rect::new(
+            self.rect.x + padding.left,
+            self.rect.y + padding.top,
+            (self.rect.width - padding.horizontal()).max(0.0),
+            (self.rect.height - padding.vertical()).max(0.0),
+        )
+    }
+}
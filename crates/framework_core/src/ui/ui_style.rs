@@ -0,0 +1,171 @@
+use crate::graphics::NineSlice;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UiLength {
+    /// Sized to fit content
+    Auto,
+    /// A fixed size in pixels
+    Px(f32),
+    /// Shares remaining space with siblings, weighted by the value
+    Grow(f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiDirection {
+    /// Children are placed on top of each other
+    Stack,
+    /// Children are placed left to right
+    Row,
+    /// Children are placed top to bottom
+    Column,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiAlign {
+    Start,
+    Center,
+    End,
+    /// Fills the available space, treated as Start when used for justification
+    Stretch,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct UiEdges {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl UiEdges {
+    pub const ZERO: UiEdges = UiEdges::new(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    pub const fn all(value: f32) -> Self {
+        Self::new(value, value, value, value)
+    }
+
+    pub const fn symmetric(horizontal: f32, vertical: f32) -> Self {
+        Self::new(horizontal, vertical, horizontal, vertical)
+    }
+
+    pub fn horizontal(&self) -> f32 {
+        self.left + self.right
+    }
+
+    pub fn vertical(&self) -> f32 {
+        self.top + self.bottom
+    }
+}
+
+#[derive(Clone)]
+pub struct UiStyle {
+    pub direction: UiDirection,
+    pub width: UiLength,
+    pub height: UiLength,
+    pub padding: UiEdges,
+    /// Space between children
+    pub gap: f32,
+    /// Placement of children on the cross axis, or both axes for UiDirection::Stack
+    pub align_items: UiAlign,
+    /// Placement of children on the main axis
+    pub justify_content: UiAlign,
+    pub background: Option<NineSlice>,
+    /// Hidden nodes are skipped for layout, input, and drawing
+    pub visible: bool,
+}
+
+impl Default for UiStyle {
+    fn default() -> Self {
+        Self {
+            direction: UiDirection::Stack,
+            width: UiLength::Auto,
+            height: UiLength::Auto,
+            padding: UiEdges::ZERO,
+            gap: 0.0,
+            align_items: UiAlign::Start,
+            justify_content: UiAlign::Start,
+            background: None,
+            visible: true,
+        }
+    }
+}
+
+impl UiStyle {
+    pub fn stack() -> Self {
+        Self::default()
+    }
+
+    pub fn row() -> Self {
+        Self {
+            direction: UiDirection::Row,
+            ..Default::default()
+        }
+    }
+
+    pub fn column() -> Self {
+        Self {
+            direction: UiDirection::Column,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_direction(mut self, direction: UiDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn with_width(mut self, width: UiLength) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn with_height(mut self, height: UiLength) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn with_size(mut self, width: UiLength, height: UiLength) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn with_padding(mut self, padding: UiEdges) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_gap(mut self, gap: f32) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn with_align_items(mut self, align: UiAlign) -> Self {
+        self.align_items = align;
+        self
+    }
+
+    pub fn with_justify_content(mut self, justify: UiAlign) -> Self {
+        self.justify_content = justify;
+        self
+    }
+
+    pub fn with_background(mut self, background: NineSlice) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+}
@@ -0,0 +1,156 @@
+use super::*;
+use crate::common::TextEditBuffer;
+use crate::graphics::Color;
+use input::Key;
+use math::*;
+
+pub struct UiTextInput {
+    buffer: TextEditBuffer,
+    placeholder: String,
+    editing: bool,
+}
+
+impl UiTextInput {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            buffer: TextEditBuffer::new(text),
+            placeholder: String::new(),
+            editing: false,
+        }
+    }
+
+    /// Displayed while the text is empty
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Limit in chars
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.buffer.set_max_length(Some(max_length));
+        self
+    }
+
+    pub fn text(&self) -> &str {
+        self.buffer.text()
+    }
+
+    /// Does not emit UiEventKind::TextChanged
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.buffer.set_text(text);
+    }
+
+    pub fn buffer(&self) -> &TextEditBuffer {
+        &self.buffer
+    }
+
+    pub fn editing(&self) -> bool {
+        self.editing
+    }
+
+    fn start_editing(&mut self, context: &mut UiContext) {
+        self.editing = true;
+        context.game_io.input_mut().start_text_input();
+    }
+
+    fn stop_editing(&mut self, context: &mut UiContext) {
+        self.editing = false;
+        self.buffer.clear_selection();
+        context.game_io.input_mut().end_text_input();
+    }
+}
+
+impl UiWidget for UiTextInput {
+    fn measure(&self, theme: &UiTheme) -> Vec2 {
+        let sample = if self.buffer.text().is_empty() {
+            &self.placeholder
+        } else {
+            self.buffer.text()
+        };
+
+        let mut size = theme.measure_padded_text(sample);
+        size.y = size
+            .y
+            .max(theme.font.line_height() * theme.text_scale + theme.text_padding.y * 2.0);
+        size
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn captures_navigation(&self) -> bool {
+        self.editing
+    }
+
+    fn focus_changed(&mut self, context: &mut UiContext, focused: bool) {
+        if !focused && self.editing {
+            self.stop_editing(context);
+        }
+    }
+
+    fn activate(&mut self, context: &mut UiContext) {
+        if !self.editing {
+            self.start_editing(context);
+        }
+    }
+
+    fn update(&mut self, context: &mut UiContext) {
+        if !self.editing {
+            return;
+        }
+
+        if context.game_io.input().was_key_just_pressed(Key::Escape) {
+            self.stop_editing(context);
+            return;
+        }
+
+        let response = self.buffer.update(context.game_io);
+
+        if response.changed {
+            context.emit(UiEventKind::TextChanged);
+        }
+
+        if response.submitted {
+            self.stop_editing(context);
+            context.emit(UiEventKind::TextSubmitted);
+        }
+    }
+
+    fn draw(&self, context: &mut UiDrawContext, rect: Rect, state: UiWidgetState) {
+        let theme = context.theme();
+
+        let background = if state.focused {
+            theme
+                .text_input_focused
+                .as_ref()
+                .or(theme.text_input.as_ref())
+        } else {
+            theme.text_input.as_ref()
+        };
+
+        if let Some(background) = background {
+            context.draw_nine_slice(background, rect, Color::WHITE);
+        }
+
+        if self.buffer.text().is_empty() && !self.editing {
+            let color = theme.disabled_text_color;
+            context.draw_text_in_rect(&self.placeholder, rect, color);
+            return;
+        }
+
+        let color = if state.focused {
+            theme.focused_text_color
+        } else {
+            theme.text_color
+        };
+
+        if self.editing {
+            let mut display = self.buffer.display();
+            display.text.insert(display.cursor, '_');
+            context.draw_text_in_rect(&display.text, rect, color);
+        } else {
+            context.draw_text_in_rect(self.buffer.text(), rect, color);
+        }
+    }
+}
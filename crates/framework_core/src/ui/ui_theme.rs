@@ -0,0 +1,75 @@
+use crate::graphics::*;
+use math::*;
+
+/// Shared visuals for widgets, nine slices left as None are skipped when drawing
+#[derive(Clone)]
+pub struct UiTheme {
+    pub font: BitmapFont,
+    pub text_scale: f32,
+    /// Space between the edges of a widget and its text
+    pub text_padding: Vec2,
+    pub text_color: Color,
+    pub focused_text_color: Color,
+    pub disabled_text_color: Color,
+    pub button: Option<NineSlice>,
+    pub button_focused: Option<NineSlice>,
+    pub button_pressed: Option<NineSlice>,
+    pub text_input: Option<NineSlice>,
+    pub text_input_focused: Option<NineSlice>,
+    pub toggle_on: Option<NineSlice>,
+    pub toggle_off: Option<NineSlice>,
+    /// Size of the toggle indicator drawn next to the label
+    pub toggle_size: Vec2,
+    pub scroll_bar: Option<NineSlice>,
+    pub scroll_bar_width: f32,
+}
+
+impl UiTheme {
+    pub fn new(font: BitmapFont) -> Self {
+        let glyph_size = font.glyph_size();
+
+        Self {
+            font,
+            text_scale: 1.0,
+            text_padding: Vec2::new(glyph_size.x, glyph_size.y * 0.5),
+            text_color: Color::WHITE,
+            focused_text_color: Color::WHITE,
+            disabled_text_color: Color::new(0.5, 0.5, 0.5, 1.0),
+            button: None,
+            button_focused: None,
+            button_pressed: None,
+            text_input: None,
+            text_input_focused: None,
+            toggle_on: None,
+            toggle_off: None,
+            toggle_size: glyph_size,
+            scroll_bar: None,
+            scroll_bar_width: 4.0,
+        }
+    }
+
+    pub fn measure_text(&self, text: &str) -> Vec2 {
+        self.font.measure(text) * self.text_scale
+    }
+
+    /// Size of text with padding applied to each side
+    pub fn measure_padded_text(&self, text: &str) -> Vec2 {
+        self.measure_text(text) + self.text_padding * 2.0
+    }
+
+    pub(super) fn button_background(&self, focused: bool, pressed: bool) -> Option<&NineSlice> {
+        if pressed {
+            if let Some(nine_slice) = &self.button_pressed {
+                return Some(nine_slice);
+            }
+        }
+
+        if focused {
+            if let Some(nine_slice) = &self.button_focused {
+                return Some(nine_slice);
+            }
+        }
+
+        self.button.as_ref()
+    }
+}
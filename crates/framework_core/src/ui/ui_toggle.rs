@@ -0,0 +1,88 @@
+use super::*;
+use crate::graphics::Color;
+use math::*;
+
+pub struct UiToggle {
+    label: String,
+    value: bool,
+}
+
+impl UiToggle {
+    pub fn new(label: impl Into<String>, value: bool) -> Self {
+        Self {
+            label: label.into(),
+            value,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = label.into();
+    }
+
+    pub fn value(&self) -> bool {
+        self.value
+    }
+
+    /// Does not emit UiEventKind::Toggled
+    pub fn set_value(&mut self, value: bool) {
+        self.value = value;
+    }
+}
+
+impl UiWidget for UiToggle {
+    fn measure(&self, theme: &UiTheme) -> Vec2 {
+        let text_size = theme.measure_padded_text(&self.label);
+
+        Vec2::new(
+            text_size.x + theme.toggle_size.x + theme.text_padding.x,
+            text_size.y.max(theme.toggle_size.y),
+        )
+    }
+
+    fn focusable(&self) -> bool {
+        true
+    }
+
+    fn activate(&mut self, context: &mut UiContext) {
+        self.value = !self.value;
+        context.emit(UiEventKind::Toggled(self.value));
+    }
+
+    fn draw(&self, context: &mut UiDrawContext, rect: Rect, state: UiWidgetState) {
+        let theme = context.theme();
+
+        let text_color = if state.focused {
+            theme.focused_text_color
+        } else {
+            theme.text_color
+        };
+
+        if let Some(background) = theme.button_background(state.focused, state.pressed) {
+            context.draw_nine_slice(background, rect, Color::WHITE);
+        }
+
+        let indicator = if self.value {
+            &theme.toggle_on
+        } else {
+            &theme.toggle_off
+        };
+
+        let toggle_size = theme.toggle_size;
+        let toggle_rect = Rect::new(
+            rect.right() - theme.text_padding.x - toggle_size.x,
+            rect.center_y() - toggle_size.y * 0.5,
+            toggle_size.x,
+            toggle_size.y,
+        );
+
+        if let Some(indicator) = indicator {
+            context.draw_nine_slice(indicator, toggle_rect, Color::WHITE);
+        }
+
+        context.draw_text_in_rect(&self.label, rect, text_color);
+    }
+}
@@ -0,0 +1,776 @@
+use super::*;
+use crate::common::GameIO;
+use crate::graphics::*;
+use input::*;
+use math::*;
+use std::any::Any;
+
+/// Distance in pixels a touch must travel before it's treated as a drag instead of a tap
+const TOUCH_DRAG_THRESHOLD: f32 = 8.0;
+
+struct UiTouch {
+    id: u64,
+    start: Vec2,
+    last: Vec2,
+    dragging: bool,
+    target: Option<UiNodeKey>,
+    scroll_target: Option<UiNodeKey>,
+}
+
+/// A retained widget tree, positions are in pixels with (0.0, 0.0) as the top left of the render
+pub struct UiTree {
+    nodes: UiNodes,
+    root: UiNodeKey,
+    theme: UiTheme,
+    camera: OrthoCamera,
+    size: Vec2,
+    focused: Option<UiNodeKey>,
+    hovered: Option<UiNodeKey>,
+    pressed: Option<UiNodeKey>,
+    touch: Option<UiTouch>,
+    last_mouse_position: Vec2,
+    events: Vec<UiEvent>,
+}
+
+impl UiTree {
+    pub fn new(game_io: &GameIO, theme: UiTheme) -> Self {
+        let size = game_io.window().resolution().as_vec2();
+        let root_style = UiStyle::stack().with_size(UiLength::Grow(1.0), UiLength::Grow(1.0));
+
+        let mut nodes = UiNodes::with_key();
+        let root = nodes.insert(UiNode::new(root_style));
+
+        Self {
+            nodes,
+            root,
+            theme,
+            camera: OrthoCamera::new(game_io, size).with_inverted_y(true),
+            size,
+            focused: None,
+            hovered: None,
+            pressed: None,
+            touch: None,
+            last_mouse_position: Vec2::ZERO,
+            events: Vec::new(),
+        }
+    }
+
+    /// The root node fills the render
+    pub fn root(&self) -> UiNodeKey {
+        self.root
+    }
+
+    pub fn theme(&self) -> &UiTheme {
+        &self.theme
+    }
+
+    pub fn theme_mut(&mut self) -> &mut UiTheme {
+        &mut self.theme
+    }
+
+    pub fn add_container(&mut self, parent: UiNodeKey, style: UiStyle) -> UiNodeKey {
+        self.insert(parent, UiNode::new(style))
+    }
+
+    pub fn add_row(&mut self, parent: UiNodeKey, style: UiStyle) -> UiNodeKey {
+        self.add_container(parent, style.with_direction(UiDirection::Row))
+    }
+
+    pub fn add_column(&mut self, parent: UiNodeKey, style: UiStyle) -> UiNodeKey {
+        self.add_container(parent, style.with_direction(UiDirection::Column))
+    }
+
+    /// Children that don't fit are scrolled into view on focus, by mouse wheel, or by touch dragging
+    pub fn add_scroll_view(&mut self, parent: UiNodeKey, style: UiStyle) -> UiNodeKey {
+        let mut node = UiNode::new(style);
+        node.scroll = Some(UiScroll::default());
+
+        self.insert(parent, node)
+    }
+
+    /// A scrolling column that wraps focus navigation between the first and last items
+    pub fn add_list(&mut self, parent: UiNodeKey, style: UiStyle) -> UiNodeKey {
+        let mut node = UiNode::new(style.with_direction(UiDirection::Column));
+        node.scroll = Some(UiScroll {
+            wrap_focus: true,
+            ..Default::default()
+        });
+
+        self.insert(parent, node)
+    }
+
+    pub fn add_widget<W: UiWidget>(
+        &mut self,
+        parent: UiNodeKey,
+        style: UiStyle,
+        widget: W,
+    ) -> UiNodeKey {
+        let mut node = UiNode::new(style);
+        node.widget = Some(Box::new(widget));
+
+        self.insert(parent, node)
+    }
+
+    fn insert(&mut self, parent: UiNodeKey, mut node: UiNode) -> UiNodeKey {
+        node.parent = Some(parent);
+
+        let key = self.nodes.insert(node);
+        self.nodes[parent].children.push(key);
+
+        key
+    }
+
+    /// Removes the node and its children, the root can not be removed
+    pub fn remove(&mut self, key: UiNodeKey) {
+        if key == self.root {
+            return;
+        }
+
+        let Some(node) = self.nodes.get(key) else {
+            return;
+        };
+
+        if let Some(parent) = node.parent {
+            self.nodes[parent].children.retain(|child| *child != key);
+        }
+
+        self.remove_recursive(key);
+    }
+
+    pub fn clear_children(&mut self, key: UiNodeKey) {
+        let Some(node) = self.nodes.get_mut(key) else {
+            return;
+        };
+
+        for child in std::mem::take(&mut node.children) {
+            self.remove_recursive(child);
+        }
+    }
+
+    fn remove_recursive(&mut self, key: UiNodeKey) {
+        let Some(node) = self.nodes.remove(key) else {
+            return;
+        };
+
+        for child in node.children {
+            self.remove_recursive(child);
+        }
+
+        for state in [&mut self.focused, &mut self.hovered, &mut self.pressed] {
+            if *state == Some(key) {
+                *state = None;
+            }
+        }
+    }
+
+    pub fn contains(&self, key: UiNodeKey) -> bool {
+        self.nodes.contains_key(key)
+    }
+
+    pub fn parent(&self, key: UiNodeKey) -> Option<UiNodeKey> {
+        self.nodes.get(key)?.parent
+    }
+
+    pub fn children(&self, key: UiNodeKey) -> &[UiNodeKey] {
+        self.nodes
+            .get(key)
+            .map(|node| node.children.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn style(&self, key: UiNodeKey) -> Option<&UiStyle> {
+        Some(&self.nodes.get(key)?.style)
+    }
+
+    pub fn style_mut(&mut self, key: UiNodeKey) -> Option<&mut UiStyle> {
+        Some(&mut self.nodes.get_mut(key)?.style)
+    }
+
+    pub fn widget<W: UiWidget>(&self, key: UiNodeKey) -> Option<&W> {
+        let widget: &dyn Any = self.nodes.get(key)?.widget.as_deref()?;
+        widget.downcast_ref()
+    }
+
+    pub fn widget_mut<W: UiWidget>(&mut self, key: UiNodeKey) -> Option<&mut W> {
+        let widget: &mut dyn Any = self.nodes.get_mut(key)?.widget.as_deref_mut()?;
+        widget.downcast_mut()
+    }
+
+    /// The placement of the node resolved during the latest update
+    pub fn rect(&self, key: UiNodeKey) -> Option<Rect> {
+        Some(self.nodes.get(key)?.rect)
+    }
+
+    pub fn scroll_offset(&self, key: UiNodeKey) -> Option<Vec2> {
+        Some(self.nodes.get(key)?.scroll?.offset)
+    }
+
+    pub fn set_scroll_offset(&mut self, key: UiNodeKey, offset: Vec2) {
+        if let Some(scroll) = self
+            .nodes
+            .get_mut(key)
+            .and_then(|node| node.scroll.as_mut())
+        {
+            scroll.offset = offset;
+        }
+    }
+
+    pub fn focused(&self) -> Option<UiNodeKey> {
+        self.focused
+    }
+
+    pub fn set_focus(&mut self, game_io: &mut GameIO, key: Option<UiNodeKey>) {
+        let key = key.filter(|key| self.nodes.contains_key(*key));
+
+        if key == self.focused {
+            return;
+        }
+
+        if let Some(previous) = self.focused.take() {
+            self.with_widget(game_io, previous, |widget, context| {
+                widget.focus_changed(context, false)
+            });
+            self.events.push(UiEvent {
+                node: previous,
+                kind: UiEventKind::FocusLost,
+            });
+        }
+
+        self.focused = key;
+
+        if let Some(key) = key {
+            self.with_widget(game_io, key, |widget, context| {
+                widget.focus_changed(context, true)
+            });
+            self.events.push(UiEvent {
+                node: key,
+                kind: UiEventKind::FocusGained,
+            });
+        }
+    }
+
+    /// Events emitted during the latest update
+    pub fn events(&self) -> &[UiEvent] {
+        &self.events
+    }
+
+    pub fn was_activated(&self, key: UiNodeKey) -> bool {
+        self.events
+            .iter()
+            .any(|event| event.node == key && event.kind == UiEventKind::Activated)
+    }
+
+    pub fn update(&mut self, game_io: &mut GameIO) {
+        self.events.clear();
+        self.size = game_io.window().resolution().as_vec2();
+        self.layout();
+
+        self.update_pointer(game_io);
+        self.update_touch(game_io);
+        self.update_navigation(game_io);
+
+        for (key, _) in self.visible_nodes() {
+            self.with_widget(game_io, key, |widget, context| widget.update(context));
+        }
+
+        if let Some(focused) = self.focused {
+            self.scroll_into_view(focused);
+        }
+
+        self.layout();
+    }
+
+    fn layout(&mut self) {
+        let theme = &self.theme;
+        let bounds = Rect::new(0.0, 0.0, self.size.x, self.size.y);
+
+        layout(&mut self.nodes, self.root, bounds, &|widget| {
+            widget.measure(theme)
+        });
+    }
+
+    fn ui_position(&self, render_position: Vec2) -> Vec2 {
+        (render_position * Vec2::new(0.5, -0.5) + 0.5) * self.size
+    }
+
+    fn update_pointer(&mut self, game_io: &mut GameIO) {
+        let input = game_io.input();
+        let mouse_position = self.ui_position(input.mouse_position());
+        let moved = mouse_position != self.last_mouse_position;
+        let just_pressed = input.was_mouse_button_just_pressed(MouseButton::Left);
+        let released = input.was_mouse_button_released(MouseButton::Left);
+        let wheel_delta = input.mouse_wheel_delta();
+
+        self.last_mouse_position = mouse_position;
+
+        if wheel_delta != Vec2::ZERO {
+            if let Some(scroll_target) = self.scroll_target(mouse_position) {
+                if let Some(scroll) = &mut self.nodes[scroll_target].scroll {
+                    // positive wheel y scrolls up, ui y grows downward
+                    let delta = Vec2::new(wheel_delta.x, -wheel_delta.y);
+                    scroll.offset += delta * MouseWheelDelta::PIXELS_PER_LINE;
+                }
+            }
+        }
+
+        if moved || just_pressed {
+            let hovered = self.hit_test(mouse_position);
+
+            if hovered != self.hovered {
+                self.hovered = hovered;
+
+                if hovered.is_some() {
+                    self.set_focus(game_io, hovered);
+                }
+            }
+        }
+
+        if just_pressed {
+            self.pressed = self.hovered;
+        }
+
+        if released {
+            let pressed = self.pressed.take();
+
+            if let Some(pressed) = pressed.filter(|key| Some(*key) == self.hit_test(mouse_position))
+            {
+                self.activate(game_io, pressed);
+            }
+        }
+    }
+
+    fn update_touch(&mut self, game_io: &mut GameIO) {
+        let touches = game_io.input().touches().to_vec();
+
+        for touch in touches {
+            let position = self.ui_position(touch.position);
+
+            if touch.phase == TouchPhase::Start {
+                if self.touch.is_some() {
+                    // only the first touch interacts with the ui
+                    continue;
+                }
+
+                let target = self.hit_test(position);
+
+                self.touch = Some(UiTouch {
+                    id: touch.id,
+                    start: position,
+                    last: position,
+                    dragging: false,
+                    target,
+                    scroll_target: self.scroll_target(position),
+                });
+
+                self.pressed = target;
+                continue;
+            }
+
+            let Some(ui_touch) = &mut self.touch else {
+                continue;
+            };
+
+            if ui_touch.id != touch.id {
+                continue;
+            }
+
+            match touch.phase {
+                TouchPhase::Start => {}
+                TouchPhase::Moving => {
+                    if !ui_touch.dragging
+                        && ui_touch.start.distance(position) > TOUCH_DRAG_THRESHOLD
+                    {
+                        ui_touch.dragging = true;
+                        self.pressed = None;
+                    }
+
+                    let delta = position - ui_touch.last;
+                    ui_touch.last = position;
+
+                    if let (true, Some(scroll_target)) = (ui_touch.dragging, ui_touch.scroll_target)
+                    {
+                        if let Some(scroll) = &mut self.nodes[scroll_target].scroll {
+                            scroll.offset -= delta;
+                        }
+                    }
+                }
+                TouchPhase::End => {
+                    let ui_touch = self.touch.take().unwrap();
+                    self.pressed = None;
+
+                    let target = ui_touch
+                        .target
+                        .filter(|key| !ui_touch.dragging && Some(*key) == self.hit_test(position));
+
+                    if let Some(target) = target {
+                        self.set_focus(game_io, Some(target));
+                        self.activate(game_io, target);
+                    }
+                }
+                TouchPhase::Cancelled => {
+                    self.touch = None;
+                    self.pressed = None;
+                }
+            }
+        }
+
+        if let Some(ui_touch) = &mut self.touch {
+            // widgets may have been removed while the touch was held
+            ui_touch.target = ui_touch.target.filter(|key| self.nodes.contains_key(*key));
+        }
+    }
+
+    fn update_navigation(&mut self, game_io: &mut GameIO) {
+        let captured = self
+            .focused
+            .and_then(|key| self.nodes.get(key))
+            .and_then(|node| node.widget.as_ref())
+            .is_some_and(|widget| widget.captures_navigation());
+
+        if captured {
+            return;
+        }
+
+        let input = game_io.input();
+
+        let key_pressed = |key: Key| input.was_key_just_pressed(key) || input.is_key_repeated(key);
+        let button_pressed = |button: Button| {
+            input
+                .controllers()
+                .iter()
+                .any(|controller| controller.was_button_just_pressed(button))
+        };
+
+        let direction = if key_pressed(Key::Up)
+            || button_pressed(Button::DPadUp)
+            || button_pressed(Button::LeftStickUp)
+        {
+            Some(UiNavDirection::Up)
+        } else if key_pressed(Key::Down)
+            || button_pressed(Button::DPadDown)
+            || button_pressed(Button::LeftStickDown)
+        {
+            Some(UiNavDirection::Down)
+        } else if key_pressed(Key::Left)
+            || button_pressed(Button::DPadLeft)
+            || button_pressed(Button::LeftStickLeft)
+        {
+            Some(UiNavDirection::Left)
+        } else if key_pressed(Key::Right)
+            || button_pressed(Button::DPadRight)
+            || button_pressed(Button::LeftStickRight)
+        {
+            Some(UiNavDirection::Right)
+        } else {
+            None
+        };
+
+        let tab = key_pressed(Key::Tab);
+        let reverse_tab = input.is_key_down(Key::LShift) || input.is_key_down(Key::RShift);
+
+        let confirm = input.was_key_just_pressed(Key::Return)
+            || input.was_key_just_pressed(Key::Space)
+            || button_pressed(Button::A);
+
+        let focusable: Vec<_> = self
+            .visible_nodes()
+            .into_iter()
+            .filter(|(key, _)| self.nodes[*key].focusable())
+            .collect();
+
+        let current = self.focused.filter(|key| {
+            focusable
+                .iter()
+                .any(|(focusable_key, _)| focusable_key == key)
+        });
+
+        let next = match (current, direction, tab) {
+            (None, Some(_), _) | (None, _, true) => focusable.first().map(|(key, _)| *key),
+            (Some(current), _, true) => {
+                let index = focusable.iter().position(|(key, _)| *key == current);
+                let len = focusable.len();
+
+                index.map(|index| {
+                    let next_index = if reverse_tab {
+                        (index + len - 1) % len
+                    } else {
+                        (index + 1) % len
+                    };
+
+                    focusable[next_index].0
+                })
+            }
+            (Some(current), Some(direction), _) => {
+                // navigating within the same scroll view uses unclipped rects to reach hidden items
+                let from = self.nodes[current].rect;
+                let candidates: Vec<_> = focusable
+                    .iter()
+                    .filter(|(key, _)| *key != current)
+                    .map(|(key, _)| (*key, self.nodes[*key].rect))
+                    .collect();
+
+                find_next(&candidates, from, direction)
+                    .or_else(|| self.wrap_focus(current, direction, &focusable))
+            }
+            _ => None,
+        };
+
+        if let Some(next) = next {
+            self.set_focus(game_io, Some(next));
+        } else if confirm {
+            if let Some(focused) = current {
+                self.activate(game_io, focused);
+            }
+        }
+    }
+
+    /// Finds the item to wrap to when navigating past the ends of a list
+    fn wrap_focus(
+        &self,
+        current: UiNodeKey,
+        direction: UiNavDirection,
+        focusable: &[(UiNodeKey, Rect)],
+    ) -> Option<UiNodeKey> {
+        let list = self.ancestors(current).find(|key| {
+            let node = &self.nodes[*key];
+            node.scroll.is_some_and(|scroll| scroll.wrap_focus)
+        })?;
+
+        let forward = match (self.nodes[list].style.direction, direction) {
+            (UiDirection::Column, UiNavDirection::Down) => true,
+            (UiDirection::Column, UiNavDirection::Up) => false,
+            (UiDirection::Row, UiNavDirection::Right) => true,
+            (UiDirection::Row, UiNavDirection::Left) => false,
+            _ => return None,
+        };
+
+        let mut items = focusable
+            .iter()
+            .map(|(key, _)| *key)
+            .filter(|key| self.ancestors(*key).any(|ancestor| ancestor == list));
+
+        let item = if forward { items.next() } else { items.last() };
+
+        item.filter(|key| *key != current)
+    }
+
+    fn ancestors(&self, key: UiNodeKey) -> impl Iterator<Item = UiNodeKey> + '_ {
+        std::iter::successors(self.nodes.get(key).and_then(|node| node.parent), |key| {
+            self.nodes.get(*key).and_then(|node| node.parent)
+        })
+    }
+
+    fn activate(&mut self, game_io: &mut GameIO, key: UiNodeKey) {
+        self.with_widget(game_io, key, |widget, context| widget.activate(context));
+    }
+
+    fn with_widget(
+        &mut self,
+        game_io: &mut GameIO,
+        key: UiNodeKey,
+        callback: impl FnOnce(&mut dyn UiWidget, &mut UiContext),
+    ) {
+        let Some(node) = self.nodes.get_mut(key) else {
+            return;
+        };
+
+        let Some(mut widget) = node.widget.take() else {
+            return;
+        };
+
+        let mut context = UiContext {
+            game_io,
+            rect: node.rect,
+            state: UiWidgetState {
+                focused: self.focused == Some(key),
+                hovered: self.hovered == Some(key),
+                pressed: self.pressed == Some(key),
+            },
+            node: key,
+            events: &mut self.events,
+        };
+
+        callback(widget.as_mut(), &mut context);
+
+        // the node may have been removed by the callback through the GameIO
+        if let Some(node) = self.nodes.get_mut(key) {
+            node.widget = Some(widget);
+        }
+    }
+
+    /// Visible nodes in draw order, paired with their rect clipped by scroll views
+    fn visible_nodes(&self) -> Vec<(UiNodeKey, Rect)> {
+        let mut output = Vec::new();
+        let bounds = Rect::new(0.0, 0.0, self.size.x, self.size.y);
+        self.collect_visible(self.root, bounds, &mut output);
+        output
+    }
+
+    fn collect_visible(&self, key: UiNodeKey, clip: Rect, output: &mut Vec<(UiNodeKey, Rect)>) {
+        let node = &self.nodes[key];
+
+        if !node.style.visible {
+            return;
+        }
+
+        let clipped = node.rect.scissor(clip);
+
+        if clipped.width <= 0.0 || clipped.height <= 0.0 {
+            return;
+        }
+
+        output.push((key, clipped));
+
+        let child_clip = if node.scroll.is_some() {
+            node.content_rect().scissor(clip)
+        } else {
+            clip
+        };
+
+        for child in &node.children {
+            self.collect_visible(*child, child_clip, output);
+        }
+    }
+
+    /// Returns the top most focusable node under the position
+    fn hit_test(&self, position: Vec2) -> Option<UiNodeKey> {
+        self.visible_nodes()
+            .into_iter()
+            .rev()
+            .find(|(key, rect)| rect.contains(position) && self.nodes[*key].focusable())
+            .map(|(key, _)| key)
+    }
+
+    fn scroll_target(&self, position: Vec2) -> Option<UiNodeKey> {
+        self.visible_nodes()
+            .into_iter()
+            .rev()
+            .find(|(key, rect)| rect.contains(position) && self.nodes[*key].scroll.is_some())
+            .map(|(key, _)| key)
+    }
+
+    fn scroll_into_view(&mut self, key: UiNodeKey) {
+        let mut rect = self.nodes[key].rect;
+        let ancestors: Vec<_> = self.ancestors(key).collect();
+
+        for ancestor in ancestors {
+            let node = &mut self.nodes[ancestor];
+            let viewport = node.content_rect();
+
+            let Some(scroll) = &mut node.scroll else {
+                continue;
+            };
+
+            let mut shift = Vec2::ZERO;
+
+            for axis in 0..2 {
+                let start = rect.position()[axis];
+                let end = start + rect.size()[axis];
+                let viewport_start = viewport.position()[axis];
+                let viewport_end = viewport_start + viewport.size()[axis];
+
+                if end > viewport_end {
+                    shift[axis] = end - viewport_end;
+                }
+
+                if start - shift[axis] < viewport_start {
+                    shift[axis] = start - viewport_start;
+                }
+            }
+
+            scroll.offset += shift;
+            rect.x -= shift.x;
+            rect.y -= shift.y;
+        }
+    }
+
+    pub fn draw(&mut self, game_io: &GameIO, render_pass: &mut RenderPass) {
+        let size = render_pass.target_size().as_vec2();
+
+        self.camera.resize(size);
+        self.camera
+            .set_position(Vec3::new(size.x * 0.5, size.y * 0.5, 0.0));
+
+        let sprite_queue =
+            SpriteQueue::new_with_default_pipeline(game_io, [self.camera.as_binding()])
+                .with_inverted_y(true);
+
+        let bounds = Rect::new(0.0, 0.0, size.x, size.y);
+        let mut context = UiDrawContext {
+            sprite_queue,
+            theme: &self.theme,
+            target_size: size,
+            clip: bounds,
+        };
+
+        self.draw_node(&mut context, self.root);
+
+        render_pass.consume_queue(context.sprite_queue);
+    }
+
+    fn draw_node(&self, context: &mut UiDrawContext, key: UiNodeKey) {
+        let node = &self.nodes[key];
+
+        if !node.style.visible || !node.rect.overlaps(context.clip()) {
+            return;
+        }
+
+        if let Some(background) = &node.style.background {
+            context.draw_nine_slice(background, node.rect, Color::WHITE);
+        }
+
+        if let Some(widget) = &node.widget {
+            let state = UiWidgetState {
+                focused: self.focused == Some(key),
+                hovered: self.hovered == Some(key),
+                pressed: self.pressed == Some(key),
+            };
+
+            widget.draw(context, node.rect, state);
+        }
+
+        let Some(scroll) = node.scroll else {
+            for child in &node.children {
+                self.draw_node(context, *child);
+            }
+
+            return;
+        };
+
+        let previous_clip = context.clip();
+        let viewport = node.content_rect();
+        context.set_clip(viewport.scissor(previous_clip));
+
+        for child in &node.children {
+            self.draw_node(context, *child);
+        }
+
+        context.set_clip(previous_clip);
+
+        // scroll bar
+        let max_offset = scroll.max_offset(viewport.size());
+        let theme = context.theme();
+
+        if max_offset.y <= 0.0 || viewport.height <= 0.0 {
+            return;
+        }
+
+        let Some(scroll_bar) = &theme.scroll_bar else {
+            return;
+        };
+
+        let bar_width = theme.scroll_bar_width;
+        let visible_ratio = viewport.height / scroll.content_size.y;
+        let bar_height = (viewport.height * visible_ratio).max(bar_width);
+        let progress = scroll.offset.y / max_offset.y;
+
+        let bar_rect = Rect::new(
+            viewport.right() - bar_width,
+            viewport.y + (viewport.height - bar_height) * progress,
+            bar_width,
+            bar_height,
+        );
+
+        context.draw_nine_slice(scroll_bar, bar_rect, Color::WHITE);
+    }
+}
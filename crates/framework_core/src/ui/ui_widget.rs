@@ -0,0 +1,125 @@
+use super::{UiEvent, UiEventKind, UiNodeKey, UiTheme};
+use crate::common::GameIO;
+use crate::graphics::*;
+use math::*;
+use std::any::Any;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct UiWidgetState {
+    pub focused: bool,
+    pub hovered: bool,
+    pub pressed: bool,
+}
+
+/// Passed to widgets while updating
+pub struct UiContext<'a> {
+    pub game_io: &'a mut GameIO,
+    pub rect: Rect,
+    pub state: UiWidgetState,
+    pub(super) node: UiNodeKey,
+    pub(super) events: &'a mut Vec<UiEvent>,
+}
+
+impl UiContext<'_> {
+    pub fn node(&self) -> UiNodeKey {
+        self.node
+    }
+
+    pub fn emit(&mut self, kind: UiEventKind) {
+        self.events.push(UiEvent {
+            node: self.node,
+            kind,
+        });
+    }
+}
+
+/// Passed to widgets while drawing, positions are in pixels with (0.0, 0.0) as the top left of the render
+pub struct UiDrawContext<'a> {
+    pub(super) sprite_queue: SpriteQueue<'a>,
+    pub(super) theme: &'a UiTheme,
+    pub(super) target_size: Vec2,
+    pub(super) clip: Rect,
+}
+
+impl<'a> UiDrawContext<'a> {
+    pub fn theme(&self) -> &'a UiTheme {
+        self.theme
+    }
+
+    pub fn sprite_queue(&mut self) -> &mut SpriteQueue<'a> {
+        &mut self.sprite_queue
+    }
+
+    /// The visible region, draws outside of this rect are cut off
+    pub fn clip(&self) -> Rect {
+        self.clip
+    }
+
+    pub(super) fn set_clip(&mut self, clip: Rect) {
+        self.clip = clip;
+        self.sprite_queue.set_scissor(clip / self.target_size);
+    }
+
+    pub fn draw_nine_slice(&mut self, nine_slice: &NineSlice, rect: Rect, color: Color) {
+        nine_slice.draw(&mut self.sprite_queue, rect, color);
+    }
+
+    pub fn draw_sprite(&mut self, sprite: &Sprite) {
+        self.sprite_queue.draw_sprite(sprite);
+    }
+
+    pub fn draw_text(&mut self, text: &str, position: Vec2, color: Color) {
+        let theme = self.theme;
+        let scale = Vec2::splat(theme.text_scale);
+
+        theme
+            .font
+            .draw_text(&mut self.sprite_queue, text, position, scale, color);
+    }
+
+    /// Draws text centered vertically in the rect, aligned to the left edge with padding from the theme
+    pub fn draw_text_in_rect(&mut self, text: &str, rect: Rect, color: Color) {
+        let text_size = self.theme.measure_text(text);
+        let position = Vec2::new(
+            rect.x + self.theme.text_padding.x,
+            rect.center_y() - text_size.y * 0.5,
+        );
+
+        self.draw_text(text, position.floor(), color);
+    }
+
+    /// Draws text centered in the rect
+    pub fn draw_centered_text(&mut self, text: &str, rect: Rect, color: Color) {
+        let text_size = self.theme.measure_text(text);
+        let position = rect.center() - text_size * 0.5;
+
+        self.draw_text(text, position.floor(), color);
+    }
+}
+
+pub trait UiWidget: Any {
+    /// The minimum size needed to display the widget, used by UiLength::Auto
+    fn measure(&self, _theme: &UiTheme) -> Vec2 {
+        Vec2::ZERO
+    }
+
+    /// Focusable widgets can be selected with the mouse, touch, keyboard, or a controller
+    fn focusable(&self) -> bool {
+        false
+    }
+
+    /// While true, navigation inputs are left to the widget
+    fn captures_navigation(&self) -> bool {
+        false
+    }
+
+    fn focus_changed(&mut self, _context: &mut UiContext, _focused: bool) {}
+
+    /// Called when the widget is clicked, tapped, or confirmed while focused
+    fn activate(&mut self, _context: &mut UiContext) {}
+
+    /// Called every UiTree update while the widget is visible
+    fn update(&mut self, _context: &mut UiContext) {}
+
+    fn draw(&self, context: &mut UiDrawContext, rect: Rect, state: UiWidgetState);
+}
@@ -0,0 +1,206 @@
+use math::*;
+use std::fmt;
+use std::sync::Arc;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeadzoneShape {
+    /// Applies to the distance from the center, preserving the stick's direction
+    #[default]
+    Radial,
+    /// Applies to each axis separately, making it easier to hold a perfectly straight direction
+    Axial,
+}
+
+#[derive(Default, Clone)]
+pub enum ResponseCurve {
+    #[default]
+    Linear,
+    /// Gives finer control near the center
+    Quadratic,
+    /// Maps the magnitude after deadzones are applied, both input and output are in the range [0.0, 1.0]
+    Custom(Arc<dyn Fn(f32) -> f32 + Send + Sync>),
+}
+
+impl ResponseCurve {
+    pub fn apply(&self, value: f32) -> f32 {
+        match self {
+            ResponseCurve::Linear => value,
+            ResponseCurve::Quadratic => value * value,
+            ResponseCurve::Custom(callback) => callback(value).clamp(0.0, 1.0),
+        }
+    }
+}
+
+impl fmt::Debug for ResponseCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResponseCurve::Linear => write!(f, "Linear"),
+            ResponseCurve::Quadratic => write!(f, "Quadratic"),
+            ResponseCurve::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// Controls which of the simulated stick direction buttons, such as `Button::LeftStickUp`, can be held together
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StickDirectionMode {
+    /// Diagonals press two buttons
+    #[default]
+    EightWay,
+    /// Only the button closest to the stick's direction is pressed
+    FourWay,
+}
+
+#[derive(Debug, Clone)]
+pub struct StickConfig {
+    pub deadzone_shape: DeadzoneShape,
+    /// Input below this magnitude is treated as 0.0
+    pub inner_deadzone: f32,
+    /// Input within this distance of the edge is treated as fully pushed
+    pub outer_deadzone: f32,
+    pub response_curve: ResponseCurve,
+    /// Input below this magnitude releases the simulated direction buttons
+    pub button_deadzone: f32,
+    pub direction_mode: StickDirectionMode,
+}
+
+impl Default for StickConfig {
+    fn default() -> Self {
+        Self {
+            deadzone_shape: DeadzoneShape::Radial,
+            inner_deadzone: 0.1,
+            outer_deadzone: 0.0,
+            response_curve: ResponseCurve::Linear,
+            button_deadzone: 0.35,
+            direction_mode: StickDirectionMode::EightWay,
+        }
+    }
+}
+
+impl StickConfig {
+    pub fn apply(&self, value: Vec2) -> Vec2 {
+        match self.deadzone_shape {
+            DeadzoneShape::Radial => {
+                let length = value.length();
+
+                if length == 0.0 {
+                    return Vec2::ZERO;
+                }
+
+                let magnitude = self.apply_magnitude(length.min(1.0));
+
+                value / length * magnitude
+            }
+            DeadzoneShape::Axial => Vec2::new(
+                value.x.signum() * self.apply_magnitude(value.x.abs().min(1.0)),
+                value.y.signum() * self.apply_magnitude(value.y.abs().min(1.0)),
+            ),
+        }
+    }
+
+    fn apply_magnitude(&self, magnitude: f32) -> f32 {
+        let value = remap_deadzones(magnitude, self.inner_deadzone, self.outer_deadzone);
+
+        self.response_curve.apply(value)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TriggerConfig {
+    /// Input below this value is treated as 0.0
+    pub inner_deadzone: f32,
+    /// Input within this distance of the end is treated as fully pulled
+    pub outer_deadzone: f32,
+    pub response_curve: ResponseCurve,
+    /// How far the trigger must be pulled to press the trigger's button, compared against unprocessed input
+    pub button_threshold: f32,
+}
+
+impl Default for TriggerConfig {
+    fn default() -> Self {
+        Self {
+            inner_deadzone: 0.1,
+            outer_deadzone: 0.0,
+            response_curve: ResponseCurve::Linear,
+            button_threshold: 0.1,
+        }
+    }
+}
+
+impl TriggerConfig {
+    pub fn apply(&self, value: f32) -> f32 {
+        let value = remap_deadzones(
+            value.clamp(0.0, 1.0),
+            self.inner_deadzone,
+            self.outer_deadzone,
+        );
+
+        self.response_curve.apply(value)
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct ControllerConfig {
+    pub left_stick: StickConfig,
+    pub right_stick: StickConfig,
+    pub left_trigger: TriggerConfig,
+    pub right_trigger: TriggerConfig,
+}
+
+fn remap_deadzones(magnitude: f32, inner_deadzone: f32, outer_deadzone: f32) -> f32 {
+    let outer_edge = 1.0 - outer_deadzone;
+
+    if magnitude < inner_deadzone {
+        return 0.0;
+    }
+
+    if outer_edge <= inner_deadzone {
+        return 1.0;
+    }
+
+    inverse_lerp!(inner_deadzone, outer_edge, magnitude).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stick_deadzones() {
+        let mut config = StickConfig {
+            inner_deadzone: 0.2,
+            outer_deadzone: 0.2,
+            ..Default::default()
+        };
+
+        assert_eq!(config.apply(Vec2::new(0.1, 0.1)), Vec2::ZERO);
+        assert_eq!(config.apply(Vec2::new(0.0, 0.9)), Vec2::new(0.0, 1.0));
+        assert!((config.apply(Vec2::new(0.5, 0.0)).x - 0.5).abs() < 0.0001);
+
+        // radial keeps the small x component, axial drops it
+        let input = Vec2::new(0.15, 0.6);
+        assert!(config.apply(input).x > 0.0);
+
+        config.deadzone_shape = DeadzoneShape::Axial;
+        assert_eq!(config.apply(input).x, 0.0);
+    }
+
+    #[test]
+    fn trigger_response_curve() {
+        let config = TriggerConfig {
+            inner_deadzone: 0.0,
+            response_curve: ResponseCurve::Quadratic,
+            ..Default::default()
+        };
+
+        assert_eq!(config.apply(0.5), 0.25);
+
+        let config = TriggerConfig {
+            inner_deadzone: 0.0,
+            response_curve: ResponseCurve::Custom(Arc::new(|value| value * 2.0)),
+            ..Default::default()
+        };
+
+        assert_eq!(config.apply(0.75), 1.0);
+    }
+}
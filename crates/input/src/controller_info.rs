@@ -0,0 +1,120 @@
+/// Battery state reported by the controller
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PowerInfo {
+    #[default]
+    Unknown,
+    /// The controller doesn't have a battery
+    Wired,
+    /// Battery percentage in the range [0, 100]
+    Discharging(u8),
+    /// Battery percentage in the range [0, 100]
+    Charging(u8),
+    Charged,
+}
+
+/// The family of a controller's button labels, useful for displaying the correct button glyphs
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ControllerLayout {
+    #[default]
+    Unknown,
+    Xbox,
+    PlayStation,
+    /// A and B are swapped compared to Xbox controllers, as are X and Y
+    Nintendo,
+}
+
+impl ControllerLayout {
+    const MICROSOFT_VENDOR_ID: u16 = 0x045e;
+    const SONY_VENDOR_ID: u16 = 0x054c;
+    const NINTENDO_VENDOR_ID: u16 = 0x057e;
+
+    /// Detects the layout from the vendor, falling back to keywords in the name for third party controllers
+    pub fn detect(vendor_id: Option<u16>, name: &str) -> Self {
+        match vendor_id {
+            Some(Self::MICROSOFT_VENDOR_ID) => return ControllerLayout::Xbox,
+            Some(Self::SONY_VENDOR_ID) => return ControllerLayout::PlayStation,
+            Some(Self::NINTENDO_VENDOR_ID) => return ControllerLayout::Nintendo,
+            _ => {}
+        }
+
+        let name = name.to_lowercase();
+        let name_contains = |keywords: &[&str]| keywords.iter().any(|k| name.contains(k));
+
+        if name_contains(&["xbox", "xinput", "x-box"]) {
+            ControllerLayout::Xbox
+        } else if name_contains(&["playstation", "dualshock", "dualsense", "ps3", "ps4", "ps5"]) {
+            ControllerLayout::PlayStation
+        } else if name_contains(&["nintendo", "switch", "joy-con", "joycon"]) {
+            ControllerLayout::Nintendo
+        } else {
+            ControllerLayout::Unknown
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ControllerInfo {
+    pub name: String,
+    pub vendor_id: Option<u16>,
+    pub product_id: Option<u16>,
+    /// SDL compatible GUID as 32 lowercase hex digits, used to find mappings in `gamecontrollerdb.txt`
+    pub guid: Option<String>,
+    pub layout: ControllerLayout,
+}
+
+impl ControllerInfo {
+    /// Detects the layout, and generates a GUID from the vendor and product when both are available
+    pub fn new(name: String, vendor_id: Option<u16>, product_id: Option<u16>) -> Self {
+        let guid = vendor_id
+            .zip(product_id)
+            .map(|(vendor_id, product_id)| create_sdl_guid(vendor_id, product_id));
+
+        Self {
+            layout: ControllerLayout::detect(vendor_id, &name),
+            name,
+            vendor_id,
+            product_id,
+            guid,
+        }
+    }
+
+    pub fn with_guid(mut self, guid: String) -> Self {
+        self.guid = Some(guid.to_lowercase());
+        self
+    }
+}
+
+/// Creates a GUID in the format SDL uses for USB devices, without a version or CRC
+pub fn create_sdl_guid(vendor_id: u16, product_id: u16) -> String {
+    const USB_BUS: u16 = 0x03;
+
+    let words = [USB_BUS, 0, vendor_id, 0, product_id, 0, 0, 0];
+
+    words
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Reads the vendor and product from an SDL GUID, returns None for GUIDs not based on vendor and product
+pub fn parse_sdl_guid(guid: &str) -> Option<(u16, u16)> {
+    if guid.len() != 32 || !guid.is_ascii() {
+        return None;
+    }
+
+    let read_word = |index: usize| {
+        let start = index * 4;
+        let low = u8::from_str_radix(&guid[start..start + 2], 16).ok()?;
+        let high = u8::from_str_radix(&guid[start + 2..start + 4], 16).ok()?;
+
+        Some(u16::from_le_bytes([low, high]))
+    };
+
+    // SDL stores the vendor and product with padding after each, the padding is used by other formats
+    if read_word(3)? != 0 || read_word(5)? != 0 {
+        return None;
+    }
+
+    Some((read_word(2)?, read_word(4)?))
+}
@@ -0,0 +1,207 @@
+use crate::{parse_sdl_guid, Button};
+
+/// A single line from an SDL `gamecontrollerdb.txt` file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ControllerMapping {
+    source: String,
+    guid: String,
+    name: String,
+    platform: Option<String>,
+    bindings: Vec<(String, String)>,
+}
+
+impl ControllerMapping {
+    /// Returns None for comments, blank lines, and malformed lines
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let mut parts = line.split(',');
+        let guid = parts.next()?.trim().to_lowercase();
+        let name = parts.next()?.trim().to_string();
+
+        if guid.len() != 32 || !guid.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let mut platform = None;
+        let mut bindings = Vec::new();
+
+        for part in parts {
+            let Some((key, value)) = part.split_once(':') else {
+                continue;
+            };
+
+            let key = key.trim();
+            let value = value.trim();
+
+            if key == "platform" {
+                platform = Some(value.to_string());
+            } else {
+                bindings.push((key.to_string(), value.to_string()));
+            }
+        }
+
+        Some(Self {
+            source: line.to_string(),
+            guid,
+            name,
+            platform,
+            bindings,
+        })
+    }
+
+    /// The original line, useful for passing to other SDL compatible libraries
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn guid(&self) -> &str {
+        &self.guid
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Uses SDL's platform names, such as "Windows", "Mac OS X", "Linux", and "Android"
+    pub fn platform(&self) -> Option<&str> {
+        self.platform.as_deref()
+    }
+
+    /// Mappings without a platform apply to every platform
+    pub fn supports_platform(&self, platform: &str) -> bool {
+        self.platform.as_deref().is_none_or(|p| p == platform)
+    }
+
+    /// The device input bound to an SDL button or axis name, such as "a" -> "b0"
+    pub fn binding(&self, name: &str) -> Option<&str> {
+        self.bindings
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Finds the button bound to a device button index
+    pub fn button_for_index(&self, index: u32) -> Option<Button> {
+        let target = format!("b{index}");
+
+        self.bindings
+            .iter()
+            .find(|(_, value)| *value == target)
+            .and_then(|(key, _)| translate_sdl_button_name(key))
+    }
+}
+
+fn translate_sdl_button_name(name: &str) -> Option<Button> {
+    let button = match name {
+        "a" => Button::A,
+        "b" => Button::B,
+        "x" => Button::X,
+        "y" => Button::Y,
+        "back" => Button::Select,
+        "guide" => Button::Meta,
+        "start" => Button::Start,
+        "leftstick" => Button::LeftStick,
+        "rightstick" => Button::RightStick,
+        "leftshoulder" => Button::LeftShoulder,
+        "rightshoulder" => Button::RightShoulder,
+        "lefttrigger" => Button::LeftTrigger,
+        "righttrigger" => Button::RightTrigger,
+        "dpup" => Button::DPadUp,
+        "dpdown" => Button::DPadDown,
+        "dpleft" => Button::DPadLeft,
+        "dpright" => Button::DPadRight,
+        "paddle1" => Button::Paddle1,
+        "paddle2" => Button::Paddle2,
+        "paddle3" => Button::Paddle3,
+        "paddle4" => Button::Paddle4,
+        _ => return None,
+    };
+
+    Some(button)
+}
+
+/// Mappings in the SDL `gamecontrollerdb.txt` format, see https://github.com/mdqinc/SDL_GameControllerDB
+#[derive(Debug, Default, Clone)]
+pub struct ControllerMappingDatabase {
+    mappings: Vec<ControllerMapping>,
+}
+
+impl ControllerMappingDatabase {
+    pub fn parse(text: &str) -> Self {
+        let mut database = Self::default();
+        database.add(text);
+        database
+    }
+
+    /// Mappings replace existing mappings with the same GUID and platform
+    pub fn add(&mut self, text: &str) {
+        for mapping in text.lines().filter_map(ControllerMapping::parse) {
+            self.mappings
+                .retain(|m| m.guid != mapping.guid || m.platform != mapping.platform);
+
+            self.mappings.push(mapping);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ControllerMapping> {
+        self.mappings.iter()
+    }
+
+    /// Prefers an exact GUID match, falling back to a mapping for the same vendor and product
+    pub fn find(&self, guid: &str, platform: &str) -> Option<&ControllerMapping> {
+        let guid = guid.to_lowercase();
+        let mut candidates = self
+            .mappings
+            .iter()
+            .rev()
+            .filter(|mapping| mapping.supports_platform(platform));
+
+        if let Some(mapping) = candidates.clone().find(|mapping| mapping.guid == guid) {
+            return Some(mapping);
+        }
+
+        let ids = parse_sdl_guid(&guid)?;
+
+        candidates.find(|mapping| parse_sdl_guid(&mapping.guid) == Some(ids))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::create_sdl_guid;
+
+    const DB: &str = "
+# Linux
+030000005e0400008e02000010010000,Xbox 360 Controller,a:b0,b:b1,back:b6,dpup:h0.1,leftx:a0,platform:Linux,
+03000000790000000600000000000000,Generic USB Joystick,a:b2,b:b1,x:b3,y:b0,platform:Android,
+";
+
+    #[test]
+    fn find_mapping() {
+        let database = ControllerMappingDatabase::parse(DB);
+        assert_eq!(database.iter().count(), 2);
+
+        // matches vendor and product when the version differs
+        let guid = create_sdl_guid(0x045e, 0x028e);
+        let mapping = database.find(&guid, "Linux").unwrap();
+        assert_eq!(mapping.name(), "Xbox 360 Controller");
+        assert_eq!(mapping.binding("leftx"), Some("a0"));
+        assert_eq!(mapping.button_for_index(6), Some(Button::Select));
+        assert!(database.find(&guid, "Windows").is_none());
+
+        let guid = create_sdl_guid(0x0079, 0x0006);
+        let mapping = database.find(&guid, "Android").unwrap();
+        assert_eq!(mapping.button_for_index(2), Some(Button::A));
+        assert_eq!(mapping.button_for_index(9), None);
+    }
+}
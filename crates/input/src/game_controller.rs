@@ -1,10 +1,8 @@
+use crate::{ControllerConfig, ControllerInfo, ControllerLayout, PowerInfo, StickDirectionMode};
 use math::*;
 use std::time::Duration;
 pub use strum::{EnumString, IntoStaticStr};
 
-const STICK_DEADZONE: f32 = 0.1;
-const STICK_BUTTON_DEADZONE: f32 = 0.35;
-
 #[derive(EnumString, IntoStaticStr, Debug, PartialEq, Eq, Copy, Clone, Hash)]
 pub enum AnalogAxis {
     DPadX,
@@ -72,17 +70,30 @@ pub trait RumblePack {
     fn rumble(&self, weak: f32, strong: f32, duration: Duration);
 }
 
+/// Handle to the backend's device, used for querying live device state
+pub trait ControllerDevice {
+    fn power_info(&self) -> PowerInfo;
+}
+
 pub struct GameController {
     id: usize,
     rumble_pack: Box<dyn RumblePack>,
+    device: Option<Box<dyn ControllerDevice>>,
+    info: ControllerInfo,
     latest_button: Option<Button>,
     previous_buttons: Vec<Button>,
     pressed_buttons: Vec<Button>,
+    config: ControllerConfig,
 
     raw_left_stick_x: f32,
     raw_left_stick_y: f32,
     raw_right_stick_x: f32,
     raw_right_stick_y: f32,
+    raw_left_trigger: f32,
+    raw_right_trigger: f32,
+    /// Set once an axis event arrives, digital only triggers are left to button events
+    left_trigger_analog: bool,
+    right_trigger_analog: bool,
 
     left_stick_x: f32,
     left_stick_y: f32,
@@ -97,14 +108,21 @@ impl GameController {
         Self {
             id,
             rumble_pack,
+            device: None,
+            info: ControllerInfo::default(),
             latest_button: None,
             previous_buttons: Vec::new(),
             pressed_buttons: Vec::new(),
+            config: ControllerConfig::default(),
 
             raw_left_stick_x: 0.0,
             raw_left_stick_y: 0.0,
             raw_right_stick_x: 0.0,
             raw_right_stick_y: 0.0,
+            raw_left_trigger: 0.0,
+            raw_right_trigger: 0.0,
+            left_trigger_analog: false,
+            right_trigger_analog: false,
 
             left_stick_x: 0.0,
             left_stick_y: 0.0,
@@ -119,6 +137,47 @@ impl GameController {
         self.id
     }
 
+    pub fn info(&self) -> &ControllerInfo {
+        &self.info
+    }
+
+    pub fn set_info(&mut self, info: ControllerInfo) {
+        self.info = info;
+    }
+
+    pub fn name(&self) -> &str {
+        &self.info.name
+    }
+
+    pub fn layout(&self) -> ControllerLayout {
+        self.info.layout
+    }
+
+    pub fn power_info(&self) -> PowerInfo {
+        self.device
+            .as_ref()
+            .map(|device| device.power_info())
+            .unwrap_or_default()
+    }
+
+    pub fn set_device(&mut self, device: Option<Box<dyn ControllerDevice>>) {
+        self.device = device;
+    }
+
+    /// Used when a backend reconnects a controller with the same id
+    pub fn set_rumble_pack(&mut self, rumble_pack: Box<dyn RumblePack>) {
+        self.rumble_pack = rumble_pack;
+    }
+
+    pub fn config(&self) -> &ControllerConfig {
+        &self.config
+    }
+
+    /// Changes apply on the next call to update_sticks
+    pub fn set_config(&mut self, config: ControllerConfig) {
+        self.config = config;
+    }
+
     pub fn rumble(&self, weak: f32, strong: f32, duration: Duration) {
         self.rumble_pack.rumble(weak, strong, duration);
     }
@@ -207,22 +266,12 @@ impl GameController {
             }
             AnalogAxis::DPadY => self.update_axis_buttons(Button::DPadUp, Button::DPadDown, value),
             AnalogAxis::LeftTrigger => {
-                self.left_trigger = if value < STICK_DEADZONE { 0.0 } else { value };
-
-                if self.left_trigger > 0.0 {
-                    self.simulate_button_press(Button::LeftTrigger);
-                } else {
-                    self.simulate_button_release(Button::LeftTrigger);
-                }
+                self.raw_left_trigger = value;
+                self.left_trigger_analog = true;
             }
             AnalogAxis::RightTrigger => {
-                self.right_trigger = if value < STICK_DEADZONE { 0.0 } else { value };
-
-                if self.right_trigger > 0.0 {
-                    self.simulate_button_press(Button::RightTrigger);
-                } else {
-                    self.simulate_button_release(Button::RightTrigger);
-                }
+                self.raw_right_trigger = value;
+                self.right_trigger_analog = true;
             }
             AnalogAxis::LeftStickX => {
                 self.raw_left_stick_x = value;
@@ -239,57 +288,102 @@ impl GameController {
         }
     }
 
+    /// Applies the config to the latest axis values, also updates triggers
     pub fn update_sticks(&mut self) {
         // left stick
-        (self.left_stick_x, self.left_stick_y) =
-            self.apply_deadzone(STICK_DEADZONE, self.raw_left_stick_x, self.raw_left_stick_y);
-
-        let (button_x, button_y) = self.apply_deadzone(
-            STICK_BUTTON_DEADZONE,
-            self.raw_left_stick_x,
-            self.raw_left_stick_y,
+        let raw_left_stick = Vec2::new(self.raw_left_stick_x, self.raw_left_stick_y);
+        let left_stick_config = &self.config.left_stick;
+
+        (self.left_stick_x, self.left_stick_y) = left_stick_config.apply(raw_left_stick).into();
+
+        self.update_stick_buttons(
+            raw_left_stick,
+            left_stick_config.button_deadzone,
+            left_stick_config.direction_mode,
+            [
+                Button::LeftStickLeft,
+                Button::LeftStickRight,
+                Button::LeftStickDown,
+                Button::LeftStickUp,
+            ],
         );
 
-        self.axis_simulate_button(
-            button_x,
-            button_y,
-            Button::LeftStickLeft,
-            Button::LeftStickRight,
+        // right stick
+        let raw_right_stick = Vec2::new(self.raw_right_stick_x, self.raw_right_stick_y);
+        let right_stick_config = &self.config.right_stick;
+
+        (self.right_stick_x, self.right_stick_y) = right_stick_config.apply(raw_right_stick).into();
+
+        self.update_stick_buttons(
+            raw_right_stick,
+            right_stick_config.button_deadzone,
+            right_stick_config.direction_mode,
+            [
+                Button::RightStickLeft,
+                Button::RightStickRight,
+                Button::RightStickDown,
+                Button::RightStickUp,
+            ],
         );
 
-        self.axis_simulate_button(
-            button_y,
-            button_x,
-            Button::LeftStickDown,
-            Button::LeftStickUp,
-        );
+        // triggers
+        let left_trigger_config = &self.config.left_trigger;
+        self.left_trigger = left_trigger_config.apply(self.raw_left_trigger);
 
-        // right stick
-        (self.right_stick_x, self.right_stick_y) = self.apply_deadzone(
-            STICK_DEADZONE,
-            self.raw_right_stick_x,
-            self.raw_right_stick_y,
-        );
+        if self.left_trigger_analog {
+            let left_pressed = self.raw_left_trigger >= left_trigger_config.button_threshold;
+            self.update_button(Button::LeftTrigger, left_pressed);
+        }
 
-        let (button_x, button_y) = self.apply_deadzone(
-            STICK_BUTTON_DEADZONE,
-            self.raw_right_stick_x,
-            self.raw_right_stick_y,
-        );
+        let right_trigger_config = &self.config.right_trigger;
+        self.right_trigger = right_trigger_config.apply(self.raw_right_trigger);
 
-        self.axis_simulate_button(
-            button_x,
-            button_y,
-            Button::RightStickLeft,
-            Button::RightStickRight,
-        );
+        if self.right_trigger_analog {
+            let right_pressed = self.raw_right_trigger >= right_trigger_config.button_threshold;
+            self.update_button(Button::RightTrigger, right_pressed);
+        }
+    }
 
-        self.axis_simulate_button(
-            button_y,
-            button_x,
-            Button::RightStickDown,
-            Button::RightStickUp,
-        );
+    fn update_button(&mut self, button: Button, pressed: bool) {
+        if pressed {
+            self.simulate_button_press(button);
+        } else {
+            self.simulate_button_release(button);
+        }
+    }
+
+    /// Buttons are ordered as left, right, down, up
+    fn update_stick_buttons(
+        &mut self,
+        raw: Vec2,
+        deadzone: f32,
+        direction_mode: StickDirectionMode,
+        buttons: [Button; 4],
+    ) {
+        let [left, right, down, up] = buttons;
+
+        let value = if raw.length() < deadzone {
+            Vec2::ZERO
+        } else {
+            raw
+        };
+
+        match direction_mode {
+            StickDirectionMode::EightWay => {
+                self.axis_simulate_button(value.x, value.y, left, right);
+                self.axis_simulate_button(value.y, value.x, down, up);
+            }
+            StickDirectionMode::FourWay => {
+                // only the dominant axis presses a button
+                if value.x.abs() >= value.y.abs() {
+                    self.axis_simulate_button(value.x, 0.0, left, right);
+                    self.axis_simulate_button(0.0, 0.0, down, up);
+                } else {
+                    self.axis_simulate_button(0.0, 0.0, left, right);
+                    self.axis_simulate_button(value.y, 0.0, down, up);
+                }
+            }
+        }
     }
 
     fn axis_simulate_button(&mut self, value: f32, other: f32, low: Button, high: Button) {
@@ -311,26 +405,69 @@ impl GameController {
         }
     }
 
-    fn apply_deadzone(&self, deadzone: f32, x: f32, y: f32) -> (f32, f32) {
-        if deadzone == 0.0 {
-            return (x, y);
-        }
+    pub fn flush(&mut self) {
+        self.previous_buttons.clone_from(&self.pressed_buttons);
+        self.latest_button = None;
+    }
+}
 
-        let v = Vec2::new(x, y);
+#[cfg(test)]
+mod test {
+    use super::*;
 
-        let length = v.length().min(1.0);
+    struct NoRumble;
 
-        if length < deadzone {
-            return (0.0, 0.0);
-        }
+    impl RumblePack for NoRumble {
+        fn rumble(&self, _weak: f32, _strong: f32, _duration: Duration) {}
+    }
+
+    #[test]
+    fn digital_triggers() {
+        let mut controller = GameController::new(0, Box::new(NoRumble));
+
+        controller.simulate_button_press(Button::LeftTrigger);
+        controller.update_sticks();
 
-        let norm = inverse_lerp!(deadzone, 1.0, length) / length;
+        assert!(controller.is_button_down(Button::LeftTrigger));
+        assert!(controller.was_button_just_pressed(Button::LeftTrigger));
 
-        (v * norm).into()
+        controller.flush();
+        controller.simulate_button_release(Button::LeftTrigger);
+        controller.update_sticks();
+
+        assert!(controller.was_button_released(Button::LeftTrigger));
     }
 
-    pub fn flush(&mut self) {
-        self.previous_buttons.clone_from(&self.pressed_buttons);
-        self.latest_button = None;
+    #[test]
+    fn analog_triggers() {
+        let mut controller = GameController::new(0, Box::new(NoRumble));
+
+        controller.simulate_axis_movement(AnalogAxis::RightTrigger, 1.0);
+        controller.update_sticks();
+
+        assert!(controller.was_button_just_pressed(Button::RightTrigger));
+
+        controller.flush();
+        controller.simulate_axis_movement(AnalogAxis::RightTrigger, 0.0);
+        controller.update_sticks();
+
+        assert!(controller.was_button_released(Button::RightTrigger));
+    }
+
+    #[test]
+    fn power_info() {
+        struct Battery;
+
+        impl ControllerDevice for Battery {
+            fn power_info(&self) -> PowerInfo {
+                PowerInfo::Charging(50)
+            }
+        }
+
+        let mut controller = GameController::new(0, Box::new(NoRumble));
+        assert_eq!(controller.power_info(), PowerInfo::Unknown);
+
+        controller.set_device(Some(Box::new(Battery)));
+        assert_eq!(controller.power_info(), PowerInfo::Charging(50));
     }
 }
@@ -0,0 +1,460 @@
+use crate::{Touch, TouchPhase};
+use math::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Positions and vectors use the same space as `Touch::position`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Gesture {
+    Tap {
+        position: Vec2,
+    },
+    /// The first tap is also reported as a `Gesture::Tap`
+    DoubleTap {
+        position: Vec2,
+    },
+    /// Reported once while the touch is held in place
+    LongPress {
+        position: Vec2,
+    },
+    Swipe {
+        direction: SwipeDirection,
+        start: Vec2,
+        end: Vec2,
+        /// Units per second
+        velocity: Vec2,
+    },
+    /// Reported every tick while a single touch is dragged
+    Pan {
+        position: Vec2,
+        delta: Vec2,
+    },
+    Pinch {
+        center: Vec2,
+        /// Change in distance between the touches since the last tick, above 1.0 when spreading apart
+        scale: f32,
+    },
+    Rotate {
+        center: Vec2,
+        /// Change in radians since the last tick, positive values are counter clockwise
+        angle: f32,
+    },
+}
+
+/// Distances are fractions of the render's height, which keeps thresholds consistent across aspect ratios
+#[derive(Debug, Clone)]
+pub struct GestureConfig {
+    /// Touches that move further than this are no longer taps or long presses
+    pub tap_max_distance: f32,
+    pub tap_max_duration: Duration,
+    /// Max time between the end of the first tap and the end of the second tap
+    pub double_tap_max_interval: Duration,
+    pub long_press_duration: Duration,
+    pub swipe_min_distance: f32,
+    /// Fractions of the render's height per second
+    pub swipe_min_speed: f32,
+    /// Scale change required before pinch gestures are reported, 0.1 requires 10% growth or shrinking
+    pub pinch_threshold: f32,
+    /// Rotation in radians required before rotate gestures are reported
+    pub rotate_threshold: f32,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            tap_max_distance: 0.03,
+            tap_max_duration: Duration::from_millis(300),
+            double_tap_max_interval: Duration::from_millis(300),
+            long_press_duration: Duration::from_millis(500),
+            swipe_min_distance: 0.1,
+            swipe_min_speed: 0.5,
+            pinch_threshold: 0.05,
+            rotate_threshold: 0.1,
+        }
+    }
+}
+
+struct TrackedTouch {
+    id: u64,
+    start_position: Vec2,
+    start_instant: Instant,
+    position: Vec2,
+    moved: bool,
+    long_pressed: bool,
+    /// Touches that were ever part of a multi touch gesture don't create single touch gestures
+    multi_touch: bool,
+}
+
+struct TwoTouchState {
+    ids: (u64, u64),
+    start_distance: f32,
+    start_angle: f32,
+    distance: f32,
+    angle: f32,
+    pinching: bool,
+    rotating: bool,
+}
+
+/// Creates gestures from touches, see `GameInputManager::gestures()`
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    aspect_ratio: f32,
+    touches: Vec<TrackedTouch>,
+    two_touch_state: Option<TwoTouchState>,
+    last_tap: Option<(Instant, Vec2)>,
+    gestures: Vec<Gesture>,
+}
+
+impl Default for GestureRecognizer {
+    fn default() -> Self {
+        Self::new(GestureConfig::default())
+    }
+}
+
+impl GestureRecognizer {
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            aspect_ratio: 1.0,
+            touches: Vec::new(),
+            two_touch_state: None,
+            last_tap: None,
+            gestures: Vec::new(),
+        }
+    }
+
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    pub fn set_config(&mut self, config: GestureConfig) {
+        self.config = config;
+    }
+
+    /// Gestures recognized in the latest update
+    pub fn gestures(&self) -> &[Gesture] {
+        &self.gestures
+    }
+
+    /// Should be called every tick, even when there are no touches, to detect long presses
+    pub fn update(&mut self, touches: &[Touch], aspect_ratio: f32, now: Instant) {
+        self.gestures.clear();
+        self.aspect_ratio = aspect_ratio;
+
+        for touch in touches {
+            self.update_touch(touch, now);
+        }
+
+        // touches can disappear without ending, such as when they're claimed by virtual controls
+        self.touches.retain(|tracked| {
+            touches
+                .iter()
+                .any(|touch| touch.id == tracked.id && touch.phase != TouchPhase::End)
+        });
+
+        self.update_two_touch_gestures();
+    }
+
+    fn update_touch(&mut self, touch: &Touch, now: Instant) {
+        let index = match self.touches.iter().position(|t| t.id == touch.id) {
+            Some(index) => index,
+            None if touch.phase == TouchPhase::Cancelled => return,
+            None => {
+                // touches can start and end within the same tick
+                self.touches.push(TrackedTouch {
+                    id: touch.id,
+                    start_position: touch.position,
+                    start_instant: now,
+                    position: touch.position,
+                    moved: false,
+                    long_pressed: false,
+                    multi_touch: false,
+                });
+
+                self.touches.len() - 1
+            }
+        };
+
+        if self.touches.len() > 1 {
+            for tracked in &mut self.touches {
+                tracked.multi_touch = true;
+            }
+        }
+
+        let aspect_ratio = self.aspect_ratio;
+        let config = &self.config;
+        let tracked = &mut self.touches[index];
+
+        let previous_position = tracked.position;
+        tracked.position = touch.position;
+
+        let offset = to_height_space(tracked.position - tracked.start_position, aspect_ratio);
+        tracked.moved |= offset.length() > config.tap_max_distance;
+
+        if tracked.multi_touch || touch.phase == TouchPhase::Cancelled {
+            return;
+        }
+
+        let elapsed = now.saturating_duration_since(tracked.start_instant);
+
+        if touch.phase != TouchPhase::End {
+            if tracked.moved {
+                self.gestures.push(Gesture::Pan {
+                    position: tracked.position,
+                    delta: tracked.position - previous_position,
+                });
+            } else if !tracked.long_pressed && elapsed >= config.long_press_duration {
+                tracked.long_pressed = true;
+
+                self.gestures.push(Gesture::LongPress {
+                    position: tracked.position,
+                });
+            }
+
+            return;
+        }
+
+        if !tracked.moved {
+            if !tracked.long_pressed && elapsed <= config.tap_max_duration {
+                let position = tracked.position;
+                self.push_tap(position, now);
+            }
+
+            return;
+        }
+
+        let seconds = elapsed.as_secs_f32().max(f32::EPSILON);
+        let speed = offset.length() / seconds;
+
+        if offset.length() >= config.swipe_min_distance && speed >= config.swipe_min_speed {
+            let delta = tracked.position - tracked.start_position;
+
+            let direction = if offset.x.abs() >= offset.y.abs() {
+                if offset.x < 0.0 {
+                    SwipeDirection::Left
+                } else {
+                    SwipeDirection::Right
+                }
+            } else if offset.y < 0.0 {
+                SwipeDirection::Down
+            } else {
+                SwipeDirection::Up
+            };
+
+            self.gestures.push(Gesture::Swipe {
+                direction,
+                start: tracked.start_position,
+                end: tracked.position,
+                velocity: delta / seconds,
+            });
+        }
+    }
+
+    fn push_tap(&mut self, position: Vec2, now: Instant) {
+        self.gestures.push(Gesture::Tap { position });
+
+        let is_double_tap = self.last_tap.is_some_and(|(instant, last_position)| {
+            let offset = to_height_space(position - last_position, self.aspect_ratio);
+
+            now.saturating_duration_since(instant) <= self.config.double_tap_max_interval
+                && offset.length() <= self.config.tap_max_distance * 2.0
+        });
+
+        if is_double_tap {
+            self.gestures.push(Gesture::DoubleTap { position });
+            self.last_tap = None;
+        } else {
+            self.last_tap = Some((now, position));
+        }
+    }
+
+    fn update_two_touch_gestures(&mut self) {
+        let [a, b] = &self.touches[..] else {
+            self.two_touch_state = None;
+            return;
+        };
+
+        let ids = (a.id, b.id);
+        let offset = to_height_space(b.position - a.position, self.aspect_ratio);
+        let center = (a.position + b.position) * 0.5;
+        let distance = offset.length();
+        let angle = offset.y.atan2(offset.x);
+
+        let Some(state) = &mut self.two_touch_state else {
+            self.two_touch_state = Some(TwoTouchState {
+                ids,
+                start_distance: distance,
+                start_angle: angle,
+                distance,
+                angle,
+                pinching: false,
+                rotating: false,
+            });
+            return;
+        };
+
+        if state.ids != ids {
+            self.two_touch_state = None;
+            return self.update_two_touch_gestures();
+        }
+
+        if state.start_distance > 0.0 && state.distance > 0.0 {
+            let total_scale = distance / state.start_distance;
+            state.pinching |= (total_scale - 1.0).abs() >= self.config.pinch_threshold;
+
+            if state.pinching && distance != state.distance {
+                self.gestures.push(Gesture::Pinch {
+                    center,
+                    scale: distance / state.distance,
+                });
+            }
+        }
+
+        let total_angle = wrap_angle(angle - state.start_angle);
+        state.rotating |= total_angle.abs() >= self.config.rotate_threshold;
+
+        let angle_delta = wrap_angle(angle - state.angle);
+
+        if state.rotating && angle_delta != 0.0 {
+            self.gestures.push(Gesture::Rotate {
+                center,
+                angle: angle_delta,
+            });
+        }
+
+        state.distance = distance;
+        state.angle = angle;
+    }
+}
+
+/// Converts a vector from `Touch::position` space to fractions of the render's height
+pub(crate) fn to_height_space(value: Vec2, aspect_ratio: f32) -> Vec2 {
+    Vec2::new(value.x * aspect_ratio, value.y) * 0.5
+}
+
+/// Converts a vector from fractions of the render's height to `Touch::position` space
+pub(crate) fn from_height_space(value: Vec2, aspect_ratio: f32) -> Vec2 {
+    Vec2::new(value.x / aspect_ratio, value.y) * 2.0
+}
+
+fn wrap_angle(angle: f32) -> f32 {
+    use std::f32::consts::{PI, TAU};
+
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn touch(id: u64, phase: TouchPhase, position: Vec2) -> Touch {
+        Touch {
+            id,
+            phase,
+            position,
+            pressure: None,
+        }
+    }
+
+    #[test]
+    fn taps_and_swipes() {
+        let mut recognizer = GestureRecognizer::default();
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+
+        recognizer.update(&[touch(0, TouchPhase::Start, Vec2::ZERO)], 1.0, ms(0));
+        recognizer.update(&[touch(0, TouchPhase::End, Vec2::ZERO)], 1.0, ms(50));
+        assert_eq!(
+            recognizer.gestures(),
+            [Gesture::Tap {
+                position: Vec2::ZERO
+            }]
+        );
+
+        recognizer.update(&[touch(1, TouchPhase::Start, Vec2::ZERO)], 1.0, ms(100));
+        recognizer.update(&[touch(1, TouchPhase::End, Vec2::ZERO)], 1.0, ms(150));
+        assert!(recognizer.gestures().contains(&Gesture::DoubleTap {
+            position: Vec2::ZERO
+        }));
+
+        let end = Vec2::new(-1.0, 0.1);
+        recognizer.update(&[touch(2, TouchPhase::Start, Vec2::ZERO)], 1.0, ms(1000));
+        recognizer.update(&[touch(2, TouchPhase::Moving, end)], 1.0, ms(1100));
+        assert!(matches!(recognizer.gestures(), [Gesture::Pan { .. }]));
+
+        recognizer.update(&[touch(2, TouchPhase::End, end)], 1.0, ms(1200));
+        assert!(matches!(
+            recognizer.gestures(),
+            [Gesture::Swipe {
+                direction: SwipeDirection::Left,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn pinch_and_rotate() {
+        let mut recognizer = GestureRecognizer::default();
+        let now = Instant::now();
+
+        let a = Vec2::new(-0.5, 0.0);
+        let b = Vec2::new(0.5, 0.0);
+
+        recognizer.update(
+            &[
+                touch(0, TouchPhase::Start, a),
+                touch(1, TouchPhase::Start, b),
+            ],
+            1.0,
+            now,
+        );
+        assert!(recognizer.gestures().is_empty());
+
+        recognizer.update(
+            &[
+                touch(0, TouchPhase::Moving, a * 2.0),
+                touch(1, TouchPhase::Moving, b * 2.0),
+            ],
+            1.0,
+            now,
+        );
+
+        let [Gesture::Pinch { scale, .. }] = recognizer.gestures() else {
+            panic!("expected a pinch: {:?}", recognizer.gestures());
+        };
+        assert!((scale - 2.0).abs() < 0.0001);
+
+        // rotate a quarter turn counter clockwise
+        recognizer.update(
+            &[
+                touch(0, TouchPhase::Moving, Vec2::new(0.0, -1.0)),
+                touch(1, TouchPhase::Moving, Vec2::new(0.0, 1.0)),
+            ],
+            1.0,
+            now,
+        );
+
+        let angle = recognizer
+            .gestures()
+            .iter()
+            .find_map(|gesture| match gesture {
+                Gesture::Rotate { angle, .. } => Some(*angle),
+                _ => None,
+            });
+        assert!((angle.unwrap() - std::f32::consts::FRAC_PI_2).abs() < 0.0001);
+
+        // multi touch gestures don't create taps
+        recognizer.update(
+            &[touch(0, TouchPhase::End, a), touch(1, TouchPhase::End, b)],
+            1.0,
+            now,
+        );
+        assert!(recognizer.gestures().is_empty());
+    }
+}
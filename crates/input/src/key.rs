@@ -145,3 +145,90 @@ pub enum Key {
     Stop, // not in winit
     Plus, // not in winit
 }
+
+impl Key {
+    /// Translates characters produced by a keyboard layout, letters are case insensitive
+    pub fn from_char(c: char) -> Option<Key> {
+        let key = match c.to_ascii_uppercase() {
+            'A' => Key::A,
+            'B' => Key::B,
+            'C' => Key::C,
+            'D' => Key::D,
+            'E' => Key::E,
+            'F' => Key::F,
+            'G' => Key::G,
+            'H' => Key::H,
+            'I' => Key::I,
+            'J' => Key::J,
+            'K' => Key::K,
+            'L' => Key::L,
+            'M' => Key::M,
+            'N' => Key::N,
+            'O' => Key::O,
+            'P' => Key::P,
+            'Q' => Key::Q,
+            'R' => Key::R,
+            'S' => Key::S,
+            'T' => Key::T,
+            'U' => Key::U,
+            'V' => Key::V,
+            'W' => Key::W,
+            'X' => Key::X,
+            'Y' => Key::Y,
+            'Z' => Key::Z,
+            '1' => Key::Key1,
+            '2' => Key::Key2,
+            '3' => Key::Key3,
+            '4' => Key::Key4,
+            '5' => Key::Key5,
+            '6' => Key::Key6,
+            '7' => Key::Key7,
+            '8' => Key::Key8,
+            '9' => Key::Key9,
+            '0' => Key::Key0,
+            '`' => Key::Backquote,
+            '-' => Key::Minus,
+            '=' => Key::Equal,
+            '[' => Key::LBracket,
+            ']' => Key::RBracket,
+            ':' => Key::Colon,
+            ';' => Key::Semicolon,
+            '\'' => Key::Apostrophe,
+            ',' => Key::Comma,
+            '.' => Key::Period,
+            '/' => Key::Slash,
+            '\\' => Key::Backslash,
+            '^' => Key::Caret,
+            '*' => Key::Asterisk,
+            '@' => Key::At,
+            '+' => Key::Plus,
+            ' ' => Key::Space,
+            '\t' => Key::Tab,
+            '\r' | '\n' => Key::Return,
+            _ => return None,
+        };
+
+        Some(key)
+    }
+}
+
+/// A key reported by both its position on the keyboard and its meaning in the active layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyInput {
+    /// The key found at the same position on a US QWERTY keyboard, useful for movement controls
+    pub physical: Key,
+    /// The key after applying the keyboard layout, useful for shortcuts
+    pub logical: Key,
+}
+
+impl KeyInput {
+    pub fn new(physical: Key, logical: Key) -> Self {
+        Self { physical, logical }
+    }
+}
+
+impl From<Key> for KeyInput {
+    fn from(key: Key) -> Self {
+        Self::new(key, key)
+    }
+}
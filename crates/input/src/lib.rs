@@ -1,9 +1,23 @@
+mod controller_config;
+mod controller_info;
+mod controller_mapping;
 mod game_controller;
+mod gesture;
 mod key;
+mod modifiers;
 mod mouse;
+mod shortcut;
 mod touch;
+mod virtual_controls;
 
+pub use controller_config::*;
+pub use controller_info::*;
+pub use controller_mapping::*;
 pub use game_controller::*;
+pub use gesture::*;
 pub use key::*;
+pub use modifiers::*;
 pub use mouse::*;
+pub use shortcut::*;
 pub use touch::*;
+pub use virtual_controls::*;
@@ -0,0 +1,86 @@
+use crate::Key;
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
+/// A set of modifier keys, left and right keys are treated as the same modifier
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Modifiers(u8);
+
+impl Modifiers {
+    pub const NONE: Modifiers = Modifiers(0);
+    pub const SHIFT: Modifiers = Modifiers(1);
+    pub const CONTROL: Modifiers = Modifiers(1 << 1);
+    pub const ALT: Modifiers = Modifiers(1 << 2);
+    /// The Windows, Command, or Super key
+    pub const META: Modifiers = Modifiers(1 << 3);
+
+    /// Returns `Modifiers::NONE` for keys that aren't modifiers
+    pub fn from_key(key: Key) -> Modifiers {
+        match key {
+            Key::LShift | Key::RShift => Modifiers::SHIFT,
+            Key::LControl | Key::RControl => Modifiers::CONTROL,
+            Key::LAlt | Key::RAlt => Modifiers::ALT,
+            Key::LMeta | Key::RMeta => Modifiers::META,
+            _ => Modifiers::NONE,
+        }
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, other: Modifiers) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Modifiers) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Modifiers) {
+        self.0 &= !other.0;
+    }
+
+    pub fn shift(self) -> bool {
+        self.contains(Modifiers::SHIFT)
+    }
+
+    pub fn control(self) -> bool {
+        self.contains(Modifiers::CONTROL)
+    }
+
+    pub fn alt(self) -> bool {
+        self.contains(Modifiers::ALT)
+    }
+
+    pub fn meta(self) -> bool {
+        self.contains(Modifiers::META)
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Modifiers;
+
+    fn bitor(self, rhs: Modifiers) -> Modifiers {
+        Modifiers(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for Modifiers {
+    fn bitor_assign(&mut self, rhs: Modifiers) {
+        self.insert(rhs);
+    }
+}
+
+impl BitAnd for Modifiers {
+    type Output = Modifiers;
+
+    fn bitand(self, rhs: Modifiers) -> Modifiers {
+        Modifiers(self.0 & rhs.0)
+    }
+}
+
+impl FromIterator<Modifiers> for Modifiers {
+    fn from_iter<T: IntoIterator<Item = Modifiers>>(iter: T) -> Self {
+        iter.into_iter().fold(Modifiers::NONE, BitOr::bitor)
+    }
+}
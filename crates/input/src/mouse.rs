@@ -1,3 +1,5 @@
+use math::Vec2;
+
 #[derive(PartialEq, Eq, Copy, Clone, Debug, Hash)]
 pub enum MouseButton {
     Left,
@@ -5,3 +7,31 @@ pub enum MouseButton {
     Right,
     Other(u16),
 }
+
+/// Positive x scrolls right, positive y scrolls up
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MouseWheelDelta {
+    /// Reported by most mouse wheels
+    Lines(Vec2),
+    /// Reported by touchpads and high precision wheels
+    Pixels(Vec2),
+}
+
+impl MouseWheelDelta {
+    /// Used for converting between lines and pixels
+    pub const PIXELS_PER_LINE: f32 = 20.0;
+
+    pub fn lines(self) -> Vec2 {
+        match self {
+            Self::Lines(lines) => lines,
+            Self::Pixels(pixels) => pixels / Self::PIXELS_PER_LINE,
+        }
+    }
+
+    pub fn pixels(self) -> Vec2 {
+        match self {
+            Self::Lines(lines) => lines * Self::PIXELS_PER_LINE,
+            Self::Pixels(pixels) => pixels,
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{Key, Modifiers};
+use std::fmt;
+use std::str::FromStr;
+
+/// A key pressed while holding an exact set of modifiers, such as `Ctrl+Shift+S`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    pub modifiers: Modifiers,
+    pub key: Key,
+}
+
+impl Shortcut {
+    pub const fn new(modifiers: Modifiers, key: Key) -> Self {
+        Self { modifiers, key }
+    }
+
+    /// Extra modifiers prevent a match, so `Ctrl+Z` won't trigger for `Ctrl+Shift+Z`
+    pub fn matches(&self, modifiers: Modifiers, key: Key) -> bool {
+        self.key == key && self.modifiers == modifiers
+    }
+}
+
+impl From<Key> for Shortcut {
+    fn from(key: Key) -> Self {
+        Self::new(Modifiers::NONE, key)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShortcutParseError {
+    MissingKey,
+    UnknownKey(String),
+    UnknownModifier(String),
+}
+
+impl fmt::Display for ShortcutParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShortcutParseError::MissingKey => write!(f, "Shortcut is missing a key"),
+            ShortcutParseError::UnknownKey(key) => write!(f, "Unknown shortcut key: {key:?}"),
+            ShortcutParseError::UnknownModifier(modifier) => {
+                write!(f, "Unknown shortcut modifier: {modifier:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShortcutParseError {}
+
+/// Accepts modifiers in any order and case, followed by a `Key` name or character: `Ctrl+Shift+S`, `alt+1`, `Ctrl++`
+impl FromStr for Shortcut {
+    type Err = ShortcutParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let (modifiers_text, key_text) = if s == "+" {
+            ("", "+")
+        } else if let Some(modifiers_text) = s.strip_suffix("++") {
+            (modifiers_text, "+")
+        } else {
+            s.rsplit_once('+').unwrap_or(("", s))
+        };
+
+        let modifiers = modifiers_text
+            .split('+')
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(|text| {
+                parse_modifier(text)
+                    .ok_or_else(|| ShortcutParseError::UnknownModifier(text.to_string()))
+            })
+            .collect::<Result<Modifiers, _>>()?;
+
+        let key_text = key_text.trim();
+
+        if key_text.is_empty() {
+            return Err(ShortcutParseError::MissingKey);
+        }
+
+        let key = parse_key(key_text)
+            .ok_or_else(|| ShortcutParseError::UnknownKey(key_text.to_string()))?;
+
+        Ok(Shortcut::new(modifiers, key))
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const NAMES: [(Modifiers, &str); 4] = [
+            (Modifiers::CONTROL, "Ctrl"),
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::META, "Meta"),
+        ];
+
+        for (modifier, name) in NAMES {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        let key_name: &'static str = self.key.into();
+
+        match self.key {
+            Key::Plus => write!(f, "+"),
+            // Key1 -> 1
+            _ => write!(f, "{}", key_name.strip_prefix("Key").unwrap_or(key_name)),
+        }
+    }
+}
+
+fn parse_modifier(text: &str) -> Option<Modifiers> {
+    let modifier = match text.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Modifiers::CONTROL,
+        "shift" => Modifiers::SHIFT,
+        "alt" | "option" => Modifiers::ALT,
+        "meta" | "super" | "cmd" | "command" | "win" => Modifiers::META,
+        _ => return None,
+    };
+
+    Some(modifier)
+}
+
+fn parse_key(text: &str) -> Option<Key> {
+    if let Ok(key) = Key::from_str(text) {
+        return Some(key);
+    }
+
+    let mut chars = text.chars();
+
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Key::from_char(c);
+    }
+
+    match text.to_ascii_lowercase().as_str() {
+        "esc" => Some(Key::Escape),
+        "enter" => Some(Key::Return),
+        "del" => Some(Key::Delete),
+        "ins" => Some(Key::Insert),
+        "space" => Some(Key::Space),
+        "tab" => Some(Key::Tab),
+        "backspace" => Some(Key::Backspace),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let shortcut: Shortcut = "Ctrl+Shift+S".parse().unwrap();
+        assert_eq!(
+            shortcut,
+            Shortcut::new(Modifiers::CONTROL | Modifiers::SHIFT, Key::S)
+        );
+
+        let shortcut: Shortcut = " shift + ctrl + s ".parse().unwrap();
+        assert_eq!(
+            shortcut,
+            Shortcut::new(Modifiers::CONTROL | Modifiers::SHIFT, Key::S)
+        );
+
+        let shortcut: Shortcut = "Ctrl++".parse().unwrap();
+        assert_eq!(shortcut, Shortcut::new(Modifiers::CONTROL, Key::Plus));
+
+        let shortcut: Shortcut = "Alt+Esc".parse().unwrap();
+        assert_eq!(shortcut, Shortcut::new(Modifiers::ALT, Key::Escape));
+
+        assert_eq!(
+            "Ctrl+".parse::<Shortcut>(),
+            Err(ShortcutParseError::MissingKey)
+        );
+        assert_eq!(
+            "Hyper+S".parse::<Shortcut>(),
+            Err(ShortcutParseError::UnknownModifier(String::from("Hyper")))
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let shortcuts = [
+            Shortcut::new(Modifiers::CONTROL | Modifiers::SHIFT, Key::S),
+            Shortcut::new(Modifiers::ALT | Modifiers::META, Key::Key1),
+            Shortcut::new(Modifiers::CONTROL, Key::Plus),
+            Shortcut::from(Key::F5),
+        ];
+
+        for shortcut in shortcuts {
+            assert_eq!(shortcut.to_string().parse(), Ok(shortcut));
+        }
+    }
+}
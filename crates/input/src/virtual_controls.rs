@@ -0,0 +1,337 @@
+use crate::{from_height_space, to_height_space};
+use crate::{AnalogAxis, Button, ControllerInfo, GameController, RumblePack, Touch, TouchPhase};
+use math::*;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VirtualStick {
+    Left,
+    Right,
+    /// Presses the d-pad buttons, diagonals press two buttons
+    DPad,
+}
+
+/// An on screen joystick, positions use the same space as `Touch::position`
+#[derive(Debug, Clone)]
+pub struct VirtualJoystick {
+    pub stick: VirtualStick,
+    /// Resting position of the joystick
+    pub center: Vec2,
+    /// How far the knob can move from the origin, as a fraction of the render's height
+    pub radius: f32,
+    /// Touches starting within this distance of the center control the joystick, as a fraction of the render's height
+    pub activation_radius: f32,
+    /// Moves the origin to where the touch started, instead of using the center
+    pub floating: bool,
+    touch_id: Option<u64>,
+    origin: Vec2,
+    value: Vec2,
+}
+
+impl VirtualJoystick {
+    pub fn new(stick: VirtualStick, center: Vec2, radius: f32) -> Self {
+        Self {
+            stick,
+            center,
+            radius,
+            activation_radius: radius,
+            floating: false,
+            touch_id: None,
+            origin: center,
+            value: Vec2::ZERO,
+        }
+    }
+
+    pub fn with_activation_radius(mut self, activation_radius: f32) -> Self {
+        self.activation_radius = activation_radius;
+        self
+    }
+
+    pub fn with_floating(mut self, floating: bool) -> Self {
+        self.floating = floating;
+        self
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.touch_id.is_some()
+    }
+
+    /// Where the joystick is drawn from, differs from the center while a floating joystick is active
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    /// Where the knob should be drawn
+    pub fn knob_position(&self, aspect_ratio: f32) -> Vec2 {
+        self.origin + from_height_space(self.value * self.radius, aspect_ratio)
+    }
+
+    /// Unprocessed stick input, each axis is in the range [-1.0, 1.0] and positive y is up
+    pub fn value(&self) -> Vec2 {
+        self.value
+    }
+
+    fn owns_touch(&self, id: u64) -> bool {
+        self.touch_id == Some(id)
+    }
+
+    fn update(&mut self, touches: &[Touch], aspect_ratio: f32, is_claimed: impl Fn(u64) -> bool) {
+        let owned_touch = self
+            .touch_id
+            .and_then(|id| touches.iter().find(|touch| touch.id == id))
+            .filter(|touch| !matches!(touch.phase, TouchPhase::End | TouchPhase::Cancelled));
+
+        let touch = owned_touch.or_else(|| {
+            touches.iter().find(|touch| {
+                let offset = to_height_space(touch.position - self.center, aspect_ratio);
+
+                touch.phase == TouchPhase::Start
+                    && !is_claimed(touch.id)
+                    && offset.length() <= self.activation_radius
+            })
+        });
+
+        let Some(touch) = touch else {
+            self.touch_id = None;
+            self.origin = self.center;
+            self.value = Vec2::ZERO;
+            return;
+        };
+
+        if self.touch_id != Some(touch.id) {
+            self.touch_id = Some(touch.id);
+            self.origin = if self.floating {
+                touch.position
+            } else {
+                self.center
+            };
+        }
+
+        let offset = to_height_space(touch.position - self.origin, aspect_ratio);
+
+        self.value = if self.radius > 0.0 {
+            (offset / self.radius).clamp_length_max(1.0)
+        } else {
+            Vec2::ZERO
+        };
+    }
+}
+
+/// An on screen button, positions use the same space as `Touch::position`
+#[derive(Debug, Clone)]
+pub struct VirtualButton {
+    pub button: Button,
+    pub center: Vec2,
+    /// As a fraction of the render's height
+    pub radius: f32,
+    touch_ids: Vec<u64>,
+}
+
+impl VirtualButton {
+    pub fn new(button: Button, center: Vec2, radius: f32) -> Self {
+        Self {
+            button,
+            center,
+            radius,
+            touch_ids: Vec::new(),
+        }
+    }
+
+    pub fn is_pressed(&self) -> bool {
+        !self.touch_ids.is_empty()
+    }
+
+    /// Any touch over the button presses it, allowing fingers to slide between buttons
+    fn update(&mut self, touches: &[Touch], aspect_ratio: f32, is_claimed: impl Fn(u64) -> bool) {
+        self.touch_ids.clear();
+
+        for touch in touches {
+            let offset = to_height_space(touch.position - self.center, aspect_ratio);
+
+            if !matches!(touch.phase, TouchPhase::End | TouchPhase::Cancelled)
+                && !is_claimed(touch.id)
+                && offset.length() <= self.radius
+            {
+                self.touch_ids.push(touch.id);
+            }
+        }
+    }
+}
+
+/// On screen controls that feed into a `GameController`, see `GameInputManager::set_virtual_controller()`
+#[derive(Debug, Default, Clone)]
+pub struct VirtualController {
+    pub joysticks: Vec<VirtualJoystick>,
+    pub buttons: Vec<VirtualButton>,
+}
+
+impl VirtualController {
+    /// The id of the `GameController` fed by the virtual controller
+    pub const CONTROLLER_ID: usize = usize::MAX;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_joystick(mut self, joystick: VirtualJoystick) -> Self {
+        self.joysticks.push(joystick);
+        self
+    }
+
+    pub fn with_button(mut self, button: VirtualButton) -> Self {
+        self.buttons.push(button);
+        self
+    }
+
+    /// Whether a touch is controlling a joystick or pressing a button
+    pub fn owns_touch(&self, touch: &Touch) -> bool {
+        self.joysticks.iter().any(|j| j.owns_touch(touch.id))
+            || self.buttons.iter().any(|b| b.touch_ids.contains(&touch.id))
+    }
+
+    pub fn create_controller(&self) -> GameController {
+        let mut controller = GameController::new(Self::CONTROLLER_ID, Box::new(VirtualRumblePack));
+
+        controller.set_info(ControllerInfo::new(
+            String::from("Virtual Controller"),
+            None,
+            None,
+        ));
+
+        controller
+    }
+
+    pub fn update(&mut self, touches: &[Touch], aspect_ratio: f32) {
+        let mut claimed_touches = Vec::new();
+
+        for joystick in &mut self.joysticks {
+            joystick.update(touches, aspect_ratio, |id| claimed_touches.contains(&id));
+
+            if let Some(id) = joystick.touch_id {
+                claimed_touches.push(id);
+            }
+        }
+
+        for button in &mut self.buttons {
+            button.update(touches, aspect_ratio, |id| claimed_touches.contains(&id));
+        }
+    }
+
+    /// Applies the latest state to the controller, before `GameController::update_sticks()`
+    pub fn apply(&self, controller: &mut GameController) {
+        let mut left_stick = Vec2::ZERO;
+        let mut right_stick = Vec2::ZERO;
+        let mut dpad = Vec2::ZERO;
+
+        for joystick in &self.joysticks {
+            match joystick.stick {
+                VirtualStick::Left => left_stick += joystick.value,
+                VirtualStick::Right => right_stick += joystick.value,
+                VirtualStick::DPad => dpad += joystick.value,
+            }
+        }
+
+        let left_stick = left_stick.clamp_length_max(1.0);
+        let right_stick = right_stick.clamp_length_max(1.0);
+
+        controller.simulate_axis_movement(AnalogAxis::LeftStickX, left_stick.x);
+        controller.simulate_axis_movement(AnalogAxis::LeftStickY, left_stick.y);
+        controller.simulate_axis_movement(AnalogAxis::RightStickX, right_stick.x);
+        controller.simulate_axis_movement(AnalogAxis::RightStickY, right_stick.y);
+
+        const DPAD_DEADZONE: f32 = 0.35;
+        // sin(22.5 degrees), splits the d-pad into eight 45 degree slices
+        const DPAD_RATIO: f32 = 0.383;
+
+        let dpad_length = dpad.length();
+        let dpad_axis = |value: f32| {
+            if dpad_length >= DPAD_DEADZONE && value.abs() >= dpad_length * DPAD_RATIO {
+                value.signum()
+            } else {
+                0.0
+            }
+        };
+
+        controller.simulate_axis_movement(AnalogAxis::DPadX, dpad_axis(dpad.x));
+        // DPadY is positive when pushed down
+        controller.simulate_axis_movement(AnalogAxis::DPadY, -dpad_axis(dpad.y));
+
+        let mut buttons: Vec<(Button, bool)> = Vec::new();
+
+        for virtual_button in &self.buttons {
+            let is_pressed = virtual_button.is_pressed();
+
+            match buttons
+                .iter_mut()
+                .find(|(b, _)| *b == virtual_button.button)
+            {
+                Some((_, pressed)) => *pressed |= is_pressed,
+                None => buttons.push((virtual_button.button, is_pressed)),
+            }
+        }
+
+        for (button, pressed) in buttons {
+            if pressed {
+                controller.simulate_button_press(button);
+            } else {
+                controller.simulate_button_release(button);
+            }
+        }
+    }
+}
+
+struct VirtualRumblePack;
+
+impl RumblePack for VirtualRumblePack {
+    fn rumble(&self, _weak: f32, _strong: f32, _duration: Duration) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn touch(id: u64, phase: TouchPhase, position: Vec2) -> Touch {
+        Touch {
+            id,
+            phase,
+            position,
+            pressure: None,
+        }
+    }
+
+    #[test]
+    fn virtual_controller() {
+        let mut virtual_controller = VirtualController::new()
+            .with_joystick(
+                VirtualJoystick::new(VirtualStick::Left, Vec2::new(-0.5, -0.5), 0.1)
+                    .with_activation_radius(0.15),
+            )
+            .with_button(VirtualButton::new(Button::A, Vec2::new(0.5, -0.5), 0.1));
+
+        let mut controller = virtual_controller.create_controller();
+
+        let touches = [
+            touch(0, TouchPhase::Start, Vec2::new(-0.5, -0.3)),
+            touch(1, TouchPhase::Start, Vec2::new(0.5, -0.5)),
+        ];
+
+        virtual_controller.update(&touches, 1.0);
+        virtual_controller.apply(&mut controller);
+        controller.update_sticks();
+
+        assert!(touches.iter().all(|t| virtual_controller.owns_touch(t)));
+        assert!(controller.axis(AnalogAxis::LeftStickY) > 0.99);
+        assert!(controller.is_button_down(Button::A));
+        assert!(controller.is_button_down(Button::LeftStickUp));
+
+        // the joystick keeps its touch after leaving the activation radius
+        let touches = [touch(0, TouchPhase::Moving, Vec2::new(0.5, -0.5))];
+
+        virtual_controller.update(&touches, 1.0);
+        virtual_controller.apply(&mut controller);
+        controller.update_sticks();
+
+        assert!(controller.axis(AnalogAxis::LeftStickX) > 0.99);
+        assert!(!controller.is_button_down(Button::A));
+    }
+}
@@ -1,4 +1,4 @@
-use super::translate_sdl_key;
+use super::{translate_sdl_key, translate_sdl_scancode};
 use framework_core::runtime::{GameWindowEvent, GameWindowLifecycle, InputEvent};
 use input::*;
 use math::*;
@@ -23,6 +23,7 @@ pub(crate) fn translate_sdl_event(
                 UVec2::new(width as u32, height as u32),
             )),
             SDLWindowEvent::Moved(x, y) => Some(GameWindowEvent::Moved(IVec2::new(x, y))),
+            SDLWindowEvent::DisplayChanged(_) => Some(GameWindowEvent::MonitorChanged),
             _ => None,
         },
         SDLEvent::TextInput {
@@ -111,7 +112,8 @@ pub(crate) fn translate_sdl_event(
             window_id, x, y, ..
         } => {
             if game_window_id == window_id {
-                let position = Vec2::new(x as f32, y as f32);
+                // SDL2 reports screen coordinates, sizes use drawable pixels
+                let position = Vec2::new(x as f32, y as f32) * window.scale_factor();
                 let position = window.normalize_vec2(position);
 
                 Some(InputEvent::MouseMoved(position).into())
@@ -119,6 +121,25 @@ pub(crate) fn translate_sdl_event(
                 None
             }
         }
+        SDLEvent::MouseWheel {
+            window_id,
+            direction,
+            precise_x,
+            precise_y,
+            ..
+        } => {
+            if game_window_id == window_id {
+                let mut delta = Vec2::new(precise_x, precise_y);
+
+                if direction == sdl2::mouse::MouseWheelDirection::Flipped {
+                    delta = -delta;
+                }
+
+                Some(InputEvent::MouseWheel(MouseWheelDelta::Lines(delta)).into())
+            } else {
+                None
+            }
+        }
         SDLEvent::MouseButtonDown {
             window_id,
             mouse_btn,
@@ -142,22 +163,26 @@ pub(crate) fn translate_sdl_event(
             }
         }
         SDLEvent::KeyDown {
-            window_id, keycode, ..
+            window_id,
+            keycode,
+            scancode,
+            ..
         } => {
             if game_window_id == window_id {
-                keycode
-                    .and_then(translate_sdl_key)
+                translate_sdl_key_input(keycode, scancode)
                     .map(|key| GameWindowEvent::InputEvent(InputEvent::KeyDown(key)))
             } else {
                 None
             }
         }
         SDLEvent::KeyUp {
-            window_id, keycode, ..
+            window_id,
+            keycode,
+            scancode,
+            ..
         } => {
             if game_window_id == window_id {
-                keycode
-                    .and_then(translate_sdl_key)
+                translate_sdl_key_input(keycode, scancode)
                     .map(|key| GameWindowEvent::InputEvent(InputEvent::KeyUp(key)))
             } else {
                 None
@@ -201,6 +226,20 @@ pub(crate) fn translate_sdl_event(
     }
 }
 
+fn translate_sdl_key_input(
+    keycode: Option<sdl2::keyboard::Keycode>,
+    scancode: Option<sdl2::keyboard::Scancode>,
+) -> Option<KeyInput> {
+    let logical = keycode.and_then(translate_sdl_key);
+    let physical = scancode.and_then(translate_sdl_scancode);
+
+    match (physical, logical) {
+        (Some(physical), Some(logical)) => Some(KeyInput::new(physical, logical)),
+        (Some(key), None) | (None, Some(key)) => Some(key.into()),
+        (None, None) => None,
+    }
+}
+
 fn convert_button(button: SDLButton) -> Option<Button> {
     match button {
         SDLButton::A => Some(Button::A),
@@ -1,5 +1,6 @@
 use input::*;
 use sdl2::keyboard::Keycode as SDLKeycode;
+use sdl2::keyboard::Scancode as SDLScancode;
 
 pub(super) fn translate_sdl_key(sdl_key: SDLKeycode) -> Option<Key> {
     match sdl_key {
@@ -148,3 +149,147 @@ pub(super) fn translate_sdl_key(sdl_key: SDLKeycode) -> Option<Key> {
         _ => None,
     }
 }
+
+/// Translates by position on a US QWERTY keyboard
+pub(super) fn translate_sdl_scancode(sdl_scancode: SDLScancode) -> Option<Key> {
+    match sdl_scancode {
+        SDLScancode::Grave => Some(Key::Backquote),
+        SDLScancode::Num1 => Some(Key::Key1),
+        SDLScancode::Num2 => Some(Key::Key2),
+        SDLScancode::Num3 => Some(Key::Key3),
+        SDLScancode::Num4 => Some(Key::Key4),
+        SDLScancode::Num5 => Some(Key::Key5),
+        SDLScancode::Num6 => Some(Key::Key6),
+        SDLScancode::Num7 => Some(Key::Key7),
+        SDLScancode::Num8 => Some(Key::Key8),
+        SDLScancode::Num9 => Some(Key::Key9),
+        SDLScancode::Num0 => Some(Key::Key0),
+        SDLScancode::Minus => Some(Key::Minus),
+        SDLScancode::Equals => Some(Key::Equal),
+        SDLScancode::Backspace => Some(Key::Backspace),
+        SDLScancode::Kp1 => Some(Key::Numpad1),
+        SDLScancode::Kp2 => Some(Key::Numpad2),
+        SDLScancode::Kp3 => Some(Key::Numpad3),
+        SDLScancode::Kp4 => Some(Key::Numpad4),
+        SDLScancode::Kp5 => Some(Key::Numpad5),
+        SDLScancode::Kp6 => Some(Key::Numpad6),
+        SDLScancode::Kp7 => Some(Key::Numpad7),
+        SDLScancode::Kp8 => Some(Key::Numpad8),
+        SDLScancode::Kp9 => Some(Key::Numpad9),
+        SDLScancode::Kp0 => Some(Key::Numpad0),
+        SDLScancode::KpPlus => Some(Key::NumpadPlus),
+        SDLScancode::KpDivide => Some(Key::NumpadDivide),
+        SDLScancode::KpDecimal => Some(Key::NumpadDecimal),
+        SDLScancode::KpComma => Some(Key::NumpadComma),
+        SDLScancode::KpEnter => Some(Key::NumpadEnter),
+        SDLScancode::KpEquals => Some(Key::NumpadEqual),
+        SDLScancode::KpMultiply => Some(Key::NumpadMultiply),
+        SDLScancode::KpMinus => Some(Key::NumpadMinus),
+        SDLScancode::A => Some(Key::A),
+        SDLScancode::B => Some(Key::B),
+        SDLScancode::C => Some(Key::C),
+        SDLScancode::D => Some(Key::D),
+        SDLScancode::E => Some(Key::E),
+        SDLScancode::F => Some(Key::F),
+        SDLScancode::G => Some(Key::G),
+        SDLScancode::H => Some(Key::H),
+        SDLScancode::I => Some(Key::I),
+        SDLScancode::J => Some(Key::J),
+        SDLScancode::K => Some(Key::K),
+        SDLScancode::L => Some(Key::L),
+        SDLScancode::M => Some(Key::M),
+        SDLScancode::N => Some(Key::N),
+        SDLScancode::O => Some(Key::O),
+        SDLScancode::P => Some(Key::P),
+        SDLScancode::Q => Some(Key::Q),
+        SDLScancode::R => Some(Key::R),
+        SDLScancode::S => Some(Key::S),
+        SDLScancode::T => Some(Key::T),
+        SDLScancode::U => Some(Key::U),
+        SDLScancode::V => Some(Key::V),
+        SDLScancode::W => Some(Key::W),
+        SDLScancode::X => Some(Key::X),
+        SDLScancode::Y => Some(Key::Y),
+        SDLScancode::Z => Some(Key::Z),
+        SDLScancode::Escape => Some(Key::Escape),
+        SDLScancode::F1 => Some(Key::F1),
+        SDLScancode::F2 => Some(Key::F2),
+        SDLScancode::F3 => Some(Key::F3),
+        SDLScancode::F4 => Some(Key::F4),
+        SDLScancode::F5 => Some(Key::F5),
+        SDLScancode::F6 => Some(Key::F6),
+        SDLScancode::F7 => Some(Key::F7),
+        SDLScancode::F8 => Some(Key::F8),
+        SDLScancode::F9 => Some(Key::F9),
+        SDLScancode::F10 => Some(Key::F10),
+        SDLScancode::F11 => Some(Key::F11),
+        SDLScancode::F12 => Some(Key::F12),
+        SDLScancode::F13 => Some(Key::F13),
+        SDLScancode::F14 => Some(Key::F14),
+        SDLScancode::F15 => Some(Key::F15),
+        SDLScancode::F16 => Some(Key::F16),
+        SDLScancode::F17 => Some(Key::F17),
+        SDLScancode::F18 => Some(Key::F18),
+        SDLScancode::F19 => Some(Key::F19),
+        SDLScancode::F20 => Some(Key::F20),
+        SDLScancode::F21 => Some(Key::F21),
+        SDLScancode::F22 => Some(Key::F22),
+        SDLScancode::F23 => Some(Key::F23),
+        SDLScancode::F24 => Some(Key::F24),
+        SDLScancode::Cut => Some(Key::Cut),
+        SDLScancode::Copy => Some(Key::Copy),
+        SDLScancode::Paste => Some(Key::Paste),
+        SDLScancode::Tab => Some(Key::Tab),
+        SDLScancode::NumLockClear => Some(Key::NumLock),
+        SDLScancode::CapsLock => Some(Key::CapsLock),
+        SDLScancode::ScrollLock => Some(Key::ScrollLock),
+        SDLScancode::Pause => Some(Key::Pause),
+        SDLScancode::Insert => Some(Key::Insert),
+        SDLScancode::Delete => Some(Key::Delete),
+        SDLScancode::Home => Some(Key::Home),
+        SDLScancode::End => Some(Key::End),
+        SDLScancode::PageUp => Some(Key::PageUp),
+        SDLScancode::PageDown => Some(Key::PageDown),
+        SDLScancode::LShift => Some(Key::LShift),
+        SDLScancode::Application => Some(Key::ContextMenu),
+        SDLScancode::LCtrl => Some(Key::LControl),
+        SDLScancode::LAlt => Some(Key::LAlt),
+        SDLScancode::LGui => Some(Key::LMeta),
+        SDLScancode::RShift => Some(Key::RShift),
+        SDLScancode::RCtrl => Some(Key::RControl),
+        SDLScancode::RAlt => Some(Key::RAlt),
+        SDLScancode::RGui => Some(Key::RMeta),
+        SDLScancode::Left => Some(Key::Left),
+        SDLScancode::Right => Some(Key::Right),
+        SDLScancode::Up => Some(Key::Up),
+        SDLScancode::Down => Some(Key::Down),
+        SDLScancode::LeftBracket => Some(Key::LBracket),
+        SDLScancode::RightBracket => Some(Key::RBracket),
+        SDLScancode::Semicolon => Some(Key::Semicolon),
+        SDLScancode::Apostrophe => Some(Key::Apostrophe),
+        SDLScancode::Return => Some(Key::Return),
+        SDLScancode::Space => Some(Key::Space),
+        SDLScancode::Calculator => Some(Key::Calculator),
+        SDLScancode::Comma => Some(Key::Comma),
+        SDLScancode::Period => Some(Key::Period),
+        SDLScancode::Slash => Some(Key::Slash),
+        SDLScancode::Backslash => Some(Key::Backslash),
+        SDLScancode::Mail => Some(Key::Mail),
+        SDLScancode::AudioPlay => Some(Key::MediaPlayPause),
+        SDLScancode::MediaSelect => Some(Key::MediaSelect),
+        SDLScancode::AudioStop => Some(Key::MediaStop),
+        SDLScancode::AudioPrev => Some(Key::MediaPrev),
+        SDLScancode::AudioNext => Some(Key::MediaNext),
+        SDLScancode::Mute => Some(Key::Mute),
+        SDLScancode::Computer => Some(Key::MyComputer),
+        SDLScancode::AcBookmarks => Some(Key::WebBookmarks),
+        SDLScancode::AcBack => Some(Key::WebBack),
+        SDLScancode::AcForward => Some(Key::WebForward),
+        SDLScancode::AcHome => Some(Key::WebHome),
+        SDLScancode::AcRefresh => Some(Key::WebRefresh),
+        SDLScancode::AcSearch => Some(Key::WebSearch),
+        SDLScancode::AcStop => Some(Key::WebStop),
+        SDLScancode::Stop => Some(Key::Stop),
+        _ => None,
+    }
+}
@@ -1,11 +1,13 @@
 mod event_translation;
 mod key_translation;
+mod sdl2_controller_device;
 mod sdl2_game_loop;
 mod sdl2_game_window;
 mod sdl2_rumble_pack;
 
 use event_translation::*;
 use key_translation::*;
+use sdl2_controller_device::*;
 use sdl2_game_window::*;
 use sdl2_rumble_pack::*;
 
@@ -0,0 +1,27 @@
+use input::{ControllerDevice, PowerInfo};
+
+pub(crate) struct Sdl2ControllerDevice {
+    joystick: sdl2::joystick::Joystick,
+}
+
+impl Sdl2ControllerDevice {
+    pub fn new(joystick: sdl2::joystick::Joystick) -> Self {
+        Self { joystick }
+    }
+}
+
+impl ControllerDevice for Sdl2ControllerDevice {
+    fn power_info(&self) -> PowerInfo {
+        use sdl2::joystick::PowerLevel;
+
+        // SDL2 only reports rough levels, and doesn't report charging
+        match self.joystick.power_level() {
+            Ok(PowerLevel::Empty) => PowerInfo::Discharging(5),
+            Ok(PowerLevel::Low) => PowerInfo::Discharging(20),
+            Ok(PowerLevel::Medium) => PowerInfo::Discharging(70),
+            Ok(PowerLevel::Full) => PowerInfo::Discharging(100),
+            Ok(PowerLevel::Wired) => PowerInfo::Wired,
+            _ => PowerInfo::Unknown,
+        }
+    }
+}
@@ -1,9 +1,10 @@
-use crate::{translate_sdl_event, Sdl2GameWindow, Sdl2RumblePack};
+use crate::{translate_sdl_event, Sdl2ControllerDevice, Sdl2GameWindow, Sdl2RumblePack};
 use framework_core::runtime::{
     GameRuntimeCore, GameRuntimeCoreParams, GameWindowConfig, GameWindowEvent, GameWindowLoop,
     InputEvent,
 };
 use input::*;
+use math::{IRect, Vec2};
 use std::future::Future;
 
 pub struct Sdl2GameLoop {
@@ -27,18 +28,48 @@ impl GameWindowLoop for Sdl2GameLoop {
 }
 
 impl Sdl2GameLoop {
-    async fn build(window_config: GameWindowConfig<()>) -> anyhow::Result<Sdl2GameLoop> {
+    async fn build(mut window_config: GameWindowConfig<()>) -> anyhow::Result<Sdl2GameLoop> {
         let sdl_context = sdl2::init().map_err(|e| anyhow::anyhow!(e))?;
         let event_pump = sdl_context.event_pump().map_err(|e| anyhow::anyhow!(e))?;
 
         let video_subsystem = sdl_context.video().map_err(|e| anyhow::anyhow!(e))?;
+        let mut restored_position = None;
+
+        if let Some(window_state) = window_config.window_state {
+            let display_count = video_subsystem.num_video_displays().unwrap_or_default();
+
+            let monitors: Vec<_> = (0..display_count)
+                .filter_map(|i| video_subsystem.display_bounds(i).ok())
+                .map(|bounds| {
+                    IRect::new(
+                        bounds.x(),
+                        bounds.y(),
+                        bounds.width() as i32,
+                        bounds.height() as i32,
+                    )
+                })
+                .collect();
+
+            let window_state = window_state.validate(&monitors);
+            window_config.size = window_state.size;
+            window_config.fullscreen = window_state.fullscreen;
+            restored_position = window_state.position;
+        }
+
         let mut sdl_window_builder = video_subsystem.window(
             &window_config.title,
             window_config.size.x,
             window_config.size.y,
         );
 
-        sdl_window_builder.position_centered();
+        if let Some(position) = restored_position {
+            sdl_window_builder.position(position.x, position.y);
+        } else {
+            sdl_window_builder.position_centered();
+        }
+
+        // sizes reported to the game are in drawable pixels, see Sdl2GameWindow::scale_factor()
+        sdl_window_builder.allow_highdpi();
 
         if window_config.resizable {
             sdl_window_builder.resizable();
@@ -71,33 +102,81 @@ impl Sdl2GameLoop {
 
     async fn run(mut self, loop_params: GameRuntimeCoreParams) -> anyhow::Result<()> {
         let window_id = self.window.id();
+        let platform = sdl2::get_platform();
+
+        for mapping in loop_params.controller_mappings.iter() {
+            if mapping.supports_platform(platform) {
+                let _ = self.game_controller_subsystem.add_mapping(mapping.source());
+            }
+        }
+
+        let joystick_subsystem = self
+            .game_controller_subsystem
+            .sdl()
+            .joystick()
+            .map_err(|e| anyhow::anyhow!(e))?;
+
         let mut game_runtime = GameRuntimeCore::new(Box::new(self.window), loop_params)?;
 
         while !game_runtime.quitting() {
             for sdl_event in self.event_pump.poll_iter() {
                 if let sdl2::event::Event::JoyDeviceAdded { which, .. } = sdl_event {
                     if let Ok(controller) = self.game_controller_subsystem.open(which) {
+                        let mut info = ControllerInfo::new(
+                            controller.name(),
+                            controller.vendor_id(),
+                            controller.product_id(),
+                        );
+
+                        if let Ok(guid) = joystick_subsystem.device_guid(which) {
+                            info = info.with_guid(guid.string());
+                        }
+
+                        // opened separately for power info, SDL shares the underlying device
+                        let device = joystick_subsystem.open(which).ok().map(|joystick| {
+                            Box::from(Sdl2ControllerDevice::new(joystick))
+                                as Box<dyn ControllerDevice>
+                        });
+
                         game_runtime.push_event(
                             InputEvent::ControllerConnected {
                                 controller_id: which as usize,
+                                info,
                                 rumble_pack: Box::from(Sdl2RumblePack::new(controller)),
+                                device,
                             }
                             .into(),
                         );
                     }
                 }
 
+                // translated separately from MouseMoved
+                if let sdl2::event::Event::MouseMotion {
+                    window_id: motion_window_id,
+                    xrel,
+                    yrel,
+                    ..
+                } = sdl_event
+                {
+                    if motion_window_id == window_id {
+                        let delta = Vec2::new(xrel as f32, -yrel as f32);
+                        game_runtime.push_event(InputEvent::MouseMotion(delta).into());
+                    }
+                }
+
                 let window = game_runtime.game_io().window();
 
                 if let Some(event) = translate_sdl_event(window, window_id, sdl_event) {
                     // reducing differences with winit
                     let text = match event {
-                        GameWindowEvent::InputEvent(InputEvent::KeyDown(Key::Backspace)) => {
-                            Some("\u{8}")
-                        }
-                        GameWindowEvent::InputEvent(InputEvent::KeyDown(Key::Delete)) => {
-                            Some("\u{7f}")
-                        }
+                        GameWindowEvent::InputEvent(InputEvent::KeyDown(KeyInput {
+                            logical: Key::Backspace,
+                            ..
+                        })) => Some("\u{8}"),
+                        GameWindowEvent::InputEvent(InputEvent::KeyDown(KeyInput {
+                            logical: Key::Delete,
+                            ..
+                        })) => Some("\u{7f}"),
                         _ => None,
                     };
 
@@ -107,11 +186,25 @@ impl Sdl2GameLoop {
                         )));
                     }
 
+                    let monitor_changed = matches!(event, GameWindowEvent::MonitorChanged);
+
                     game_runtime.push_event(event);
+
+                    if monitor_changed {
+                        // SDL2 doesn't report scale changes, the scale is derived from the display
+                        let scale_factor = game_runtime.game_io().window().scale_factor();
+                        game_runtime.push_event(GameWindowEvent::ScaleFactorChanged(scale_factor));
+                    }
                 }
             }
 
             game_runtime.tick();
+
+            // secondary windows are unsupported
+            for request in game_runtime.take_window_requests() {
+                game_runtime.reject_window(request.id);
+            }
+
             game_runtime.sleep().await;
         }
         Ok(())
@@ -1,6 +1,12 @@
-use framework_core::graphics::{wgpu, Color, GraphicsContext, HasGraphicsContext, RenderTarget};
+use framework_core::common::{
+    CursorGrab, CursorIcon, CustomCursor, FullscreenMode, GameWindow, MonitorInfo, VideoMode,
+};
+use framework_core::graphics::{
+    wgpu, AcquiredSurfaceTexture, Color, GraphicsContext, HasGraphicsContext, RenderTarget,
+};
 use framework_core::runtime::GameWindowConfig;
-use framework_core::{common::GameWindow, runtime::GameWindowLifecycle};
+use framework_core::runtime::GameWindowLifecycle;
+use logging::log;
 use math::*;
 
 const DEFAULT_IME_CURSOR_AREA: Rect = Rect::new(-1.0, 1.0, 0.0, 0.0);
@@ -18,6 +24,9 @@ pub struct Sdl2GameWindow {
     integer_scaling: bool,
     clear_color: Option<Color>,
     ime_cursor_area: Rect,
+    cursor_grab: CursorGrab,
+    // SDL2 resets the cursor if the active cursor is freed
+    cursor: Option<sdl2::mouse::Cursor>,
 }
 
 impl Sdl2GameWindow {
@@ -26,7 +35,7 @@ impl Sdl2GameWindow {
         window_config: GameWindowConfig<()>,
     ) -> anyhow::Result<Self> {
         let position = window.position().into();
-        let size = window.size().into();
+        let size: UVec2 = window.drawable_size().into();
 
         let wgpu_instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
             backends: Default::default(),
@@ -45,12 +54,8 @@ impl Sdl2GameWindow {
         let adapter = graphics.adapter();
         let device = graphics.device();
 
-        let window_size = window.size();
-        let width = window_size.0;
-        let height = window_size.1;
-
         let mut surface_config = surface
-            .get_default_config(adapter, width, height)
+            .get_default_config(adapter, size.x, size.y)
             .expect("Surface unsupported by adapter");
 
         surface_config.present_mode = wgpu::PresentMode::AutoVsync;
@@ -70,12 +75,57 @@ impl Sdl2GameWindow {
             resolution: window_config.resolution.unwrap_or(size),
             clear_color: Some(Color::TRANSPARENT),
             ime_cursor_area: DEFAULT_IME_CURSOR_AREA,
+            cursor_grab: CursorGrab::None,
+            cursor: None,
         })
     }
 
     pub(crate) fn id(&self) -> u32 {
         self.window.id()
     }
+
+    /// SDL2 only reports the scale through the window's drawable size,
+    /// displays other than the one containing the window are reported with a scale of 1.0
+    fn display_scale_factor(&self, display: i32) -> f32 {
+        if self.window.display_index() == Ok(display) {
+            self.scale_factor()
+        } else {
+            1.0
+        }
+    }
+}
+
+fn translate_cursor_icon(icon: CursorIcon) -> sdl2::mouse::SystemCursor {
+    use sdl2::mouse::SystemCursor;
+
+    match icon {
+        CursorIcon::Default => SystemCursor::Arrow,
+        CursorIcon::Pointer | CursorIcon::Grab | CursorIcon::Grabbing => SystemCursor::Hand,
+        CursorIcon::Text => SystemCursor::IBeam,
+        CursorIcon::Crosshair => SystemCursor::Crosshair,
+        CursorIcon::Move => SystemCursor::SizeAll,
+        CursorIcon::Wait => SystemCursor::Wait,
+        CursorIcon::Progress => SystemCursor::WaitArrow,
+        CursorIcon::NotAllowed => SystemCursor::No,
+        CursorIcon::ResizeHorizontal => SystemCursor::SizeWE,
+        CursorIcon::ResizeVertical => SystemCursor::SizeNS,
+        CursorIcon::ResizeNwse => SystemCursor::SizeNWSE,
+        CursorIcon::ResizeNesw => SystemCursor::SizeNESW,
+    }
+}
+
+fn translate_display_mode(display_mode: &sdl2::video::DisplayMode) -> VideoMode {
+    let bit_depth = display_mode
+        .format
+        .into_masks()
+        .map(|masks| masks.bpp as u16)
+        .unwrap_or(32);
+
+    VideoMode {
+        size: UVec2::new(display_mode.w as u32, display_mode.h as u32),
+        bit_depth,
+        refresh_rate: display_mode.refresh_rate as f32,
+    }
 }
 
 impl HasGraphicsContext for Sdl2GameWindow {
@@ -101,10 +151,33 @@ impl GameWindowLifecycle for Sdl2GameWindow {
         }
     }
 
+    fn recreate_graphics(&mut self) -> anyhow::Result<()> {
+        let graphics =
+            framework_core::async_task::block_on(self.graphics.recreate(Some(&self.surface)))?;
+
+        self.replace_graphics(graphics);
+        Ok(())
+    }
+
+    fn replace_graphics(&mut self, graphics: GraphicsContext) {
+        self.graphics = graphics;
+        self.surface
+            .configure(self.graphics.device(), &self.surface_config);
+    }
+
     fn acquire_render_target(&mut self) -> Option<RenderTarget> {
-        let wgpu::CurrentSurfaceTexture::Success(surface_texture) =
-            self.surface.get_current_texture()
-        else {
+        let mut acquired = self
+            .graphics
+            .acquire_surface_texture(&self.surface, &self.surface_config);
+
+        if matches!(acquired, AcquiredSurfaceTexture::Lost) {
+            self.rebuild_surface();
+            acquired = self
+                .graphics
+                .acquire_surface_texture(&self.surface, &self.surface_config);
+        }
+
+        let AcquiredSurfaceTexture::Ready(surface_texture) = acquired else {
             return None;
         };
 
@@ -127,7 +200,9 @@ impl GameWindowLifecycle for Sdl2GameWindow {
         self.position = position;
     }
 
-    fn resized(&mut self, size: UVec2) {
+    fn resized(&mut self, _size: UVec2) {
+        // SDL2 reports screen coordinates, the surface uses the drawable size
+        let size: UVec2 = self.window.drawable_size().into();
         self.size = size;
 
         if !self.locked_resolution {
@@ -160,6 +235,7 @@ impl GameWindowLifecycle for Sdl2GameWindow {
         rect.set_position(rect.position() * Vec2::new(0.5, -0.5) + 0.5);
         rect *= self.resolution.as_vec2();
         rect *= self.render_scale();
+        rect /= self.scale_factor();
 
         let text_input = self.window.subsystem().text_input();
         text_input.set_rect(sdl2::rect::Rect::new(
@@ -203,7 +279,132 @@ impl GameWindow for Sdl2GameWindow {
         let _ = self.window.set_fullscreen(mode);
     }
 
+    fn fullscreen_mode(&self) -> FullscreenMode {
+        use sdl2::video::FullscreenType;
+
+        match self.window.fullscreen_state() {
+            FullscreenType::True => match self.window.display_mode() {
+                Ok(display_mode) => {
+                    FullscreenMode::Exclusive(translate_display_mode(&display_mode))
+                }
+                Err(_) => FullscreenMode::Borderless,
+            },
+            _ => FullscreenMode::Borderless,
+        }
+    }
+
+    fn set_fullscreen_mode(&mut self, monitor_index: Option<usize>, mode: FullscreenMode) {
+        use sdl2::video::{FullscreenType, WindowPos};
+
+        let video_subsystem = self.window.subsystem().clone();
+        let display_count = video_subsystem.num_video_displays().unwrap_or_default();
+        let current_display = self.window.display_index().ok();
+
+        let display = monitor_index
+            .map(|index| index as i32)
+            .filter(|index| *index < display_count)
+            .or(current_display);
+
+        let Some(display) = display else {
+            self.set_fullscreen(true);
+            return;
+        };
+
+        // fullscreen applies to the display containing the window
+        if current_display != Some(display) {
+            if let Ok(bounds) = video_subsystem.display_bounds(display) {
+                let _ = self.window.set_fullscreen(FullscreenType::Off);
+                self.window.set_position(
+                    WindowPos::Positioned(bounds.x()),
+                    WindowPos::Positioned(bounds.y()),
+                );
+            }
+        }
+
+        let display_mode = match mode {
+            FullscreenMode::Borderless => None,
+            FullscreenMode::Exclusive(video_mode) => {
+                let mode_count = video_subsystem
+                    .num_display_modes(display)
+                    .unwrap_or_default();
+
+                let display_mode = (0..mode_count)
+                    .filter_map(|i| video_subsystem.display_mode(display, i).ok())
+                    .find(|display_mode| translate_display_mode(display_mode) == video_mode);
+
+                if display_mode.is_none() {
+                    log::warn!("{video_mode:?} is not supported, using borderless fullscreen");
+                }
+
+                display_mode
+            }
+        };
+
+        let fullscreen_type = match display_mode {
+            Some(display_mode) => match self.window.set_display_mode(display_mode) {
+                Ok(()) => FullscreenType::True,
+                Err(err) => {
+                    log::warn!("Failed to set video mode, using borderless fullscreen: {err}");
+                    FullscreenType::Desktop
+                }
+            },
+            None => FullscreenType::Desktop,
+        };
+
+        let _ = self.window.set_fullscreen(fullscreen_type);
+    }
+
+    fn monitors(&self) -> Vec<MonitorInfo> {
+        let video_subsystem = self.window.subsystem();
+        let display_count = video_subsystem.num_video_displays().unwrap_or_default();
+
+        (0..display_count)
+            .filter_map(|display| {
+                let bounds = video_subsystem.display_bounds(display).ok()?;
+                let mode_count = video_subsystem
+                    .num_display_modes(display)
+                    .unwrap_or_default();
+
+                let video_modes: Vec<_> = (0..mode_count)
+                    .filter_map(|i| video_subsystem.display_mode(display, i).ok())
+                    .map(|display_mode| translate_display_mode(&display_mode))
+                    .collect();
+
+                let scale_factor = self.display_scale_factor(display);
+
+                Some(MonitorInfo {
+                    index: display as usize,
+                    name: video_subsystem.display_name(display).unwrap_or_default(),
+                    primary: display == 0,
+                    position: IVec2::new(bounds.x(), bounds.y()),
+                    size: UVec2::new(bounds.width(), bounds.height()),
+                    scale_factor,
+                    refresh_rates: VideoMode::distinct_refresh_rates(&video_modes),
+                    video_modes,
+                })
+            })
+            .collect()
+    }
+
+    fn current_monitor(&self) -> Option<usize> {
+        self.window
+            .display_index()
+            .ok()
+            .map(|display| display as usize)
+    }
+
+    fn scale_factor(&self) -> f32 {
+        let (width, _) = self.window.size();
+
+        if width == 0 {
+            return 1.0;
+        }
+
+        self.window.drawable_size().0 as f32 / width as f32
+    }
+
     fn request_size(&mut self, size: UVec2) {
+        let size = (size.as_vec2() / self.scale_factor()).round().as_uvec2();
         let _ = self.window.set_size(size.x, size.y);
     }
 
@@ -241,6 +442,71 @@ impl GameWindow for Sdl2GameWindow {
         let _ = self.window.set_title(title);
     }
 
+    fn cursor_visible(&self) -> bool {
+        self.window.subsystem().sdl().mouse().is_cursor_showing()
+    }
+
+    fn set_cursor_visible(&mut self, visible: bool) {
+        self.window.subsystem().sdl().mouse().show_cursor(visible);
+    }
+
+    fn cursor_grab(&self) -> CursorGrab {
+        self.cursor_grab
+    }
+
+    fn set_cursor_grab(&mut self, grab: CursorGrab) {
+        let mouse = self.window.subsystem().sdl().mouse();
+        mouse.set_relative_mouse_mode(grab == CursorGrab::Locked);
+
+        self.window.set_mouse_grab(grab != CursorGrab::None);
+        self.cursor_grab = grab;
+    }
+
+    fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        if let Ok(cursor) = sdl2::mouse::Cursor::from_system(translate_cursor_icon(icon)) {
+            cursor.set();
+            self.cursor = Some(cursor);
+        }
+    }
+
+    fn set_custom_cursor(&mut self, cursor: &CustomCursor) {
+        use sdl2::mouse::Cursor;
+        use sdl2::pixels::PixelFormatEnum;
+        use sdl2::surface::Surface;
+
+        if !cursor.is_valid() {
+            log::error!("Invalid custom cursor, keeping the current cursor");
+            return;
+        }
+
+        let mut rgba = cursor.rgba.clone();
+
+        let surface = match Surface::from_data(
+            &mut rgba,
+            cursor.size.x,
+            cursor.size.y,
+            cursor.size.x * 4,
+            PixelFormatEnum::RGBA32,
+        ) {
+            Ok(surface) => surface,
+            Err(err) => {
+                log::error!("Failed to create custom cursor: {err}");
+                return;
+            }
+        };
+
+        // SDL2 copies the surface
+        let hotspot = cursor.hotspot.as_ivec2();
+
+        match Cursor::from_surface(surface, hotspot.x, hotspot.y) {
+            Ok(cursor) => {
+                cursor.set();
+                self.cursor = Some(cursor);
+            }
+            Err(err) => log::error!("Failed to create custom cursor: {err}"),
+        }
+    }
+
     fn clear_color(&self) -> Option<Color> {
         self.clear_color
     }
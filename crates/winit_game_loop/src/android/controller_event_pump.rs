@@ -1,9 +1,13 @@
 use framework_core::runtime::GameRuntimeCore;
+use input::ControllerMappingDatabase;
 
 pub(crate) struct ControllerEventPump {}
 
 impl ControllerEventPump {
-    pub(crate) fn new(_: &mut GameRuntimeCore) -> anyhow::Result<Self> {
+    pub(crate) fn new(
+        _: &mut GameRuntimeCore,
+        _: &ControllerMappingDatabase,
+    ) -> anyhow::Result<Self> {
         Ok(Self {})
     }
 
@@ -1,2 +1,3 @@
 pub(crate) mod controller_event_pump;
+
 pub use android::{jni, AndroidJVM};
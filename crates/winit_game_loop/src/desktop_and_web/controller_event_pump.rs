@@ -1,7 +1,8 @@
+use super::default_controller_device::DefaultControllerDevice;
 use super::default_rumble_pack::DefaultRumblePack;
 use framework_core::runtime::{GameRuntimeCore, InputEvent};
 use gilrs::ev::EventType as GilRsEvent;
-use input::{AnalogAxis, Button};
+use input::{AnalogAxis, Button, ControllerInfo, ControllerMappingDatabase};
 use logging::log;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -11,18 +12,32 @@ pub(crate) struct ControllerEventPump {
 }
 
 impl ControllerEventPump {
-    pub(crate) fn new(game_runtime: &mut GameRuntimeCore) -> anyhow::Result<Self> {
-        let gilrs = gilrs::Gilrs::new().map_err(|e| {
-            log::error!("{e}");
-            anyhow::anyhow!("Failed to initialize game controller subsystem")
-        })?;
+    pub(crate) fn new(
+        game_runtime: &mut GameRuntimeCore,
+        mappings: &ControllerMappingDatabase,
+    ) -> anyhow::Result<Self> {
+        let mappings = mappings
+            .iter()
+            .map(|mapping| mapping.source())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let gilrs = gilrs::GilrsBuilder::new()
+            .add_mappings(&mappings)
+            .build()
+            .map_err(|e| {
+                log::error!("{e}");
+                anyhow::anyhow!("Failed to initialize game controller subsystem")
+            })?;
 
         let gilrs = Rc::new(RefCell::new(gilrs));
 
-        for (id, _) in gilrs.borrow().gamepads() {
+        for (id, gamepad) in gilrs.borrow().gamepads() {
             let input_event = InputEvent::ControllerConnected {
                 controller_id: id.into(),
+                info: controller_info(&gamepad),
                 rumble_pack: Box::from(DefaultRumblePack::new(gilrs.clone(), id)),
+                device: Some(Box::from(DefaultControllerDevice::new(gilrs.clone(), id))),
             };
 
             game_runtime.push_event(input_event.into());
@@ -51,7 +66,9 @@ fn convert_event(
     match event {
         GilRsEvent::Connected => Some(InputEvent::ControllerConnected {
             controller_id: id.into(),
+            info: controller_info(&gilrs.borrow().gamepad(id)),
             rumble_pack: Box::from(DefaultRumblePack::new(gilrs.clone(), id)),
+            device: Some(Box::from(DefaultControllerDevice::new(gilrs.clone(), id))),
         }),
         GilRsEvent::Disconnected => Some(InputEvent::ControllerDisconnected(id.into())),
         GilRsEvent::ButtonPressed(button, _) => Some(InputEvent::ControllerButtonDown {
@@ -71,6 +88,21 @@ fn convert_event(
     }
 }
 
+fn controller_info(gamepad: &gilrs::Gamepad) -> ControllerInfo {
+    let guid = gamepad
+        .uuid()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    ControllerInfo::new(
+        gamepad.name().to_string(),
+        gamepad.vendor_id(),
+        gamepad.product_id(),
+    )
+    .with_guid(guid)
+}
+
 fn convert_button(button: gilrs::Button) -> Option<Button> {
     match button {
         gilrs::Button::Mode => Some(Button::Meta),
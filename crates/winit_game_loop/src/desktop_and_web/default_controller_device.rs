@@ -0,0 +1,28 @@
+use gilrs::{GamepadId, Gilrs};
+use input::{ControllerDevice, PowerInfo};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Debug)]
+pub(crate) struct DefaultControllerDevice {
+    gilrs: Rc<RefCell<Gilrs>>,
+    gamepad_id: GamepadId,
+}
+
+impl DefaultControllerDevice {
+    pub(super) fn new(gilrs: Rc<RefCell<Gilrs>>, gamepad_id: GamepadId) -> Self {
+        Self { gilrs, gamepad_id }
+    }
+}
+
+impl ControllerDevice for DefaultControllerDevice {
+    fn power_info(&self) -> PowerInfo {
+        match self.gilrs.borrow().gamepad(self.gamepad_id).power_info() {
+            gilrs::PowerInfo::Unknown => PowerInfo::Unknown,
+            gilrs::PowerInfo::Wired => PowerInfo::Wired,
+            gilrs::PowerInfo::Discharging(percent) => PowerInfo::Discharging(percent),
+            gilrs::PowerInfo::Charging(percent) => PowerInfo::Charging(percent),
+            gilrs::PowerInfo::Charged => PowerInfo::Charged,
+        }
+    }
+}
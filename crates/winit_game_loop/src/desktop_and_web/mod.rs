@@ -1,4 +1,5 @@
 mod controller_event_pump;
+mod default_controller_device;
 mod default_rumble_pack;
 
 pub(crate) use controller_event_pump::*;
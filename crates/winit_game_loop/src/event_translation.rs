@@ -17,6 +17,9 @@ pub(crate) fn translate_winit_event(
         WinitWindowEvent::Resized(winit::dpi::PhysicalSize { width, height }) => {
             vec![GameWindowEvent::Resized(UVec2::new(width, height))]
         }
+        WinitWindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+            vec![GameWindowEvent::ScaleFactorChanged(scale_factor as f32)]
+        }
         WinitWindowEvent::Moved(position) => {
             vec![GameWindowEvent::Moved(IVec2::new(position.x, position.y))]
         }
@@ -50,6 +53,19 @@ pub(crate) fn translate_winit_event(
 
             vec![InputEvent::MouseMoved(normalized).into()]
         }
+        WinitWindowEvent::MouseWheel { delta, .. } => {
+            // winit uses positive x for scrolling left
+            let delta = match delta {
+                winit::event::MouseScrollDelta::LineDelta(x, y) => {
+                    MouseWheelDelta::Lines(Vec2::new(-x, y))
+                }
+                winit::event::MouseScrollDelta::PixelDelta(position) => {
+                    MouseWheelDelta::Pixels(Vec2::new(-position.x as f32, position.y as f32))
+                }
+            };
+
+            vec![InputEvent::MouseWheel(delta).into()]
+        }
         WinitWindowEvent::MouseInput { state, button, .. } => {
             if let Some(button) = translate_winit_mouse_button(button) {
                 if state == winit::event::ElementState::Pressed {
@@ -93,7 +109,11 @@ pub(crate) fn translate_winit_event(
                 None
             };
 
-            if let Some(key) = key_code.and_then(super::translate_winit_key) {
+            if let Some(physical) = key_code.and_then(super::translate_winit_key) {
+                // named keys such as arrows aren't affected by the layout
+                let logical = translate_winit_logical_key(&logical_key).unwrap_or(physical);
+                let key = KeyInput::new(physical, logical);
+
                 if state == winit::event::ElementState::Pressed {
                     events.push(InputEvent::KeyDown(key).into());
                 } else {
@@ -108,6 +128,17 @@ pub(crate) fn translate_winit_event(
                     cfg_android! { logical_key.to_text() }
                 };
 
+                // not every platform reports text for these, matching other backends
+                let text = text.or(match logical_key {
+                    winit::keyboard::Key::Named(winit::keyboard::NamedKey::Backspace) => {
+                        Some("\u{8}")
+                    }
+                    winit::keyboard::Key::Named(winit::keyboard::NamedKey::Delete) => {
+                        Some("\u{7f}")
+                    }
+                    _ => None,
+                });
+
                 if let Some(text) = text {
                     let text = if text == "\r" {
                         String::from("\n")
@@ -125,6 +156,30 @@ pub(crate) fn translate_winit_event(
     }
 }
 
+pub(crate) fn translate_winit_device_event(
+    event: winit::event::DeviceEvent,
+) -> Option<GameWindowEvent> {
+    match event {
+        winit::event::DeviceEvent::MouseMotion { delta: (x, y) } => {
+            Some(InputEvent::MouseMotion(Vec2::new(x as f32, -y as f32)).into())
+        }
+        _ => None,
+    }
+}
+
+fn translate_winit_logical_key(logical_key: &winit::keyboard::Key) -> Option<Key> {
+    let winit::keyboard::Key::Character(text) = logical_key else {
+        return None;
+    };
+
+    let mut chars = text.chars();
+
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Key::from_char(c),
+        _ => None,
+    }
+}
+
 fn translate_winit_mouse_button(button: WinitMouseButton) -> Option<MouseButton> {
     match button {
         WinitMouseButton::Left => Some(MouseButton::Left),
@@ -1,11 +1,19 @@
 use super::LoopState;
-use crate::event_translation::translate_winit_event;
+use crate::event_translation::{translate_winit_device_event, translate_winit_event};
 use crate::{ControllerEventPump, WinitGameWindow};
+use cfg_macros::cfg_desktop;
+use framework_core::common::{GameWindowId, SecondaryWindowConfig};
+use framework_core::graphics::HasGraphicsContext;
 use framework_core::runtime::*;
+use logging::log;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
 use winit::application::ApplicationHandler;
+use winit::dpi::PhysicalSize;
 use winit::event::StartCause as WinitEventStartCause;
 use winit::event_loop::{ActiveEventLoop, ControlFlow};
-use winit::window::WindowId;
+use winit::window::{WindowId, WindowLevel};
 
 pub struct ActiveStateParams {
     pub owned_display_handle: winit::event_loop::OwnedDisplayHandle,
@@ -14,8 +22,18 @@ pub struct ActiveStateParams {
     pub runtime_params: GameRuntimeCoreParams,
 }
 
+struct SecondaryWinitWindow {
+    id: GameWindowId,
+    window_id: WindowId,
+    winit_window: Arc<winit::window::Window>,
+    pending_custom_cursor: Rc<Cell<Option<winit::window::CustomCursorSource>>>,
+}
+
 pub struct ActiveState {
     window_id: WindowId,
+    winit_window: Arc<winit::window::Window>,
+    pending_custom_cursor: Rc<Cell<Option<winit::window::CustomCursorSource>>>,
+    secondary_windows: Vec<SecondaryWinitWindow>,
     game_runtime: GameRuntimeCore,
     controller_event_pump: ControllerEventPump,
     handled_suspended: bool,
@@ -32,18 +50,89 @@ impl ActiveState {
         )
         .await?;
 
+        let winit_window = window.winit_window().clone();
+        let pending_custom_cursor = window.pending_custom_cursor();
+
+        let controller_mappings = params.runtime_params.controller_mappings.clone();
         let mut game_runtime = GameRuntimeCore::new(Box::new(window), params.runtime_params)?;
 
-        let controller_event_pump = ControllerEventPump::new(&mut game_runtime)?;
+        let controller_event_pump =
+            ControllerEventPump::new(&mut game_runtime, &controller_mappings)?;
 
         Ok(Self {
             window_id,
+            winit_window,
+            pending_custom_cursor,
+            secondary_windows: Vec::new(),
             game_runtime,
             controller_event_pump,
             handled_suspended: true,
             next_state: None,
         })
     }
+
+    fn create_secondary_window(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        id: GameWindowId,
+        config: &SecondaryWindowConfig,
+    ) -> anyhow::Result<()> {
+        let window_attributes = winit::window::Window::default_attributes()
+            .with_title(&config.title)
+            .with_inner_size(PhysicalSize::new(config.size.x, config.size.y))
+            .with_resizable(config.resizable)
+            .with_decorations(!config.borderless)
+            .with_window_level(if config.always_on_top {
+                WindowLevel::AlwaysOnTop
+            } else {
+                WindowLevel::Normal
+            });
+
+        let winit_window = event_loop.create_window(window_attributes)?;
+        let window_id = winit_window.id();
+
+        let graphics = self.game_runtime.game_io().graphics().clone();
+        let window = WinitGameWindow::from_window_with_graphics(winit_window, graphics, config)?;
+
+        self.secondary_windows.push(SecondaryWinitWindow {
+            id,
+            window_id,
+            winit_window: window.winit_window().clone(),
+            pending_custom_cursor: window.pending_custom_cursor(),
+        });
+
+        self.game_runtime.attach_window(id, Box::new(window));
+
+        Ok(())
+    }
+
+    fn update_secondary_windows(&mut self, event_loop: &ActiveEventLoop) {
+        let closed_windows = self.game_runtime.take_closed_windows();
+
+        self.secondary_windows
+            .retain(|window| !closed_windows.contains(&window.id));
+
+        for request in self.game_runtime.take_window_requests() {
+            if !cfg_desktop!() {
+                // a single window is supported on web and android
+                self.game_runtime.reject_window(request.id);
+                continue;
+            }
+
+            if let Err(err) = self.create_secondary_window(event_loop, request.id, &request.config)
+            {
+                log::error!("Failed to create window {:?}: {err}", request.config.title);
+                self.game_runtime.reject_window(request.id);
+            }
+        }
+
+        for window in &self.secondary_windows {
+            if let Some(source) = window.pending_custom_cursor.take() {
+                let cursor = event_loop.create_custom_cursor(source);
+                window.winit_window.set_cursor(cursor);
+            }
+        }
+    }
 }
 
 impl LoopState for ActiveState {
@@ -82,16 +171,46 @@ impl ApplicationHandler for ActiveState {
         window_id: WindowId,
         event: winit::event::WindowEvent,
     ) {
-        if self.window_id == window_id {
-            let window = self.game_runtime.game_io().window();
+        let id = if self.window_id == window_id {
+            GameWindowId::MAIN
+        } else if let Some(window) = self
+            .secondary_windows
+            .iter()
+            .find(|window| window.window_id == window_id)
+        {
+            window.id
+        } else {
+            return;
+        };
 
-            for event in translate_winit_event(window, event) {
-                self.game_runtime.push_event(event);
-            }
+        let Some(window) = self.game_runtime.window(id) else {
+            return;
+        };
+
+        for event in translate_winit_event(window, event) {
+            self.game_runtime.push_window_event(id, event);
+        }
+    }
+
+    fn device_event(
+        &mut self,
+        _: &ActiveEventLoop,
+        _: winit::event::DeviceId,
+        event: winit::event::DeviceEvent,
+    ) {
+        if let Some(event) = translate_winit_device_event(event) {
+            self.game_runtime.push_event(event);
         }
     }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        if let Some(source) = self.pending_custom_cursor.take() {
+            let cursor = event_loop.create_custom_cursor(source);
+            self.winit_window.set_cursor(cursor);
+        }
+
+        self.update_secondary_windows(event_loop);
+
         if self.game_runtime.game_io().suspended() && self.handled_suspended {
             event_loop.set_control_flow(ControlFlow::Wait);
         } else {
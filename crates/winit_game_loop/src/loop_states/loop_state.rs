@@ -1,5 +1,5 @@
 use winit::application::ApplicationHandler;
-use winit::event::{StartCause, WindowEvent};
+use winit::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
 use winit::event_loop::ActiveEventLoop;
 use winit::window::WindowId;
 
@@ -41,6 +41,15 @@ impl ApplicationHandler for RootLoopState {
         self.state.window_event(event_loop, window_id, event)
     }
 
+    fn device_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        self.state.device_event(event_loop, device_id, event)
+    }
+
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         self.state.about_to_wait(event_loop);
 
@@ -4,8 +4,9 @@ use cfg_macros::cfg_web;
 use framework_core::runtime::GameRuntimeCoreParams;
 use framework_core::runtime::GameWindowConfig;
 use logging::log;
+use math::IRect;
 use winit::application::ApplicationHandler;
-use winit::dpi::PhysicalSize;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::event_loop::ActiveEventLoop;
 use winit::event_loop::ControlFlow;
 use winit::window::WindowLevel;
@@ -45,7 +46,30 @@ impl LoopState for StartingState {
 impl ApplicationHandler for StartingState {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         let params = self.params.take().unwrap();
-        let window_config = params.window_config;
+        let mut window_config = params.window_config;
+        let mut restored_position = None;
+
+        if let Some(window_state) = window_config.window_state {
+            let monitors: Vec<_> = event_loop
+                .available_monitors()
+                .map(|monitor| {
+                    let position = monitor.position();
+                    let size = monitor.size();
+
+                    IRect::new(
+                        position.x,
+                        position.y,
+                        size.width as i32,
+                        size.height as i32,
+                    )
+                })
+                .collect();
+
+            let window_state = window_state.validate(&monitors);
+            window_config.size = window_state.size;
+            window_config.fullscreen = window_state.fullscreen;
+            restored_position = window_state.position;
+        }
 
         let mut window_attributes = winit::window::Window::default_attributes()
             .with_title(&window_config.title)
@@ -62,6 +86,11 @@ impl ApplicationHandler for StartingState {
                 WindowLevel::Normal
             });
 
+        if let Some(position) = restored_position {
+            window_attributes =
+                window_attributes.with_position(PhysicalPosition::new(position.x, position.y));
+        }
+
         if window_config.fullscreen {
             use winit::window::Fullscreen;
             window_attributes =
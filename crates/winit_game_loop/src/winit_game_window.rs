@@ -1,9 +1,17 @@
 use crate::WinitPlatformApp;
 use cfg_macros::*;
-use framework_core::common::GameWindow;
-use framework_core::graphics::{wgpu, Color, GraphicsContext, HasGraphicsContext, RenderTarget};
+use framework_core::common::{
+    CursorGrab, CursorIcon, CustomCursor, FullscreenMode, GameWindow, MonitorInfo,
+    SecondaryWindowConfig, VideoMode,
+};
+use framework_core::graphics::{
+    wgpu, AcquiredSurfaceTexture, Color, GraphicsContext, HasGraphicsContext, RenderTarget,
+};
 use framework_core::runtime::{GameWindowConfig, GameWindowLifecycle};
+use logging::log;
 use math::*;
+use std::cell::Cell;
+use std::rc::Rc;
 use std::sync::Arc;
 use winit::dpi::{PhysicalPosition, PhysicalSize};
 
@@ -22,6 +30,9 @@ pub struct WinitGameWindow {
     integer_scaling: bool,
     clear_color: Option<Color>,
     ime_cursor_area: Rect,
+    cursor_visible: bool,
+    cursor_grab: CursorGrab,
+    pending_custom_cursor: Rc<Cell<Option<winit::window::CustomCursorSource>>>,
     #[allow(dead_code)]
     platform_app: Option<WinitPlatformApp>,
 }
@@ -73,9 +84,114 @@ impl WinitGameWindow {
             integer_scaling: window_config.integer_scaling,
             clear_color: Some(Color::TRANSPARENT),
             ime_cursor_area: DEFAULT_IME_CURSOR_AREA,
+            cursor_visible: true,
+            cursor_grab: CursorGrab::None,
+            pending_custom_cursor: Default::default(),
             platform_app: window_config.platform_app,
         })
     }
+
+    /// Creates a window sharing the graphics device of an existing window
+    pub(crate) fn from_window_with_graphics(
+        window: winit::window::Window,
+        graphics: GraphicsContext,
+        window_config: &SecondaryWindowConfig,
+    ) -> anyhow::Result<Self> {
+        let window = Arc::new(window);
+        let position = window.outer_position().unwrap_or_default();
+
+        let surface = graphics.wgpu_instance().create_surface(window.clone())?;
+
+        let adapter = graphics.adapter();
+        let device = graphics.device();
+
+        let window_size = window_config.size;
+
+        let mut surface_config = surface
+            .get_default_config(adapter, window_size.x.max(1), window_size.y.max(1))
+            .ok_or_else(|| anyhow::anyhow!("Surface unsupported by adapter"))?;
+
+        // pipelines are built for the default format, the surface must match it
+        let format = graphics.default_texture_format();
+
+        if !surface.get_capabilities(adapter).formats.contains(&format) {
+            anyhow::bail!("Surface does not support {format:?}");
+        }
+
+        surface_config.format = format;
+        surface_config.present_mode = wgpu::PresentMode::AutoVsync;
+        surface.configure(device, &surface_config);
+
+        Ok(Self {
+            window,
+            graphics,
+            surface,
+            surface_config,
+            surface_texture: None,
+            position: IVec2::new(position.x, position.y),
+            size: window_size,
+            resolution: window_config.resolution.unwrap_or(window_size),
+            locked_resolution: window_config.resolution.is_some(),
+            integer_scaling: window_config.integer_scaling,
+            clear_color: Some(Color::TRANSPARENT),
+            ime_cursor_area: DEFAULT_IME_CURSOR_AREA,
+            cursor_visible: true,
+            cursor_grab: CursorGrab::None,
+            pending_custom_cursor: Default::default(),
+            platform_app: None,
+        })
+    }
+}
+
+impl WinitGameWindow {
+    pub(crate) fn winit_window(&self) -> &Arc<winit::window::Window> {
+        &self.window
+    }
+
+    /// Custom cursors require the event loop to create, see `ActiveState`
+    pub(crate) fn pending_custom_cursor(
+        &self,
+    ) -> Rc<Cell<Option<winit::window::CustomCursorSource>>> {
+        self.pending_custom_cursor.clone()
+    }
+
+    fn monitor_handle(&self, index: Option<usize>) -> Option<winit::monitor::MonitorHandle> {
+        match index {
+            Some(index) => self.window.available_monitors().nth(index),
+            None => self.window.current_monitor(),
+        }
+    }
+}
+
+fn translate_cursor_icon(icon: CursorIcon) -> winit::window::CursorIcon {
+    use winit::window::CursorIcon as WinitCursorIcon;
+
+    match icon {
+        CursorIcon::Default => WinitCursorIcon::Default,
+        CursorIcon::Pointer => WinitCursorIcon::Pointer,
+        CursorIcon::Text => WinitCursorIcon::Text,
+        CursorIcon::Crosshair => WinitCursorIcon::Crosshair,
+        CursorIcon::Move => WinitCursorIcon::Move,
+        CursorIcon::Grab => WinitCursorIcon::Grab,
+        CursorIcon::Grabbing => WinitCursorIcon::Grabbing,
+        CursorIcon::Wait => WinitCursorIcon::Wait,
+        CursorIcon::Progress => WinitCursorIcon::Progress,
+        CursorIcon::NotAllowed => WinitCursorIcon::NotAllowed,
+        CursorIcon::ResizeHorizontal => WinitCursorIcon::EwResize,
+        CursorIcon::ResizeVertical => WinitCursorIcon::NsResize,
+        CursorIcon::ResizeNwse => WinitCursorIcon::NwseResize,
+        CursorIcon::ResizeNesw => WinitCursorIcon::NeswResize,
+    }
+}
+
+fn translate_video_mode(video_mode: &winit::monitor::VideoModeHandle) -> VideoMode {
+    let size = video_mode.size();
+
+    VideoMode {
+        size: UVec2::new(size.width, size.height),
+        bit_depth: video_mode.bit_depth(),
+        refresh_rate: video_mode.refresh_rate_millihertz() as f32 / 1000.0,
+    }
 }
 
 impl HasGraphicsContext for WinitGameWindow {
@@ -96,10 +212,42 @@ impl GameWindowLifecycle for WinitGameWindow {
         }
     }
 
+    fn recreate_graphics(&mut self) -> anyhow::Result<()> {
+        if !self.supports_graphics_recreation() {
+            anyhow::bail!("Recreating the graphics device is unsupported on web");
+        }
+
+        let graphics =
+            framework_core::async_task::block_on(self.graphics.recreate(Some(&self.surface)))?;
+
+        self.replace_graphics(graphics);
+        Ok(())
+    }
+
+    fn supports_graphics_recreation(&self) -> bool {
+        // adapters are requested through the browser, which can't be blocked on
+        !cfg_web!()
+    }
+
+    fn replace_graphics(&mut self, graphics: GraphicsContext) {
+        self.graphics = graphics;
+        self.surface
+            .configure(self.graphics.device(), &self.surface_config);
+    }
+
     fn acquire_render_target(&mut self) -> Option<RenderTarget> {
-        let wgpu::CurrentSurfaceTexture::Success(surface_texture) =
-            self.surface.get_current_texture()
-        else {
+        let mut acquired = self
+            .graphics
+            .acquire_surface_texture(&self.surface, &self.surface_config);
+
+        if matches!(acquired, AcquiredSurfaceTexture::Lost) {
+            self.rebuild_surface();
+            acquired = self
+                .graphics
+                .acquire_surface_texture(&self.surface, &self.surface_config);
+        }
+
+        let AcquiredSurfaceTexture::Ready(surface_texture) = acquired else {
             return None;
         };
 
@@ -181,6 +329,20 @@ impl GameWindowLifecycle for WinitGameWindow {
         let size = PhysicalSize::new(rect.width, rect.height);
         self.window.set_ime_cursor_area(position, size);
     }
+
+    #[cfg(target_os = "android")]
+    fn storage_directory(&self) -> Option<std::path::PathBuf> {
+        let app = self.platform_app.as_ref()?;
+        android::util::internal_storage_path(app)
+    }
+
+    #[cfg(target_os = "android")]
+    fn create_clipboard(&self) -> Box<dyn framework_core::common::ClipboardBackend> {
+        match &self.platform_app {
+            Some(app) => Box::new(android::AndroidClipboard::new(app.clone())),
+            None => framework_core::common::default_clipboard(),
+        }
+    }
 }
 
 impl GameWindow for WinitGameWindow {
@@ -221,10 +383,112 @@ impl GameWindow for WinitGameWindow {
         });
     }
 
+    fn fullscreen_mode(&self) -> FullscreenMode {
+        use winit::window::Fullscreen;
+
+        match self.window.fullscreen() {
+            Some(Fullscreen::Exclusive(video_mode)) => {
+                FullscreenMode::Exclusive(translate_video_mode(&video_mode))
+            }
+            _ => FullscreenMode::Borderless,
+        }
+    }
+
+    fn set_fullscreen_mode(&mut self, monitor_index: Option<usize>, mode: FullscreenMode) {
+        use winit::window::Fullscreen;
+
+        let monitor = self
+            .monitor_handle(monitor_index)
+            .or_else(|| self.window.current_monitor());
+
+        let exclusive_mode = match mode {
+            FullscreenMode::Borderless => None,
+            FullscreenMode::Exclusive(video_mode) => {
+                let handle = monitor.as_ref().and_then(|monitor| {
+                    monitor
+                        .video_modes()
+                        .find(|handle| translate_video_mode(handle) == video_mode)
+                });
+
+                if handle.is_none() {
+                    log::warn!("{video_mode:?} is not supported, using borderless fullscreen");
+                }
+
+                handle
+            }
+        };
+
+        let fullscreen = match exclusive_mode {
+            Some(handle) => Fullscreen::Exclusive(handle),
+            None => Fullscreen::Borderless(monitor),
+        };
+
+        self.window.set_fullscreen(Some(fullscreen));
+
+        cfg_android!({
+            if let Some(app) = &self.platform_app {
+                android::util::hide_system_bars(app)
+            }
+        });
+    }
+
+    fn monitors(&self) -> Vec<MonitorInfo> {
+        let primary_monitor = self.window.primary_monitor();
+
+        self.window
+            .available_monitors()
+            .enumerate()
+            .map(|(index, monitor)| {
+                let position = monitor.position();
+                let size = monitor.size();
+                let video_modes: Vec<_> = monitor
+                    .video_modes()
+                    .map(|video_mode| translate_video_mode(&video_mode))
+                    .collect();
+
+                let mut refresh_rates = VideoMode::distinct_refresh_rates(&video_modes);
+
+                if refresh_rates.is_empty() {
+                    if let Some(millihertz) = monitor.refresh_rate_millihertz() {
+                        refresh_rates.push(millihertz as f32 / 1000.0);
+                    }
+                }
+
+                MonitorInfo {
+                    index,
+                    name: monitor.name().unwrap_or_default(),
+                    primary: primary_monitor.as_ref() == Some(&monitor),
+                    position: IVec2::new(position.x, position.y),
+                    size: UVec2::new(size.width, size.height),
+                    scale_factor: monitor.scale_factor() as f32,
+                    refresh_rates,
+                    video_modes,
+                }
+            })
+            .collect()
+    }
+
+    fn current_monitor(&self) -> Option<usize> {
+        let current_monitor = self.window.current_monitor()?;
+
+        self.window
+            .available_monitors()
+            .position(|monitor| monitor == current_monitor)
+    }
+
     fn size(&self) -> UVec2 {
         self.size
     }
 
+    fn scale_factor(&self) -> f32 {
+        if cfg_web!() {
+            // sizes are already logical on web, see resized()
+            1.0
+        } else {
+            self.window.scale_factor() as f32
+        }
+    }
+
     fn request_size(&mut self, size: UVec2) {
         let requested_size = PhysicalSize::new(size.x, size.y);
 
@@ -263,6 +527,77 @@ impl GameWindow for WinitGameWindow {
         self.window.set_title(title);
     }
 
+    fn cursor_visible(&self) -> bool {
+        self.cursor_visible
+    }
+
+    fn set_cursor_visible(&mut self, visible: bool) {
+        self.window.set_cursor_visible(visible);
+        self.cursor_visible = visible;
+    }
+
+    fn cursor_grab(&self) -> CursorGrab {
+        self.cursor_grab
+    }
+
+    fn set_cursor_grab(&mut self, grab: CursorGrab) {
+        use winit::window::CursorGrabMode;
+
+        // platforms support either confining or locking, try the requested mode first
+        let modes: &[CursorGrabMode] = match grab {
+            CursorGrab::None => &[CursorGrabMode::None],
+            CursorGrab::Confined => &[CursorGrabMode::Confined, CursorGrabMode::Locked],
+            CursorGrab::Locked => &[CursorGrabMode::Locked, CursorGrabMode::Confined],
+        };
+
+        let mut result = Ok(());
+
+        for &mode in modes {
+            result = self.window.set_cursor_grab(mode);
+
+            if result.is_ok() {
+                break;
+            }
+        }
+
+        match result {
+            Ok(()) => self.cursor_grab = grab,
+            Err(err) => log::warn!("Failed to set cursor grab to {grab:?}: {err}"),
+        }
+    }
+
+    fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.pending_custom_cursor.take();
+        self.window.set_cursor(translate_cursor_icon(icon));
+    }
+
+    fn set_custom_cursor(&mut self, cursor: &CustomCursor) {
+        if !cursor.is_valid() {
+            log::error!("Invalid custom cursor, keeping the current cursor");
+            return;
+        }
+
+        // the hotspot is within the size, so it fits as well
+        let (Ok(width), Ok(height)) = (u16::try_from(cursor.size.x), u16::try_from(cursor.size.y))
+        else {
+            log::error!("Custom cursor size {} is too large", cursor.size);
+            return;
+        };
+
+        let result = winit::window::CustomCursor::from_rgba(
+            cursor.rgba.clone(),
+            width,
+            height,
+            cursor.hotspot.x as u16,
+            cursor.hotspot.y as u16,
+        );
+
+        match result {
+            Ok(source) => self.pending_custom_cursor.set(Some(source)),
+            Err(err) => log::error!("Invalid custom cursor: {err}"),
+        }
+    }
+
     fn clear_color(&self) -> Option<Color> {
         self.clear_color
     }
@@ -0,0 +1,154 @@
+//! Draws thousands of sprites alternating between textures to stress draw call creation.
+//!
+//! `cargo run --release --example sprite_stress -- --headless` runs a fixed number of frames without a window
+//! and logs the average durations.
+
+use framework::logging::*;
+use framework::prelude::*;
+use framework_core::runtime::HeadlessGameLoop;
+use rand::prelude::*;
+
+const SPRITE_COUNT: usize = 10_000;
+const TEXTURE_COUNT: usize = 4;
+const REPORT_INTERVAL: u32 = 60;
+const HEADLESS_FRAMES: u32 = 241;
+
+fn main() -> anyhow::Result<()> {
+    std::panic::set_hook(panic_hook());
+    default_logger::init!();
+
+    let headless = std::env::args().any(|arg| arg == "--headless");
+
+    if headless {
+        Game::<HeadlessGameLoop>::new("Sprite Stress", (800, 600))
+            .with_target_fps(1000)
+            .run(|game_io| MainScene::new(game_io, Some(HEADLESS_FRAMES)))
+    } else {
+        Game::<WinitGameLoop>::new("Sprite Stress", (800, 600))
+            .with_target_fps(1000)
+            .run(|game_io| MainScene::new(game_io, None))
+    }
+}
+
+struct MainScene {
+    sprites: Vec<Sprite>,
+    camera: OrthoCamera,
+    frame_limit: Option<u32>,
+    frames: u32,
+    total_frames: u32,
+    draw_duration: Duration,
+    frame_duration: Duration,
+    total_draw_duration: Duration,
+    next_scene: NextScene,
+}
+
+impl MainScene {
+    fn new(game_io: &mut GameIO, frame_limit: Option<u32>) -> MainScene {
+        let mut camera = OrthoCamera::new(game_io, Vec2::new(800.0, 600.0));
+        camera.set_inverted_y(true);
+
+        // separately loaded textures, so neighboring sprites can't share a draw call
+        let textures: Vec<_> = (0..TEXTURE_COUNT)
+            .map(|_| {
+                Texture::load_from_memory(game_io, include_bytes!("../sprites/sprite.png")).unwrap()
+            })
+            .collect();
+
+        let mut rng = rand::thread_rng();
+        let camera_bounds = camera.bounds();
+
+        let sprites = (0..SPRITE_COUNT)
+            .map(|i| {
+                let mut sprite = Sprite::new(game_io, textures[i % TEXTURE_COUNT].clone());
+
+                sprite.set_position(Vec2::new(
+                    rng.gen_range(camera_bounds.horizontal_range()),
+                    rng.gen_range(camera_bounds.vertical_range()),
+                ));
+
+                sprite.set_rotation(rng.gen_range(0.0..std::f32::consts::PI * 2.0));
+                sprite.set_origin(Vec2::new(0.5, 0.5));
+                sprite.set_size(Vec2::new(16.0, 16.0));
+
+                sprite
+            })
+            .collect();
+
+        MainScene {
+            sprites,
+            camera,
+            frame_limit,
+            frames: 0,
+            total_frames: 0,
+            draw_duration: Duration::ZERO,
+            frame_duration: Duration::ZERO,
+            total_draw_duration: Duration::ZERO,
+            next_scene: NextScene::None,
+        }
+    }
+
+    fn track_timing(&mut self, game_io: &mut GameIO) {
+        if self.total_frames > 0 {
+            // durations are from the previous frame
+            self.draw_duration += game_io.draw_duration();
+            self.frame_duration += game_io.frame_duration();
+            self.total_draw_duration += game_io.draw_duration();
+            self.frames += 1;
+        }
+
+        self.total_frames += 1;
+
+        if self.frames == REPORT_INTERVAL {
+            log::info!(
+                "{SPRITE_COUNT} sprites, avg draw: {:.2?}, avg frame: {:.2?}",
+                self.draw_duration / self.frames,
+                self.frame_duration / self.frames,
+            );
+
+            self.frames = 0;
+            self.draw_duration = Duration::ZERO;
+            self.frame_duration = Duration::ZERO;
+        }
+
+        if Some(self.total_frames) == self.frame_limit {
+            log::info!(
+                "{SPRITE_COUNT} sprites, avg draw over {} frames: {:.2?}",
+                self.total_frames - 1,
+                self.total_draw_duration / (self.total_frames - 1),
+            );
+
+            game_io.quit();
+        }
+    }
+}
+
+impl Scene for MainScene {
+    fn next_scene(&mut self) -> &mut NextScene {
+        &mut self.next_scene
+    }
+
+    fn update(&mut self, game_io: &mut GameIO) {
+        self.track_timing(game_io);
+
+        let a = std::f32::consts::PI / 180.0 * 3.0;
+
+        for sprite in &mut self.sprites {
+            let rotation = sprite.rotation();
+            sprite.set_rotation(rotation + a);
+        }
+    }
+
+    fn draw(&mut self, game_io: &mut GameIO, render_pass: &mut RenderPass) {
+        self.camera.scale_to_window(game_io.window());
+
+        let uniforms = [self.camera.as_binding()];
+        let mut render_queue =
+            SpriteQueue::new_with_default_pipeline(game_io, uniforms).with_inverted_y(true);
+
+        for sprite in &self.sprites {
+            render_queue.draw_sprite(sprite);
+        }
+
+        render_pass.consume_queue(render_queue);
+    }
+}
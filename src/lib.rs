@@ -6,6 +6,8 @@ pub use framework_core::common;
 pub use framework_core::graphics;
 pub use framework_core::graphics::wgpu;
 pub use framework_core::runtime;
+pub use framework_core::storage;
+pub use framework_core::ui;
 pub use input;
 pub use logging;
 pub use math;
@@ -3,6 +3,7 @@ use cfg_macros::*;
 pub use framework_core::async_task::{sleep as async_sleep, AsyncTask, SyncResultAsyncError};
 pub use framework_core::common::*;
 pub use framework_core::graphics::*;
+pub use framework_core::storage::*;
 pub use input::*;
 pub use math::*;
 